@@ -0,0 +1,116 @@
+//! Lexer throughput on a large generated source: `cargo bench --bench lexer`.
+//!
+//! Times `Lexer::tokenize_all`, and compares stepping the interned `DfaTable` with
+//! stepping the string-keyed transitions of `Dfa`, the way states were walked before
+//! they were interned.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use cgk_tubes_if2224::dfa::{Dfa, DfaTable};
+use cgk_tubes_if2224::lexer::Lexer;
+
+const LINES: usize = 20_000;
+const RUNS: u32 = 5;
+
+/// Program of `lines` statements using every kind of token
+fn generated_source(lines: usize) -> String {
+    let mut source = String::from("program Besar;\nvariabel a, b : integer; x : real; s : string;\nmulai\n");
+    for i in 0..lines {
+        source.push_str(&match i % 4 {
+            0 => format!("  a := (a + {}) * b mod 7; {{ baris {} }}\n", i, i),
+            1 => format!("  x := x / {}.25 - 1.5e3;\n", i),
+            2 => format!("  jika (a <= {}) dan tidak (b <> a) maka s := 'teks ''{}''';\n", i, i),
+            _ => format!("  selama b >= {} lakukan b := b - 1; (* akhir *)\n", i),
+        });
+    }
+    source.push_str("  writeln(a)\nselesai.\n");
+    source
+}
+
+/// Fastest of RUNS runs of `run`
+fn fastest(mut run: impl FnMut()) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            run();
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+/// Next state by the transition keys as written in the rules, with the same priority
+/// as `DfaTable::next_state`: exact key, narrowest range or set, then `any`
+fn string_next_state(dfa: &Dfa, state: &str, ch: char) -> Option<String> {
+    let keys = dfa.transitions.get(state)?;
+    if let Some(next_state) = keys.get(&ch.to_string()) {
+        return Some(next_state.clone());
+    }
+
+    let mut best: Option<(usize, &String)> = None;
+    for (key, next_state) in keys {
+        let chars: Vec<char> = key.chars().collect();
+        let width = match chars.as_slice() {
+            [start, '-', end] if *start <= ch && ch <= *end => *end as usize - *start as usize + 1,
+            _ if key != "any" && !key.contains('-') && chars.len() > 1 && chars.contains(&ch) => chars.len(),
+            _ => continue,
+        };
+        if best.is_none_or(|(best_width, _)| width < best_width) {
+            best = Some((width, next_state));
+        }
+    }
+    best.map(|(_, next_state)| next_state.clone()).or_else(|| keys.get("any").cloned())
+}
+
+fn main() {
+    let dfa = Dfa::from_file("dfa_rules.json").expect("dfa_rules.json should load");
+    let source = generated_source(LINES);
+    let chars: Vec<char> = source.chars().collect();
+
+    let mut token_count = 0;
+    let lexing = fastest(|| {
+        let mut lexer = Lexer::new(source.clone(), dfa.clone());
+        token_count = black_box(lexer.tokenize_all()).len();
+        assert!(lexer.errors.is_empty(), "generated source should lex: {:?}", lexer.errors);
+    });
+
+    // Both walks step from the start state and go back to it where no transition exists
+    let table = DfaTable::new(&dfa);
+    let mut interned_steps = 0;
+    let interned = fastest(|| {
+        let mut state = table.start;
+        interned_steps = 0;
+        for &ch in &chars {
+            state = table.next_state(state, ch).unwrap_or(table.start);
+            interned_steps += 1;
+        }
+        black_box(state);
+    });
+    let mut string_steps = 0;
+    let by_string = fastest(|| {
+        let mut state = dfa.start_state.clone();
+        string_steps = 0;
+        for &ch in &chars {
+            state = string_next_state(&dfa, &state, ch).unwrap_or_else(|| dfa.start_state.clone());
+            string_steps += 1;
+        }
+        black_box(state);
+    });
+    assert_eq!(interned_steps, string_steps);
+
+    let megabytes = source.len() as f64 / 1_000_000.0;
+    println!("source: {} lines, {:.1} MB, {} tokens", LINES, megabytes, token_count);
+    println!(
+        "tokenize_all:      {:>8.2?} ({:.1} MB/s, {:.0} tokens/ms)",
+        lexing,
+        megabytes / lexing.as_secs_f64(),
+        token_count as f64 / lexing.as_secs_f64() / 1000.0
+    );
+    println!("interned states:   {:>8.2?}", interned);
+    println!(
+        "string states:     {:>8.2?} ({:.1}x slower)",
+        by_string,
+        by_string.as_secs_f64() / interned.as_secs_f64()
+    );
+}
@@ -1,8 +1,14 @@
+use crate::builtin::BUILTINS;
+use crate::operator::Operator;
+use crate::symbol_table::{FIRST_BUILTIN_INDEX, SymbolTable};
 use crate::types::DataType;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::fmt;
 
 /// AST Node - decorated abstract syntax tree
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "node")]
 pub enum AstNode {
     // Program
     Program {
@@ -13,6 +19,11 @@ pub enum AstNode {
     },
     
     // Declarations
+    LabelDecl {
+        labels: Vec<String>,
+        tab_indices: Vec<usize>,
+    },
+    
     VarDecl {
         names: Vec<String>,
         data_type: DataType,
@@ -70,6 +81,7 @@ pub enum AstNode {
         target: Box<AstNode>,
         value: Box<AstNode>,
         data_type: DataType,
+        by_reference: bool, // Target is a var parameter, the store writes the caller's variable
     },
     
     If {
@@ -78,11 +90,43 @@ pub enum AstNode {
         else_stmt: Option<Box<AstNode>>,
     },
     
+    // If with else-if branches, each as (condition, statement)
+    IfChain {
+        branches: Vec<(AstNode, AstNode)>,
+        else_stmt: Option<Box<AstNode>>,
+    },
+    
+    // Case with branches as (labels, statement)
+    Case {
+        selector: Box<AstNode>,
+        branches: Vec<(Vec<AstNode>, AstNode)>,
+        else_stmt: Option<Box<AstNode>>,
+    },
+    
     While {
         condition: Box<AstNode>,
         body: Box<AstNode>,
     },
     
+    Repeat {
+        body: Vec<AstNode>,
+        condition: Box<AstNode>, // Loop ends once this holds
+    },
+    
+    // Loop control, only valid inside selama/untuk/ulangi
+    Break,
+    Continue,
+    
+    // Statement prefixed by a declared label, the target of pergi_ke
+    Labeled {
+        label: String,
+        statement: Box<AstNode>,
+    },
+    
+    Goto {
+        label: String,
+    },
+    
     For {
         var_name: String,
         start: Box<AstNode>,
@@ -96,18 +140,27 @@ pub enum AstNode {
         name: String,
         args: Vec<AstNode>,
         tab_index: usize,
+        data_type: DataType, // Result type, void for procedures
+    },
+    
+    // write/writeln argument printed right-justified in `width` columns,
+    // a real with `prec` digits after the point
+    WriteArg {
+        expr: Box<AstNode>,
+        width: Option<Box<AstNode>>,
+        prec: Option<Box<AstNode>>,
     },
     
     // Expressions
     BinOp {
-        op: String,
+        op: Operator,
         left: Box<AstNode>,
         right: Box<AstNode>,
         data_type: DataType,
     },
     
     UnaryOp {
-        op: String,
+        op: Operator,
         operand: Box<AstNode>,
         data_type: DataType,
     },
@@ -119,6 +172,30 @@ pub enum AstNode {
         level: usize,
     },
     
+    ArrayAccess {
+        array: Box<AstNode>,
+        index: Box<AstNode>,
+        data_type: DataType,
+    },
+    
+    Deref {
+        pointer: Box<AstNode>,
+        data_type: DataType,
+    },
+    
+    FieldAccess {
+        record: Box<AstNode>,
+        field: String,
+        tab_index: usize, // Field entry in the record's block
+        data_type: DataType,
+    },
+    
+    // Set with elements as (value, upper bound of a range)
+    SetLiteral {
+        elements: Vec<(AstNode, Option<AstNode>)>,
+        data_type: DataType,
+    },
+    
     Literal {
         value: LiteralValue,
         data_type: DataType,
@@ -128,7 +205,8 @@ pub enum AstNode {
     Empty,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", content = "value")]
 pub enum LiteralValue {
     Integer(i64),
     Real(f64),
@@ -149,47 +227,259 @@ impl fmt::Display for LiteralValue {
     }
 }
 
+impl LiteralValue {
+    /// Text write/writeln prints for the value, right-justified in `width` columns,
+    /// a real with `prec` digits after the point
+    pub fn write_text(&self, width: Option<i64>, prec: Option<i64>) -> String {
+        let text = match (self, prec) {
+            (LiteralValue::Real(v), Some(prec)) => format!("{:.*}", usize::try_from(prec).unwrap_or(0), v),
+            (LiteralValue::Char(v), _) => v.to_string(),
+            (LiteralValue::String(v), _) => v.clone(),
+            (value, _) => value.to_string(),
+        };
+        let width = width.and_then(|width| usize::try_from(width).ok()).unwrap_or(0);
+        format!("{:>width$}", text)
+    }
+}
+
 impl fmt::Display for AstNode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.fmt_recursive(f, 0)
+        self.fmt_recursive(f, 0, None)
+    }
+}
+
+/// AST dump with array and record types resolved through the symbol table
+pub struct ResolvedAst<'a> {
+    node: &'a AstNode,
+    symbol_table: &'a SymbolTable,
+}
+
+impl fmt::Display for ResolvedAst<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.node.fmt_recursive(f, 0, Some(self.symbol_table))
     }
 }
 
 impl AstNode {
-    fn fmt_recursive(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+    /// Display that prints `array[1..3] of integer` instead of the atab index
+    pub fn display<'a>(&'a self, symbol_table: &'a SymbolTable) -> ResolvedAst<'a> {
+        ResolvedAst { node: self, symbol_table }
+    }
+
+    /// Direct child nodes in source order
+    pub fn children(&self) -> Vec<&AstNode> {
+        match self {
+            AstNode::Program { declarations, body, .. }
+            | AstNode::ProcDecl { declarations, body, .. }
+            | AstNode::FuncDecl { declarations, body, .. } => {
+                declarations.iter().chain([body.as_ref()]).collect()
+            }
+            AstNode::ConstDecl { value, .. } => vec![value],
+            AstNode::Block { statements, .. } => statements.iter().collect(),
+            AstNode::Assign { target, value, .. } => vec![target, value],
+            AstNode::If { condition, then_stmt, else_stmt } => {
+                [condition.as_ref(), then_stmt].into_iter().chain(else_stmt.as_deref()).collect()
+            }
+            AstNode::IfChain { branches, else_stmt } => branches
+                .iter()
+                .flat_map(|(condition, stmt)| [condition, stmt])
+                .chain(else_stmt.as_deref())
+                .collect(),
+            AstNode::Case { selector, branches, else_stmt } => [selector.as_ref()]
+                .into_iter()
+                .chain(branches.iter().flat_map(|(labels, stmt)| labels.iter().chain([stmt])))
+                .chain(else_stmt.as_deref())
+                .collect(),
+            AstNode::While { condition, body } => vec![condition, body],
+            AstNode::Repeat { body, condition } => body.iter().chain([condition.as_ref()]).collect(),
+            AstNode::For { start, end, body, .. } => vec![start, end, body],
+            AstNode::Labeled { statement, .. } => vec![statement],
+            AstNode::ProcCall { args, .. } => args.iter().collect(),
+            AstNode::WriteArg { expr, width, prec } => {
+                [expr.as_ref()].into_iter().chain(width.as_deref()).chain(prec.as_deref()).collect()
+            }
+            AstNode::BinOp { left, right, .. } => vec![left, right],
+            AstNode::UnaryOp { operand, .. } => vec![operand],
+            AstNode::ArrayAccess { array, index, .. } => vec![array, index],
+            AstNode::Deref { pointer, .. } => vec![pointer],
+            AstNode::FieldAccess { record, .. } => vec![record],
+            AstNode::SetLiteral { elements, .. } => elements
+                .iter()
+                .flat_map(|(value, upper)| [value].into_iter().chain(upper.as_ref()))
+                .collect(),
+            AstNode::LabelDecl { .. }
+            | AstNode::VarDecl { .. }
+            | AstNode::TypeDecl { .. }
+            | AstNode::ParamDecl { .. }
+            | AstNode::Goto { .. }
+            | AstNode::Var { .. }
+            | AstNode::Literal { .. }
+            | AstNode::Break
+            | AstNode::Continue
+            | AstNode::Empty => Vec::new(),
+        }
+    }
+
+    /// Direct child nodes in source order, for rewriting passes
+    pub fn children_mut(&mut self) -> Vec<&mut AstNode> {
+        match self {
+            AstNode::Program { declarations, body, .. }
+            | AstNode::ProcDecl { declarations, body, .. }
+            | AstNode::FuncDecl { declarations, body, .. } => {
+                declarations.iter_mut().chain([body.as_mut()]).collect()
+            }
+            AstNode::ConstDecl { value, .. } => vec![value],
+            AstNode::Block { statements, .. } => statements.iter_mut().collect(),
+            AstNode::Assign { target, value, .. } => vec![target, value],
+            AstNode::If { condition, then_stmt, else_stmt } => {
+                [condition.as_mut(), then_stmt].into_iter().chain(else_stmt.as_deref_mut()).collect()
+            }
+            AstNode::IfChain { branches, else_stmt } => branches
+                .iter_mut()
+                .flat_map(|(condition, stmt)| [condition, stmt])
+                .chain(else_stmt.as_deref_mut())
+                .collect(),
+            AstNode::Case { selector, branches, else_stmt } => [selector.as_mut()]
+                .into_iter()
+                .chain(branches.iter_mut().flat_map(|(labels, stmt)| labels.iter_mut().chain([stmt])))
+                .chain(else_stmt.as_deref_mut())
+                .collect(),
+            AstNode::While { condition, body } => vec![condition, body],
+            AstNode::Repeat { body, condition } => body.iter_mut().chain([condition.as_mut()]).collect(),
+            AstNode::For { start, end, body, .. } => vec![start, end, body],
+            AstNode::Labeled { statement, .. } => vec![statement],
+            AstNode::ProcCall { args, .. } => args.iter_mut().collect(),
+            AstNode::WriteArg { expr, width, prec } => [expr.as_mut()]
+                .into_iter()
+                .chain(width.as_deref_mut())
+                .chain(prec.as_deref_mut())
+                .collect(),
+            AstNode::BinOp { left, right, .. } => vec![left, right],
+            AstNode::UnaryOp { operand, .. } => vec![operand],
+            AstNode::ArrayAccess { array, index, .. } => vec![array, index],
+            AstNode::Deref { pointer, .. } => vec![pointer],
+            AstNode::FieldAccess { record, .. } => vec![record],
+            AstNode::SetLiteral { elements, .. } => elements
+                .iter_mut()
+                .flat_map(|(value, upper)| [value].into_iter().chain(upper.as_mut()))
+                .collect(),
+            AstNode::LabelDecl { .. }
+            | AstNode::VarDecl { .. }
+            | AstNode::TypeDecl { .. }
+            | AstNode::ParamDecl { .. }
+            | AstNode::Goto { .. }
+            | AstNode::Var { .. }
+            | AstNode::Literal { .. }
+            | AstNode::Break
+            | AstNode::Continue
+            | AstNode::Empty => Vec::new(),
+        }
+    }
+
+    /// Declaration nodes of a program or subprogram by declared name. Names declared
+    /// inside a subprogram, its parameters included, are qualified by the enclosing
+    /// subprograms, so `b` declared in `q` inside `p` is keyed `p::q::b`
+    pub fn declarations_index(&self) -> HashMap<String, &AstNode> {
+        let mut index = HashMap::new();
+        self.index_declarations("", &mut index);
+        index
+    }
+
+    fn index_declarations<'a>(&'a self, scope: &str, index: &mut HashMap<String, &'a AstNode>) {
+        let (params, declarations) = match self {
+            AstNode::Program { declarations, .. } => (&[][..], declarations),
+            AstNode::ProcDecl { params, declarations, .. }
+            | AstNode::FuncDecl { params, declarations, .. } => (&params[..], declarations),
+            _ => return,
+        };
+
+        for declaration in params.iter().chain(declarations) {
+            let names = match declaration {
+                AstNode::VarDecl { names, .. } | AstNode::ParamDecl { names, .. } => names.clone(),
+                AstNode::ConstDecl { name, .. }
+                | AstNode::TypeDecl { name, .. }
+                | AstNode::ProcDecl { name, .. }
+                | AstNode::FuncDecl { name, .. } => vec![name.clone()],
+                _ => Vec::new(),
+            };
+            for name in names {
+                let key = format!("{}{}", scope, name);
+                if let AstNode::ProcDecl { .. } | AstNode::FuncDecl { .. } = declaration {
+                    declaration.index_declarations(&format!("{}::", key), index);
+                }
+                index.insert(key, declaration);
+            }
+        }
+    }
+
+    /// Inline text of an assignment target, e.g. `p.sudut[i].x`
+    fn target_text(&self) -> String {
+        match self {
+            AstNode::Var { name, .. } => name.clone(),
+            AstNode::Deref { pointer, .. } => format!("{}^", pointer.target_text()),
+            AstNode::FieldAccess { record, field, .. } => {
+                format!("{}.{}", record.target_text(), field)
+            }
+            AstNode::ArrayAccess { array, index, .. } => {
+                let index_str = match index.as_ref() {
+                    AstNode::Var { name, .. } => name.clone(),
+                    AstNode::Literal { value: LiteralValue::Integer(v), .. } => format!("{}", v),
+                    _ => "?".to_string(),
+                };
+                format!("{}[{}]", array.target_text(), index_str)
+            }
+            _ => "?".to_string(),
+        }
+    }
+
+    fn fmt_recursive(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        indent: usize,
+        table: Option<&SymbolTable>,
+    ) -> fmt::Result {
         let ind = "  ".repeat(indent);
+        // Arrays and records are spelled out when the symbol table is at hand
+        let ty = |data_type: &DataType| match table {
+            Some(table) => table.describe_type(data_type),
+            None => format!("{}", data_type),
+        };
         
         match self {
-            AstNode::Program { name, declarations, body, tab_index } => {
+            AstNode::Program { name, declarations, body, .. } => {
                 writeln!(f, "{}Program(name: '{}')", ind, name)?;
                 if !declarations.is_empty() {
                     writeln!(f, "{}  Declarations", ind)?;
                     for decl in declarations {
-                        decl.fmt_recursive(f, indent + 2)?;
+                        decl.fmt_recursive(f, indent + 2, table)?;
                     }
                 }
                 writeln!(f, "{}  Block", ind)?;
-                body.fmt_recursive(f, indent + 2)?;
+                body.fmt_recursive(f, indent + 2, table)?;
+            }
+            
+            AstNode::LabelDecl { labels, tab_indices } => {
+                writeln!(f, "{}LabelDecl(labels: {:?}, indices: {:?})", ind, labels, tab_indices)?;
             }
             
             AstNode::VarDecl { names, data_type, tab_indices, level } => {
                 // Display single variable per line
                 if let (Some(name), Some(tab_idx)) = (names.first(), tab_indices.first()) {
                     writeln!(f, "{}VarDecl('{}') → tab_index:{}, type:{}, lev:{}", 
-                             ind, name, tab_idx, data_type, level)?;
+                             ind, name, tab_idx, ty(data_type), level)?;
                 }
             }
             
             AstNode::ConstDecl { name, value, data_type, tab_index } => {
                 writeln!(f, "{}ConstDecl(name: '{}', type: {}, tab_index: {})", 
-                         ind, name, data_type, tab_index)?;
+                         ind, name, ty(data_type), tab_index)?;
                 writeln!(f, "{}  Value:", ind)?;
-                value.fmt_recursive(f, indent + 2)?;
+                value.fmt_recursive(f, indent + 2, table)?;
             }
             
             AstNode::TypeDecl { name, type_def, tab_index } => {
                 writeln!(f, "{}TypeDecl(name: '{}', type: {}, tab_index: {})", 
-                         ind, name, type_def, tab_index)?;
+                         ind, name, ty(type_def), tab_index)?;
             }
             
             AstNode::ProcDecl { name, params, declarations, body, tab_index, block_index } => {
@@ -198,56 +488,53 @@ impl AstNode {
                 if !params.is_empty() {
                     writeln!(f, "{}  Parameters:", ind)?;
                     for param in params {
-                        param.fmt_recursive(f, indent + 2)?;
+                        param.fmt_recursive(f, indent + 2, table)?;
                     }
                 }
                 if !declarations.is_empty() {
                     writeln!(f, "{}  Declarations:", ind)?;
                     for decl in declarations {
-                        decl.fmt_recursive(f, indent + 2)?;
+                        decl.fmt_recursive(f, indent + 2, table)?;
                     }
                 }
                 writeln!(f, "{}  Body:", ind)?;
-                body.fmt_recursive(f, indent + 2)?;
+                body.fmt_recursive(f, indent + 2, table)?;
             }
             
             AstNode::FuncDecl { name, params, return_type, declarations, body, tab_index, block_index } => {
                 writeln!(f, "{}FuncDecl(name: '{}', return_type: {}, tab_index: {}, block_index: {})", 
-                         ind, name, return_type, tab_index, block_index)?;
+                         ind, name, ty(return_type), tab_index, block_index)?;
                 if !params.is_empty() {
                     writeln!(f, "{}  Parameters:", ind)?;
                     for param in params {
-                        param.fmt_recursive(f, indent + 2)?;
+                        param.fmt_recursive(f, indent + 2, table)?;
                     }
                 }
                 if !declarations.is_empty() {
                     writeln!(f, "{}  Declarations:", ind)?;
                     for decl in declarations {
-                        decl.fmt_recursive(f, indent + 2)?;
+                        decl.fmt_recursive(f, indent + 2, table)?;
                     }
                 }
                 writeln!(f, "{}  Body:", ind)?;
-                body.fmt_recursive(f, indent + 2)?;
+                body.fmt_recursive(f, indent + 2, table)?;
             }
             
             AstNode::ParamDecl { names, data_type, is_var, tab_indices } => {
                 writeln!(f, "{}ParamDecl(names: {:?}, type: {}, var: {}, indices: {:?})", 
-                         ind, names, data_type, is_var, tab_indices)?;
+                         ind, names, ty(data_type), is_var, tab_indices)?;
             }
             
             AstNode::Block { statements, block_index, level } => {
                 writeln!(f, "{}Block → block_index:{}, lev:{}", ind, block_index, level)?;
                 for stmt in statements {
-                    stmt.fmt_recursive(f, indent + 1)?;
+                    stmt.fmt_recursive(f, indent + 1, table)?;
                 }
             }
             
-            AstNode::Assign { target, value, data_type } => {
+            AstNode::Assign { target, value, data_type, by_reference } => {
                 // Extract target and value for inline display
-                let target_str = match target.as_ref() {
-                    AstNode::Var { name, .. } => name.clone(),
-                    _ => "?".to_string(),
-                };
+                let target_str = target.target_text();
                 let value_str = match value.as_ref() {
                     AstNode::Literal { value: LiteralValue::Integer(v), .. } => format!("{}", v),
                     AstNode::BinOp { op, left, right, .. } => {
@@ -265,46 +552,104 @@ impl AstNode {
                     },
                     _ => "...".to_string(),
                 };
-                writeln!(f, "{}Assign('{}' := {}) → type:{}", ind, target_str, value_str, data_type)?;
+                let store = if *by_reference { ", by_reference" } else { "" };
+                writeln!(f, "{}Assign('{}' := {}) → type:{}{}", ind, target_str, value_str, ty(data_type), store)?;
                 // Show children directly without labels
-                target.fmt_recursive(f, indent + 1)?;
-                value.fmt_recursive(f, indent + 1)?;
+                target.fmt_recursive(f, indent + 1, table)?;
+                value.fmt_recursive(f, indent + 1, table)?;
             }
             
             AstNode::If { condition, then_stmt, else_stmt } => {
                 writeln!(f, "{}If", ind)?;
                 writeln!(f, "{}  Condition:", ind)?;
-                condition.fmt_recursive(f, indent + 2)?;
+                condition.fmt_recursive(f, indent + 2, table)?;
                 writeln!(f, "{}  Then:", ind)?;
-                then_stmt.fmt_recursive(f, indent + 2)?;
+                then_stmt.fmt_recursive(f, indent + 2, table)?;
+                if let Some(else_part) = else_stmt {
+                    writeln!(f, "{}  Else:", ind)?;
+                    else_part.fmt_recursive(f, indent + 2, table)?;
+                }
+            }
+            
+            AstNode::IfChain { branches, else_stmt } => {
+                writeln!(f, "{}IfChain", ind)?;
+                for (i, (condition, stmt)) in branches.iter().enumerate() {
+                    writeln!(f, "{}  Branch {}:", ind, i + 1)?;
+                    writeln!(f, "{}    Condition:", ind)?;
+                    condition.fmt_recursive(f, indent + 3, table)?;
+                    writeln!(f, "{}    Then:", ind)?;
+                    stmt.fmt_recursive(f, indent + 3, table)?;
+                }
+                if let Some(else_part) = else_stmt {
+                    writeln!(f, "{}  Else:", ind)?;
+                    else_part.fmt_recursive(f, indent + 2, table)?;
+                }
+            }
+            
+            AstNode::Case { selector, branches, else_stmt } => {
+                writeln!(f, "{}Case", ind)?;
+                writeln!(f, "{}  Selector:", ind)?;
+                selector.fmt_recursive(f, indent + 2, table)?;
+                for (i, (labels, stmt)) in branches.iter().enumerate() {
+                    writeln!(f, "{}  Branch {}:", ind, i + 1)?;
+                    writeln!(f, "{}    Labels:", ind)?;
+                    for label in labels {
+                        label.fmt_recursive(f, indent + 3, table)?;
+                    }
+                    writeln!(f, "{}    Then:", ind)?;
+                    stmt.fmt_recursive(f, indent + 3, table)?;
+                }
                 if let Some(else_part) = else_stmt {
                     writeln!(f, "{}  Else:", ind)?;
-                    else_part.fmt_recursive(f, indent + 2)?;
+                    else_part.fmt_recursive(f, indent + 2, table)?;
                 }
             }
             
             AstNode::While { condition, body } => {
                 writeln!(f, "{}While", ind)?;
                 writeln!(f, "{}  Condition:", ind)?;
-                condition.fmt_recursive(f, indent + 2)?;
+                condition.fmt_recursive(f, indent + 2, table)?;
                 writeln!(f, "{}  Body:", ind)?;
-                body.fmt_recursive(f, indent + 2)?;
+                body.fmt_recursive(f, indent + 2, table)?;
             }
             
+            AstNode::Repeat { body, condition } => {
+                writeln!(f, "{}Repeat", ind)?;
+                writeln!(f, "{}  Body:", ind)?;
+                for stmt in body {
+                    stmt.fmt_recursive(f, indent + 2, table)?;
+                }
+                writeln!(f, "{}  Until:", ind)?;
+                condition.fmt_recursive(f, indent + 2, table)?;
+            }
+            
+            AstNode::Break => writeln!(f, "{}Break", ind)?,
+            
+            AstNode::Continue => writeln!(f, "{}Continue", ind)?,
+            
+            AstNode::Labeled { label, statement } => {
+                writeln!(f, "{}Label({})", ind, label)?;
+                statement.fmt_recursive(f, indent + 1, table)?;
+            }
+            
+            AstNode::Goto { label } => writeln!(f, "{}Goto({})", ind, label)?,
+            
             AstNode::For { var_name, start, end, is_downto, body, tab_index } => {
                 writeln!(f, "{}For(var: '{}', downto: {}, tab_index: {})", 
                          ind, var_name, is_downto, tab_index)?;
                 writeln!(f, "{}  Start:", ind)?;
-                start.fmt_recursive(f, indent + 2)?;
+                start.fmt_recursive(f, indent + 2, table)?;
                 writeln!(f, "{}  End:", ind)?;
-                end.fmt_recursive(f, indent + 2)?;
+                end.fmt_recursive(f, indent + 2, table)?;
                 writeln!(f, "{}  Body:", ind)?;
-                body.fmt_recursive(f, indent + 2)?;
+                body.fmt_recursive(f, indent + 2, table)?;
             }
             
-            AstNode::ProcCall { name, args, tab_index } => {
-                // Predefined procedures are at indices 29-32
-                let predefined_marker = if *tab_index >= 29 && *tab_index <= 32 {
+            AstNode::ProcCall { name, tab_index, .. } => {
+                // Predefined procedures and functions come right after the reserved words
+                let predefined_marker = if (FIRST_BUILTIN_INDEX..FIRST_BUILTIN_INDEX + BUILTINS.len())
+                    .contains(tab_index)
+                {
                     " → predefined"
                 } else {
                     ""
@@ -312,27 +657,74 @@ impl AstNode {
                 writeln!(f, "{}{}(...){}, tab_index:{}", ind, name, predefined_marker, tab_index)?;
             }
             
+            AstNode::WriteArg { expr, width, prec } => {
+                writeln!(f, "{}WriteArg", ind)?;
+                writeln!(f, "{}  Value:", ind)?;
+                expr.fmt_recursive(f, indent + 2, table)?;
+                if let Some(width) = width {
+                    writeln!(f, "{}  Width:", ind)?;
+                    width.fmt_recursive(f, indent + 2, table)?;
+                }
+                if let Some(prec) = prec {
+                    writeln!(f, "{}  Precision:", ind)?;
+                    prec.fmt_recursive(f, indent + 2, table)?;
+                }
+            }
+            
             AstNode::BinOp { op, left, right, data_type } => {
-                writeln!(f, "{}BinOp(op: '{}', type: {})", ind, op, data_type)?;
+                writeln!(f, "{}BinOp(op: '{}', type: {})", ind, op, ty(data_type))?;
                 writeln!(f, "{}  Left:", ind)?;
-                left.fmt_recursive(f, indent + 2)?;
+                left.fmt_recursive(f, indent + 2, table)?;
                 writeln!(f, "{}  Right:", ind)?;
-                right.fmt_recursive(f, indent + 2)?;
+                right.fmt_recursive(f, indent + 2, table)?;
             }
             
             AstNode::UnaryOp { op, operand, data_type } => {
-                writeln!(f, "{}UnaryOp(op: '{}', type: {})", ind, op, data_type)?;
+                writeln!(f, "{}UnaryOp(op: '{}', type: {})", ind, op, ty(data_type))?;
                 writeln!(f, "{}  Operand:", ind)?;
-                operand.fmt_recursive(f, indent + 2)?;
+                operand.fmt_recursive(f, indent + 2, table)?;
             }
             
             AstNode::Var { name, data_type, tab_index, level } => {
                 writeln!(f, "{}Var(name: '{}', type: {}, tab_index: {}, level: {})", 
-                         ind, name, data_type, tab_index, level)?;
+                         ind, name, ty(data_type), tab_index, level)?;
+            }
+            
+            AstNode::ArrayAccess { array, index, data_type } => {
+                writeln!(f, "{}ArrayAccess(type: {})", ind, ty(data_type))?;
+                writeln!(f, "{}  Array:", ind)?;
+                array.fmt_recursive(f, indent + 2, table)?;
+                writeln!(f, "{}  Index:", ind)?;
+                index.fmt_recursive(f, indent + 2, table)?;
+            }
+            
+            AstNode::Deref { pointer, data_type } => {
+                writeln!(f, "{}Deref(type: {})", ind, ty(data_type))?;
+                pointer.fmt_recursive(f, indent + 1, table)?;
+            }
+            
+            AstNode::FieldAccess { record, field, tab_index, data_type } => {
+                writeln!(f, "{}FieldAccess(field: '{}', type: {}, tab_index: {})",
+                         ind, field, ty(data_type), tab_index)?;
+                record.fmt_recursive(f, indent + 1, table)?;
+            }
+            
+            AstNode::SetLiteral { elements, data_type } => {
+                writeln!(f, "{}SetLiteral(type: {})", ind, ty(data_type))?;
+                for (value, upper) in elements {
+                    match upper {
+                        Some(upper) => {
+                            writeln!(f, "{}  Range:", ind)?;
+                            value.fmt_recursive(f, indent + 2, table)?;
+                            upper.fmt_recursive(f, indent + 2, table)?;
+                        }
+                        None => value.fmt_recursive(f, indent + 1, table)?,
+                    }
+                }
             }
             
             AstNode::Literal { value, data_type } => {
-                writeln!(f, "{}Literal(value: {}, type: {})", ind, value, data_type)?;
+                writeln!(f, "{}Literal(value: {}, type: {})", ind, value, ty(data_type))?;
             }
             
             AstNode::Empty => {
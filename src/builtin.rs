@@ -0,0 +1,231 @@
+use crate::types::DataType;
+
+/// Number of arguments a built-in accepts
+#[derive(Debug)]
+pub enum Arity {
+    Exact(usize),
+    AtMost(usize),
+    Variadic,
+}
+
+/// What each argument of a built-in must be
+#[derive(Debug)]
+pub enum ArgKind {
+    Any,
+    Numeric,
+    Integer,
+    Ordinal,
+    Text,
+    Pointer, // Must also be a variable, new and dispose store into it
+}
+
+/// How the result type of a built-in call is decided
+#[derive(Debug)]
+pub enum ResultType {
+    Fixed(DataType), // Void for procedures
+    SameAsArgument,  // abs(2) is integer, abs(2.5) is real
+}
+
+/// Signature of a predefined procedure or function
+#[derive(Debug)]
+pub struct Builtin {
+    pub name: &'static str,
+    pub arity: Arity,
+    pub arg_kind: ArgKind,
+    pub result: ResultType,
+}
+
+/// Built-ins in symbol table order, starting at tab index 29
+pub const BUILTINS: &[Builtin] = &[
+    Builtin {
+        name: "writeln",
+        arity: Arity::Variadic,
+        arg_kind: ArgKind::Any,
+        result: ResultType::Fixed(DataType::Void),
+    },
+    Builtin {
+        name: "write",
+        arity: Arity::Variadic,
+        arg_kind: ArgKind::Any,
+        result: ResultType::Fixed(DataType::Void),
+    },
+    Builtin {
+        name: "readln",
+        arity: Arity::Variadic,
+        arg_kind: ArgKind::Any,
+        result: ResultType::Fixed(DataType::Void),
+    },
+    Builtin {
+        name: "read",
+        arity: Arity::Variadic,
+        arg_kind: ArgKind::Any,
+        result: ResultType::Fixed(DataType::Void),
+    },
+    Builtin {
+        name: "eof",
+        arity: Arity::AtMost(1),
+        arg_kind: ArgKind::Text,
+        result: ResultType::Fixed(DataType::Boolean),
+    },
+    Builtin {
+        name: "eoln",
+        arity: Arity::AtMost(1),
+        arg_kind: ArgKind::Text,
+        result: ResultType::Fixed(DataType::Boolean),
+    },
+    Builtin {
+        name: "abs",
+        arity: Arity::Exact(1),
+        arg_kind: ArgKind::Numeric,
+        result: ResultType::SameAsArgument,
+    },
+    Builtin {
+        name: "sqr",
+        arity: Arity::Exact(1),
+        arg_kind: ArgKind::Numeric,
+        result: ResultType::SameAsArgument,
+    },
+    Builtin {
+        name: "sqrt",
+        arity: Arity::Exact(1),
+        arg_kind: ArgKind::Numeric,
+        result: ResultType::Fixed(DataType::Real),
+    },
+    Builtin {
+        name: "odd",
+        arity: Arity::Exact(1),
+        arg_kind: ArgKind::Integer,
+        result: ResultType::Fixed(DataType::Boolean),
+    },
+    Builtin {
+        name: "ord",
+        arity: Arity::Exact(1),
+        arg_kind: ArgKind::Ordinal,
+        result: ResultType::Fixed(DataType::Integer),
+    },
+    Builtin {
+        name: "chr",
+        arity: Arity::Exact(1),
+        arg_kind: ArgKind::Integer,
+        result: ResultType::Fixed(DataType::Char),
+    },
+    Builtin {
+        name: "succ",
+        arity: Arity::Exact(1),
+        arg_kind: ArgKind::Ordinal,
+        result: ResultType::SameAsArgument,
+    },
+    Builtin {
+        name: "pred",
+        arity: Arity::Exact(1),
+        arg_kind: ArgKind::Ordinal,
+        result: ResultType::SameAsArgument,
+    },
+    Builtin {
+        name: "round",
+        arity: Arity::Exact(1),
+        arg_kind: ArgKind::Numeric,
+        result: ResultType::Fixed(DataType::Integer),
+    },
+    Builtin {
+        name: "trunc",
+        arity: Arity::Exact(1),
+        arg_kind: ArgKind::Numeric,
+        result: ResultType::Fixed(DataType::Integer),
+    },
+    Builtin {
+        name: "new",
+        arity: Arity::Exact(1),
+        arg_kind: ArgKind::Pointer,
+        result: ResultType::Fixed(DataType::Void),
+    },
+    Builtin {
+        name: "dispose",
+        arity: Arity::Exact(1),
+        arg_kind: ArgKind::Pointer,
+        result: ResultType::Fixed(DataType::Void),
+    },
+    // Takes a type or variable, checked and folded to a constant by the analyzer
+    Builtin {
+        name: "ukuran",
+        arity: Arity::Exact(1),
+        arg_kind: ArgKind::Any,
+        result: ResultType::Fixed(DataType::Integer),
+    },
+    // End the program at once, with an optional exit code
+    Builtin {
+        name: "halt",
+        arity: Arity::AtMost(1),
+        arg_kind: ArgKind::Integer,
+        result: ResultType::Fixed(DataType::Void),
+    },
+    Builtin {
+        name: "selesai_program",
+        arity: Arity::AtMost(1),
+        arg_kind: ArgKind::Integer,
+        result: ResultType::Fixed(DataType::Void),
+    },
+];
+
+/// Signature of the built-in with this name
+pub fn find(name: &str) -> Option<&'static Builtin> {
+    BUILTINS.iter().find(|builtin| builtin.name == name)
+}
+
+impl Builtin {
+    /// Whether calls produce a value
+    pub fn is_function(&self) -> bool {
+        !matches!(self.result, ResultType::Fixed(DataType::Void))
+    }
+
+    /// Whether a call never returns, as halt stops the program
+    pub fn ends_program(&self) -> bool {
+        matches!(self.name, "halt" | "selesai_program")
+    }
+
+    /// Declared type of the built-in, unknown when it depends on the argument
+    pub fn data_type(&self) -> DataType {
+        match &self.result {
+            ResultType::Fixed(data_type) => data_type.clone(),
+            ResultType::SameAsArgument => DataType::Unknown,
+        }
+    }
+
+    /// Least and greatest accepted count when `found` arguments do not fit the arity
+    pub fn arity_mismatch(&self, found: usize) -> Option<(usize, Option<usize>)> {
+        match self.arity {
+            Arity::Exact(expected) if found != expected => Some((expected, Some(expected))),
+            Arity::AtMost(limit) if found > limit => Some((0, Some(limit))),
+            _ => None,
+        }
+    }
+}
+
+impl ArgKind {
+    /// Whether an argument of this type is accepted, unknown is accepted to avoid cascades
+    pub fn accepts(&self, data_type: &DataType) -> bool {
+        if *data_type == DataType::Unknown {
+            return true;
+        }
+        match self {
+            ArgKind::Any => true,
+            ArgKind::Numeric => data_type.is_numeric(),
+            ArgKind::Integer => *data_type == DataType::Integer,
+            ArgKind::Ordinal => data_type.is_ordinal(),
+            ArgKind::Text => *data_type == DataType::Text,
+            ArgKind::Pointer => matches!(data_type, DataType::Pointer(_)),
+        }
+    }
+
+    /// Name used in type mismatch errors
+    pub fn describe(&self) -> &'static str {
+        match self {
+            ArgKind::Any => "any",
+            ArgKind::Numeric => "numeric",
+            ArgKind::Integer => "integer",
+            ArgKind::Ordinal => "ordinal",
+            ArgKind::Text => "text",
+            ArgKind::Pointer => "pointer",
+        }
+    }
+}
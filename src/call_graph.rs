@@ -0,0 +1,80 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use crate::ast::AstNode;
+use crate::symbol_table::{FIRST_USER_INDEX, SymbolTable};
+use crate::types::ObjectKind;
+
+/// Which callees appear in the call graph
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CallGraphScope {
+    User, // Only the program's own procedures and functions
+    All,  // Built-ins too
+}
+
+/// Calls between the program and its subprograms, nodes and edges keyed by tab index
+pub struct CallGraph {
+    nodes: BTreeMap<usize, String>,
+    edges: BTreeSet<(usize, usize)>,
+}
+
+impl CallGraph {
+    /// Collect the calls made by the program and by each subprogram body
+    pub fn build(ast: &AstNode, symbol_table: &SymbolTable, scope: CallGraphScope) -> Self {
+        let mut graph = CallGraph { nodes: BTreeMap::new(), edges: BTreeSet::new() };
+        if let AstNode::Program { name, tab_index: Some(tab_index), .. } = ast {
+            graph.nodes.insert(*tab_index, name.clone());
+            graph.visit(ast, *tab_index, symbol_table, scope);
+        }
+        graph
+    }
+
+    fn visit(&mut self, node: &AstNode, caller: usize, symbol_table: &SymbolTable, scope: CallGraphScope) {
+        let mut caller = caller;
+        match node {
+            AstNode::ProcDecl { name, tab_index, .. } | AstNode::FuncDecl { name, tab_index, .. } => {
+                self.nodes.insert(*tab_index, name.clone());
+                caller = *tab_index;
+            }
+            AstNode::ProcCall { name, tab_index, .. } => self.call(caller, name, *tab_index, scope),
+            // A parameterless function is called by naming it
+            AstNode::Var { name, tab_index, .. }
+                if symbol_table.tab[*tab_index].obj == ObjectKind::Function =>
+            {
+                self.call(caller, name, *tab_index, scope);
+            }
+            // Assigning a function's result is not a call
+            AstNode::Assign { target, value, .. } if matches!(target.as_ref(), AstNode::Var { .. }) => {
+                self.visit(value, caller, symbol_table, scope);
+                return;
+            }
+            _ => {}
+        }
+
+        for child in node.children() {
+            self.visit(child, caller, symbol_table, scope);
+        }
+    }
+
+    fn call(&mut self, caller: usize, name: &str, callee: usize, scope: CallGraphScope) {
+        if callee < FIRST_USER_INDEX && scope == CallGraphScope::User {
+            return;
+        }
+        self.nodes.entry(callee).or_insert_with(|| name.to_string());
+        self.edges.insert((caller, callee));
+    }
+
+    /// Graphviz DOT text, built-ins drawn dashed
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph calls {\n");
+        for (tab_index, name) in &self.nodes {
+            let style = if *tab_index < FIRST_USER_INDEX { ", style=dashed" } else { "" };
+            writeln!(dot, "  n{} [label=\"{}\"{}];", tab_index, name, style).unwrap();
+        }
+        for (caller, callee) in &self.edges {
+            writeln!(dot, "  n{} -> n{};", caller, callee).unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
@@ -0,0 +1,262 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::ast::{AstNode, LiteralValue};
+use crate::operator::Operator;
+use crate::symbol_table::{FIRST_USER_INDEX, SymbolTable};
+use crate::types::{DataType, IntWidth, ObjectKind};
+
+/// Why an expression has no compile-time value
+#[derive(Debug, Clone, PartialEq)]
+pub enum NotConstant {
+    Identifier { name: String, kind: ObjectKind }, // Refers to something other than a constant
+    Call(String),
+    Unsupported(&'static str),
+    DivisionByZero,
+    OutOfRange,
+    Erroneous, // The expression already has a reported error
+}
+
+impl fmt::Display for NotConstant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotConstant::Identifier { name, kind } => write!(f, "'{}' is a {}", name, kind),
+            NotConstant::Call(name) => {
+                write!(f, "call to '{}' cannot be evaluated at compile time", name)
+            }
+            NotConstant::Unsupported(what) => {
+                write!(f, "{} cannot be evaluated at compile time", what)
+            }
+            NotConstant::DivisionByZero => write!(f, "division by zero"),
+            NotConstant::OutOfRange => write!(f, "value out of range"),
+            NotConstant::Erroneous => write!(f, "expression has errors"),
+        }
+    }
+}
+
+/// Evaluates decorated expressions at compile time, constants are looked up by tab index
+pub struct ConstEvaluator<'a> {
+    symbol_table: &'a SymbolTable,
+    values: &'a HashMap<usize, LiteralValue>,
+    int_width: IntWidth, // Integer results outside its range overflow
+    short_circuit: bool, // dan/atau skip their right operand once the left decides
+}
+
+impl<'a> ConstEvaluator<'a> {
+    pub fn new(
+        symbol_table: &'a SymbolTable,
+        values: &'a HashMap<usize, LiteralValue>,
+        int_width: IntWidth,
+        short_circuit: bool,
+    ) -> Self {
+        ConstEvaluator { symbol_table, values, int_width, short_circuit }
+    }
+
+    /// Ordinal value of a constant expression ('a' is 97, benar is 1)
+    pub fn evaluate_ordinal(&self, node: &AstNode) -> Result<i64, NotConstant> {
+        match self.evaluate(node)? {
+            LiteralValue::Integer(v) => Ok(v),
+            LiteralValue::Char(c) => Ok(c as i64),
+            LiteralValue::Boolean(b) => Ok(b as i64),
+            _ => Err(NotConstant::Erroneous),
+        }
+    }
+
+    /// Value of a constant expression
+    pub fn evaluate(&self, node: &AstNode) -> Result<LiteralValue, NotConstant> {
+        match node {
+            AstNode::Literal { data_type: DataType::Unknown, .. }
+            | AstNode::UnaryOp { data_type: DataType::Unknown, .. }
+            | AstNode::BinOp { data_type: DataType::Unknown, .. }
+            | AstNode::Var { data_type: DataType::Unknown, .. }
+            | AstNode::ProcCall { data_type: DataType::Unknown, .. } => Err(NotConstant::Erroneous),
+            AstNode::Literal { value, .. } => Ok(value.clone()),
+            AstNode::UnaryOp { op, operand, .. } => {
+                self.fits(unary(*op, self.evaluate(operand)?)?)
+            }
+            AstNode::BinOp { op, left, right, .. } => {
+                let left = self.evaluate(left)?;
+                let right = self.evaluate(right);
+                // salah dan b and benar atau b never compute b, so b cannot fail,
+                // but it must still be a constant expression
+                let decided = matches!(
+                    (op, &left),
+                    (Operator::And, LiteralValue::Boolean(false)) | (Operator::Or, LiteralValue::Boolean(true))
+                );
+                if self.short_circuit
+                    && decided
+                    && matches!(right, Ok(_) | Err(NotConstant::DivisionByZero | NotConstant::OutOfRange))
+                {
+                    return Ok(left);
+                }
+                self.fits(binary(*op, left, right?)?)
+            }
+            AstNode::Var { name, tab_index, .. } => match &self.symbol_table.tab[*tab_index].obj {
+                // A constant without a value had an error in its own declaration
+                ObjectKind::Constant => {
+                    self.values.get(tab_index).cloned().ok_or(NotConstant::Erroneous)
+                }
+                ObjectKind::Function => Err(NotConstant::Call(name.clone())),
+                kind => Err(NotConstant::Identifier { name: name.clone(), kind: kind.clone() }),
+            },
+            AstNode::ProcCall { name, args, tab_index, .. } if *tab_index < FIRST_USER_INDEX => {
+                match args.as_slice() {
+                    [arg] => self.apply_builtin(name, self.evaluate(arg)?),
+                    _ => Err(NotConstant::Call(name.clone())),
+                }
+            }
+            AstNode::ProcCall { name, .. } => Err(NotConstant::Call(name.clone())),
+            // Selecting from a variable names the variable in the error
+            AstNode::ArrayAccess { array: base, .. }
+            | AstNode::FieldAccess { record: base, .. }
+            | AstNode::Deref { pointer: base, .. } => {
+                self.evaluate(base)?;
+                Err(NotConstant::Unsupported("selecting a component"))
+            }
+            AstNode::SetLiteral { .. } => Err(NotConstant::Unsupported("a set")),
+            _ => Err(NotConstant::Erroneous),
+        }
+    }
+
+    /// Result of a one-argument built-in applied to a constant
+    pub fn apply_builtin(&self, name: &str, arg: LiteralValue) -> Result<LiteralValue, NotConstant> {
+        self.fits(builtin(name, arg)?)
+    }
+
+    /// A folded integer must fit the integer type
+    fn fits(&self, value: LiteralValue) -> Result<LiteralValue, NotConstant> {
+        match value {
+            LiteralValue::Integer(v) if !self.int_width.contains(v) => Err(NotConstant::OutOfRange),
+            value => Ok(value),
+        }
+    }
+}
+
+fn unary(op: Operator, value: LiteralValue) -> Result<LiteralValue, NotConstant> {
+    match (op, value) {
+        (Operator::Sub, LiteralValue::Integer(v)) => {
+            v.checked_neg().map(LiteralValue::Integer).ok_or(NotConstant::OutOfRange)
+        }
+        (Operator::Sub, LiteralValue::Real(v)) => Ok(LiteralValue::Real(-v)),
+        (Operator::Add, value @ (LiteralValue::Integer(_) | LiteralValue::Real(_))) => Ok(value),
+        (Operator::Not, LiteralValue::Boolean(b)) => Ok(LiteralValue::Boolean(!b)),
+        _ => Err(NotConstant::Erroneous),
+    }
+}
+
+fn binary(op: Operator, left: LiteralValue, right: LiteralValue) -> Result<LiteralValue, NotConstant> {
+    use LiteralValue::{Boolean, Integer, Real};
+
+    let integer = |value: Option<i64>| value.map(Integer).ok_or(NotConstant::OutOfRange);
+
+    match (op, &left, &right) {
+        (Operator::Add, Integer(a), Integer(b)) => integer(a.checked_add(*b)),
+        (Operator::Sub, Integer(a), Integer(b)) => integer(a.checked_sub(*b)),
+        (Operator::Mul, Integer(a), Integer(b)) => integer(a.checked_mul(*b)),
+        (Operator::IntDiv | Operator::Mod, Integer(_), Integer(0)) => {
+            Err(NotConstant::DivisionByZero)
+        }
+        // Both round toward zero, the sign of mod follows the dividend
+        (Operator::IntDiv, Integer(a), Integer(b)) => integer(a.checked_div(*b)),
+        (Operator::Mod, Integer(a), Integer(b)) => integer(a.checked_rem(*b)),
+        (Operator::And, Boolean(a), Boolean(b)) => Ok(Boolean(*a && *b)),
+        (Operator::Or, Boolean(a), Boolean(b)) => Ok(Boolean(*a || *b)),
+        (Operator::Add | Operator::Sub | Operator::Mul | Operator::RealDiv, _, _) => {
+            let (Some(a), Some(b)) = (real(&left), real(&right)) else {
+                return Err(NotConstant::Erroneous);
+            };
+            match op {
+                Operator::Add => Ok(Real(a + b)),
+                Operator::Sub => Ok(Real(a - b)),
+                Operator::Mul => Ok(Real(a * b)),
+                _ if b == 0.0 => Err(NotConstant::DivisionByZero),
+                _ => Ok(Real(a / b)),
+            }
+        }
+        (Operator::Eq | Operator::Neq | Operator::Lt | Operator::Gt | Operator::Le | Operator::Ge, _, _) => {
+            let ordering = compare(&left, &right).ok_or(NotConstant::Erroneous)?;
+            Ok(Boolean(match op {
+                Operator::Eq => ordering == Ordering::Equal,
+                Operator::Neq => ordering != Ordering::Equal,
+                Operator::Lt => ordering == Ordering::Less,
+                Operator::Gt => ordering == Ordering::Greater,
+                Operator::Le => ordering != Ordering::Greater,
+                _ => ordering != Ordering::Less,
+            }))
+        }
+        (Operator::In, _, _) => Err(NotConstant::Unsupported("a set")),
+        _ => Err(NotConstant::Erroneous),
+    }
+}
+
+/// Numeric value as a real, integers widen
+fn real(value: &LiteralValue) -> Option<f64> {
+    match value {
+        LiteralValue::Integer(v) => Some(*v as f64),
+        LiteralValue::Real(v) => Some(*v),
+        _ => None,
+    }
+}
+
+fn compare(left: &LiteralValue, right: &LiteralValue) -> Option<Ordering> {
+    match (left, right) {
+        (LiteralValue::Integer(a), LiteralValue::Integer(b)) => Some(a.cmp(b)),
+        (LiteralValue::Char(a), LiteralValue::Char(b)) => Some(a.cmp(b)),
+        (LiteralValue::Boolean(a), LiteralValue::Boolean(b)) => Some(a.cmp(b)),
+        (LiteralValue::String(a), LiteralValue::String(b)) => Some(a.cmp(b)),
+        // Only type-checks when char ordinals are enabled
+        (LiteralValue::Char(a), LiteralValue::Integer(b)) => Some((*a as i64).cmp(b)),
+        (LiteralValue::Integer(a), LiteralValue::Char(b)) => Some(a.cmp(&(*b as i64))),
+        _ => real(left)?.partial_cmp(&real(right)?),
+    }
+}
+
+/// Integer value of a whole real, None for NaN, infinities and reals outside i64,
+/// which a cast would quietly turn into 0 or the nearest bound
+fn whole(v: f64) -> Option<i64> {
+    const LIMIT: f64 = 9_223_372_036_854_775_808.0; // 2^63, the first real past i64::MAX
+    (-LIMIT..LIMIT).contains(&v).then_some(v as i64)
+}
+
+/// Built-in functions of one argument that have a value at compile time
+fn builtin(name: &str, arg: LiteralValue) -> Result<LiteralValue, NotConstant> {
+    use LiteralValue::{Boolean, Char, Integer, Real};
+
+    let integer = |value: Option<i64>| value.map(Integer).ok_or(NotConstant::OutOfRange);
+    let char_code = |code: Option<i64>| {
+        code.and_then(|code| u32::try_from(code).ok())
+            .and_then(char::from_u32)
+            .map(Char)
+            .ok_or(NotConstant::OutOfRange)
+    };
+
+    match (name, arg) {
+        // Pascal trunc rounds toward zero, round rounds halves away from zero
+        ("trunc", Real(v)) => integer(whole(v.trunc())),
+        ("round", Real(v)) => integer(whole(v.round())),
+        ("trunc" | "round", Integer(v)) => Ok(Integer(v)),
+        ("abs", Integer(v)) => integer(v.checked_abs()),
+        ("abs", Real(v)) => Ok(Real(v.abs())),
+        ("sqr", Integer(v)) => integer(v.checked_mul(v)),
+        ("sqr", Real(v)) => Ok(Real(v * v)),
+        ("sqrt", value) => match real(&value) {
+            Some(v) if v >= 0.0 => Ok(Real(v.sqrt())),
+            Some(_) => Err(NotConstant::OutOfRange),
+            None => Err(NotConstant::Erroneous),
+        },
+        ("odd", Integer(v)) => Ok(Boolean(v % 2 != 0)),
+        ("ord", Integer(v)) => Ok(Integer(v)),
+        ("ord", Char(c)) => Ok(Integer(c as i64)),
+        ("ord", Boolean(b)) => Ok(Integer(b as i64)),
+        ("chr", Integer(v)) => char_code(Some(v)),
+        ("succ", Integer(v)) => integer(v.checked_add(1)),
+        ("pred", Integer(v)) => integer(v.checked_sub(1)),
+        ("succ", Char(c)) => char_code(Some(c as i64 + 1)),
+        ("pred", Char(c)) => char_code(Some(c as i64 - 1)),
+        ("succ", Boolean(false)) => Ok(Boolean(true)),
+        ("pred", Boolean(true)) => Ok(Boolean(false)),
+        ("succ" | "pred", Boolean(_)) => Err(NotConstant::OutOfRange),
+        (name, _) => Err(NotConstant::Call(name.to_string())),
+    }
+}
@@ -1,20 +1,234 @@
 use serde::Deserialize;
 use std::collections::HashMap;
+use std::io::Read;
+use std::str::FromStr;
 
-#[derive(Deserialize, Debug)]
+use crate::keyword::Keyword;
+use crate::lexer::Lexer;
+use crate::token::TokenType;
+
+#[derive(Deserialize, Debug, Clone)]
 pub struct Dfa {
     pub start_state: String,
     pub keywords: Vec<String>,
     pub word_logical_operators: Vec<String>,
     pub word_arithmetic_operators: Vec<String>,
+    pub word_relational_operators: Vec<String>,
+    #[serde(default)]
+    pub keyword_roles: HashMap<String, String>, // Spelling of a reserved word and the role it plays, e.g. "begin": "BEGIN"
     pub final_states: HashMap<String, String>,
     pub transitions: HashMap<String, HashMap<String, String>>,
+    #[serde(default)]
+    pub examples: HashMap<String, String>, // Source text and the type of the one token it must lex to
 }
 
 impl Dfa {
     pub fn from_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let file_content = std::fs::read_to_string(path)?;
-        let dfa: Dfa = serde_json::from_str(&file_content)?;
+        Dfa::from_reader(std::fs::File::open(path)?)
+    }
+
+    /// Read DFA rules from any stream, e.g. rules embedded with `include_bytes!`
+    pub fn from_reader(mut reader: impl Read) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut json = String::new();
+        reader.read_to_string(&mut json)?;
+        Dfa::from_json(&json)
+    }
+
+    /// Parse DFA rules, rejecting final states whose token type the lexer does not know,
+    /// unknown keyword roles and reversed ranges
+    pub fn from_json(json: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let dfa: Dfa = serde_json::from_str(json)?;
+
+        let mut unknown: Vec<String> = dfa
+            .final_states
+            .iter()
+            .filter(|(_, token_type)| TokenType::from_dfa_name(token_type).is_none())
+            .map(|(state, token_type)| format!("{} -> {}", state, token_type))
+            .collect();
+        if !unknown.is_empty() {
+            unknown.sort();
+            return Err(format!("Unknown token type in final_states: {}", unknown.join(", ")).into());
+        }
+
+        let mut unknown: Vec<String> = dfa
+            .keyword_roles
+            .iter()
+            .filter(|(_, role)| Keyword::from_name(role).is_none())
+            .map(|(spelling, role)| format!("{} -> {}", spelling, role))
+            .collect();
+        if !unknown.is_empty() {
+            unknown.sort();
+            return Err(format!("Unknown role in keyword_roles: {}", unknown.join(", ")).into());
+        }
+
+        // A reversed range such as "z-a" matches no character, it is a typo in the rules
+        let mut reversed: Vec<String> = dfa
+            .transitions
+            .iter()
+            .flat_map(|(state, keys)| keys.keys().map(move |key| (state, key)))
+            .filter(|(_, key)| {
+                matches!(key.chars().collect::<Vec<_>>().as_slice(), [start, '-', end] if start > end)
+            })
+            .map(|(state, key)| format!("{} on {}", state, key))
+            .collect();
+        if !reversed.is_empty() {
+            reversed.sort();
+            return Err(format!("Reversed range in transitions: {}", reversed.join(", ")).into());
+        }
+
         Ok(dfa)
     }
+
+    /// Lex every example on its own and check it yields exactly one token of the
+    /// expected type, one message per example that does not
+    pub fn self_test(&self) -> Result<(), Vec<String>> {
+        let mut examples: Vec<_> = self.examples.iter().collect();
+        examples.sort();
+
+        let mut failures = Vec::new();
+        for (source, expected) in examples {
+            let mut lexer = Lexer::new(source.clone(), self.clone());
+            let tokens: Vec<_> = std::iter::from_fn(|| lexer.get_next_token()).collect();
+
+            let found = match (lexer.errors.first(), tokens.as_slice()) {
+                (Some(error), _) => format!("an error: {}", error.message),
+                (None, [token]) if token.token_type.name() == expected => continue,
+                (None, [token]) => token.token_type.name().to_string(),
+                (None, tokens) => format!("{} tokens", tokens.len()),
+            };
+            failures.push(format!("{:?}: expected {}, found {}", source, expected, found));
+        }
+
+        if failures.is_empty() { Ok(()) } else { Err(failures) }
+    }
+}
+
+/// `"...".parse::<Dfa>()`, the same as `Dfa::from_json`
+impl FromStr for Dfa {
+    type Err = Box<dyn std::error::Error>;
+
+    fn from_str(json: &str) -> Result<Self, Self::Err> {
+        Dfa::from_json(json)
+    }
+}
+
+/// Character class of a non-exact transition key
+#[derive(Debug)]
+enum CharClass {
+    Range(char, char),
+    Set(Vec<char>),
+}
+
+impl CharClass {
+    fn contains(&self, ch: char) -> bool {
+        match self {
+            CharClass::Range(start, end) => *start <= ch && ch <= *end,
+            CharClass::Set(chars) => chars.contains(&ch),
+        }
+    }
+}
+
+/// Transitions out of one state, with classes ordered by matching priority
+#[derive(Debug, Default)]
+struct StateTransitions {
+    exact: HashMap<char, u32>,
+    classes: Vec<(CharClass, u32)>,
+    any: Option<u32>,
+}
+
+/// DFA with states interned as integer ids, so stepping does no string work
+#[derive(Debug)]
+pub struct DfaTable {
+    pub start: u32,
+    final_types: Vec<Option<String>>,
+    transitions: Vec<StateTransitions>,
+}
+
+impl DfaTable {
+    pub fn new(dfa: &Dfa) -> Self {
+        let mut table = DfaTable {
+            start: 0,
+            final_types: Vec::new(),
+            transitions: Vec::new(),
+        };
+        let mut ids = HashMap::new();
+
+        table.start = table.intern(&mut ids, &dfa.start_state);
+
+        // Sort so interning (and thus ids) do not depend on HashMap iteration order
+        let mut states: Vec<_> = dfa.transitions.iter().collect();
+        states.sort_by(|a, b| a.0.cmp(b.0));
+
+        for (state, keys) in states {
+            let from = table.intern(&mut ids, state);
+            let mut keys: Vec<_> = keys.iter().collect();
+            keys.sort_by(|a, b| a.0.cmp(b.0));
+
+            let mut ranked = Vec::new();
+            for (key, next_state) in keys {
+                let to = table.intern(&mut ids, next_state);
+                let chars: Vec<char> = key.chars().collect();
+                let transitions = &mut table.transitions[from as usize];
+
+                match chars.as_slice() {
+                    _ if key == "any" => transitions.any = Some(to),
+                    [ch] => {
+                        transitions.exact.insert(*ch, to);
+                    }
+                    [start, '-', end] if start <= end => {
+                        let width = *end as u32 - *start as u32 + 1;
+                        ranked.push((width, CharClass::Range(*start, *end), to));
+                    }
+                    _ if !key.contains('-') => {
+                        ranked.push((chars.len() as u32, CharClass::Set(chars), to));
+                    }
+                    _ => {}
+                }
+            }
+
+            // The narrowest class wins; the stable sort keeps key order for ties
+            ranked.sort_by_key(|(width, _, _)| *width);
+            table.transitions[from as usize].classes =
+                ranked.into_iter().map(|(_, class, to)| (class, to)).collect();
+        }
+
+        for (state, token_type) in &dfa.final_states {
+            let id = table.intern(&mut ids, state);
+            table.final_types[id as usize] = Some(token_type.clone());
+        }
+
+        table
+    }
+
+    fn intern(&mut self, ids: &mut HashMap<String, u32>, state: &str) -> u32 {
+        if let Some(&id) = ids.get(state) {
+            return id;
+        }
+        let id = self.transitions.len() as u32;
+        ids.insert(state.to_string(), id);
+        self.final_types.push(None);
+        self.transitions.push(StateTransitions::default());
+        id
+    }
+
+    /// Next state on `ch`: exact key first, then the narrowest range or set, then `any`
+    pub fn next_state(&self, state: u32, ch: char) -> Option<u32> {
+        let transitions = &self.transitions[state as usize];
+
+        if let Some(&next_state) = transitions.exact.get(&ch) {
+            return Some(next_state);
+        }
+
+        transitions
+            .classes
+            .iter()
+            .find(|(class, _)| class.contains(ch))
+            .map(|(_, next_state)| *next_state)
+            .or(transitions.any)
+    }
+
+    /// Token type name if `state` is final
+    pub fn final_type(&self, state: u32) -> Option<&str> {
+        self.final_types[state as usize].as_deref()
+    }
 }
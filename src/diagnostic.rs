@@ -0,0 +1,74 @@
+use crate::lexer::LexError;
+use crate::parser::{ParseError, ParseWarning};
+use crate::semantic_error::{SemanticError, SemanticWarning};
+use serde::Serialize;
+
+/// Phase-independent diagnostic for machine-readable error output
+#[derive(Debug, Serialize)]
+pub struct Diagnostic {
+    pub phase: &'static str,
+    pub severity: &'static str,
+    pub message: String,
+    pub line: Option<usize>,
+    pub col: Option<usize>,
+}
+
+impl From<&LexError> for Diagnostic {
+    fn from(error: &LexError) -> Self {
+        Diagnostic {
+            phase: "lexer",
+            severity: "error",
+            message: error.message.clone(),
+            line: Some(error.line),
+            col: Some(error.column),
+        }
+    }
+}
+
+impl From<&ParseError> for Diagnostic {
+    fn from(error: &ParseError) -> Self {
+        Diagnostic {
+            phase: "parser",
+            severity: "error",
+            message: format!("{} (found {})", error.message, error.token),
+            line: Some(error.token.line),
+            col: Some(error.token.column),
+        }
+    }
+}
+
+impl From<&ParseWarning> for Diagnostic {
+    fn from(warning: &ParseWarning) -> Self {
+        Diagnostic {
+            phase: "parser",
+            severity: "warning",
+            message: format!("{} (found {})", warning.message, warning.token),
+            line: Some(warning.token.line),
+            col: Some(warning.token.column),
+        }
+    }
+}
+
+impl From<&SemanticError> for Diagnostic {
+    fn from(error: &SemanticError) -> Self {
+        Diagnostic {
+            phase: "semantic",
+            severity: "error",
+            message: error.message.clone(),
+            line: error.token.as_ref().map(|token| token.line),
+            col: error.token.as_ref().map(|token| token.column),
+        }
+    }
+}
+
+impl From<&SemanticWarning> for Diagnostic {
+    fn from(warning: &SemanticWarning) -> Self {
+        Diagnostic {
+            phase: "semantic",
+            severity: "warning",
+            message: warning.message.clone(),
+            line: warning.token.as_ref().map(|token| token.line),
+            col: warning.token.as_ref().map(|token| token.column),
+        }
+    }
+}
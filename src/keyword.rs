@@ -0,0 +1,225 @@
+use crate::dfa::Dfa;
+use std::collections::HashMap;
+
+/// Role a reserved word plays in the grammar, independent of how the DFA spells it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Keyword {
+    Program,
+    Label,
+    Const,
+    Type,
+    Var,
+    Procedure,
+    Function,
+    Begin,
+    End,
+    If,
+    Then,
+    Else,
+    While,
+    Do,
+    For,
+    To,
+    Downto,
+    Repeat,
+    Until,
+    Case,
+    Of,
+    Break,
+    Continue,
+    Goto,
+    Array,
+    Record,
+    Set,
+    Packed,
+    String,
+    Integer,
+    Real,
+    Boolean,
+    Char,
+    True,
+    False,
+    And,
+    Or,
+    Not,
+    Div,
+    Mod,
+    In,
+}
+
+/// Spellings used when the DFA rules carry no `keyword_roles`, the role's
+/// first spelling is the one diagnostics use
+const DEFAULT_SPELLINGS: &[(&str, Keyword)] = &[
+    ("program", Keyword::Program),
+    ("label", Keyword::Label),
+    ("konstanta", Keyword::Const),
+    ("tipe", Keyword::Type),
+    ("variabel", Keyword::Var),
+    ("prosedur", Keyword::Procedure),
+    ("fungsi", Keyword::Function),
+    ("mulai", Keyword::Begin),
+    ("selesai", Keyword::End),
+    ("jika", Keyword::If),
+    ("maka", Keyword::Then),
+    ("selain_itu", Keyword::Else),
+    ("selama", Keyword::While),
+    ("lakukan", Keyword::Do),
+    ("untuk", Keyword::For),
+    ("ke", Keyword::To),
+    ("turun_ke", Keyword::Downto),
+    ("ulangi", Keyword::Repeat),
+    ("sampai", Keyword::Until),
+    ("kasus", Keyword::Case),
+    ("dari", Keyword::Of),
+    ("keluar", Keyword::Break),
+    ("lanjut", Keyword::Continue),
+    ("pergi_ke", Keyword::Goto),
+    ("larik", Keyword::Array),
+    ("rekaman", Keyword::Record),
+    ("himpunan", Keyword::Set),
+    ("padat", Keyword::Packed),
+    ("string", Keyword::String),
+    ("integer", Keyword::Integer),
+    ("real", Keyword::Real),
+    ("boolean", Keyword::Boolean),
+    ("char", Keyword::Char),
+    ("benar", Keyword::True),
+    ("true", Keyword::True),
+    ("salah", Keyword::False),
+    ("false", Keyword::False),
+    ("dan", Keyword::And),
+    ("atau", Keyword::Or),
+    ("tidak", Keyword::Not),
+    ("bagi", Keyword::Div),
+    ("mod", Keyword::Mod),
+    ("dalam", Keyword::In),
+];
+
+impl Keyword {
+    /// Spelling of the role when the DFA rules carry no `keyword_roles`
+    pub fn default_spelling(self) -> &'static str {
+        DEFAULT_SPELLINGS
+            .iter()
+            .find(|(_, role)| *role == self)
+            .map(|(spelling, _)| *spelling)
+            .expect("every role has a default spelling")
+    }
+
+    /// Role named in the `keyword_roles` of the DFA rules
+    pub fn from_name(name: &str) -> Option<Keyword> {
+        let keyword = match name {
+            "PROGRAM" => Keyword::Program,
+            "LABEL" => Keyword::Label,
+            "CONST" => Keyword::Const,
+            "TYPE" => Keyword::Type,
+            "VAR" => Keyword::Var,
+            "PROCEDURE" => Keyword::Procedure,
+            "FUNCTION" => Keyword::Function,
+            "BEGIN" => Keyword::Begin,
+            "END" => Keyword::End,
+            "IF" => Keyword::If,
+            "THEN" => Keyword::Then,
+            "ELSE" => Keyword::Else,
+            "WHILE" => Keyword::While,
+            "DO" => Keyword::Do,
+            "FOR" => Keyword::For,
+            "TO" => Keyword::To,
+            "DOWNTO" => Keyword::Downto,
+            "REPEAT" => Keyword::Repeat,
+            "UNTIL" => Keyword::Until,
+            "CASE" => Keyword::Case,
+            "OF" => Keyword::Of,
+            "BREAK" => Keyword::Break,
+            "CONTINUE" => Keyword::Continue,
+            "GOTO" => Keyword::Goto,
+            "ARRAY" => Keyword::Array,
+            "RECORD" => Keyword::Record,
+            "SET" => Keyword::Set,
+            "PACKED" => Keyword::Packed,
+            "STRING" => Keyword::String,
+            "INTEGER" => Keyword::Integer,
+            "REAL" => Keyword::Real,
+            "BOOLEAN" => Keyword::Boolean,
+            "CHAR" => Keyword::Char,
+            "TRUE" => Keyword::True,
+            "FALSE" => Keyword::False,
+            "AND" => Keyword::And,
+            "OR" => Keyword::Or,
+            "NOT" => Keyword::Not,
+            "DIV" => Keyword::Div,
+            "MOD" => Keyword::Mod,
+            "IN" => Keyword::In,
+            _ => return None,
+        };
+        Some(keyword)
+    }
+}
+
+/// Spelling of every keyword role, taken from the DFA rules
+#[derive(Debug, Clone)]
+pub struct KeywordSet {
+    roles: HashMap<String, Keyword>,
+    spellings: HashMap<Keyword, String>,
+}
+
+impl Default for KeywordSet {
+    fn default() -> Self {
+        KeywordSet::from_spellings(
+            DEFAULT_SPELLINGS.iter().map(|(spelling, role)| (spelling.to_string(), *role)),
+        )
+    }
+}
+
+impl KeywordSet {
+    /// Roles from the `keyword_roles` of the rules, in the order the words are listed
+    /// there. Rules without them use the default spellings, and a role the rules
+    /// leave out keeps its default spelling for the symbol table and diagnostics
+    pub fn from_dfa(dfa: &Dfa) -> Self {
+        if dfa.keyword_roles.is_empty() {
+            return KeywordSet::default();
+        }
+
+        let listed: Vec<&String> = dfa
+            .keywords
+            .iter()
+            .chain(&dfa.word_logical_operators)
+            .chain(&dfa.word_arithmetic_operators)
+            .chain(&dfa.word_relational_operators)
+            .collect();
+        // Roles for words the lexer does not reserve, such as `packed`, come last
+        let mut unlisted: Vec<&String> =
+            dfa.keyword_roles.keys().filter(|spelling| !listed.contains(spelling)).collect();
+        unlisted.sort();
+
+        let mut set = KeywordSet::from_spellings(listed.into_iter().chain(unlisted).filter_map(
+            |spelling| {
+                let role = Keyword::from_name(dfa.keyword_roles.get(spelling)?)?;
+                Some((spelling.clone(), role))
+            },
+        ));
+        for (spelling, role) in DEFAULT_SPELLINGS {
+            set.spellings.entry(*role).or_insert_with(|| spelling.to_string());
+        }
+        set
+    }
+
+    fn from_spellings(spellings: impl IntoIterator<Item = (String, Keyword)>) -> Self {
+        let mut set = KeywordSet { roles: HashMap::new(), spellings: HashMap::new() };
+        for (spelling, role) in spellings {
+            set.spellings.entry(role).or_insert_with(|| spelling.clone());
+            set.roles.insert(spelling, role);
+        }
+        set
+    }
+
+    /// Role of a reserved word or word operator, None for any other word
+    pub fn role(&self, spelling: &str) -> Option<Keyword> {
+        self.roles.get(spelling).copied()
+    }
+
+    /// First spelling of a role, the one the symbol table and diagnostics show
+    pub fn spelling(&self, role: Keyword) -> &str {
+        // Every role has a spelling, from the rules or the defaults
+        &self.spellings[&role]
+    }
+}
@@ -1,129 +1,285 @@
-use crate::{dfa::Dfa, token::{Token, TokenType}};
+use crate::{dfa::{Dfa, DfaTable}, keyword::KeywordSet, token::{Token, TokenType}};
+use std::fmt;
+
+/// Longest token accepted unless the lexer is configured otherwise
+pub const DEFAULT_MAX_TOKEN_LENGTH: usize = 4096;
+
+/// Error for input the DFA cannot turn into a token
+#[derive(Debug, Clone)]
+pub struct LexError {
+    pub message: String,
+    pub prefix: String, // Text read from the start of the bad token up to where lexing got stuck
+    pub position: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Error: {} at position {}", self.message, self.position)
+    }
+}
 
 pub struct Lexer {
     source: Vec<char>,
     dfa: Dfa,
+    table: DfaTable,
+    keywords: KeywordSet,
     position: usize,
+    line_starts: Vec<usize>, // Position of the first character of each line
+    byte_cursor: (usize, usize), // A position and its byte offset, tokens are read front to back
+    pub max_token_length: usize, // Longer tokens are reported and skipped, not built
+    pub errors: Vec<LexError>,
 }
 
 impl Lexer {
     pub fn new(source: String, dfa: Dfa) -> Self {
-        Lexer { source: source.chars().collect(), dfa, position: 0 }
-    }
+        let source: Vec<char> = source.chars().collect();
+        let line_starts = std::iter::once(0)
+            .chain(source.iter().enumerate().filter(|(_, c)| **c == '\n').map(|(i, _)| i + 1))
+            .collect();
 
-    pub fn get_next_token(&mut self) -> Option<Token> {
-        while self.position < self.source.len() && self.source[self.position].is_whitespace() {
-            self.position += 1;
-        }
+        let table = DfaTable::new(&dfa);
+        let keywords = KeywordSet::from_dfa(&dfa);
 
-        if self.position >= self.source.len() {
-            return None;
+        Lexer {
+            source,
+            dfa,
+            table,
+            keywords,
+            position: 0,
+            line_starts,
+            byte_cursor: (0, 0),
+            max_token_length: DEFAULT_MAX_TOKEN_LENGTH,
+            errors: Vec::new(),
         }
+    }
 
-        let mut current_state = self.dfa.start_state.clone();
-        let start_pos = self.position;
-        let mut last_final_state: Option<(String, usize)> = None;
+    /// Line and column (both 1-based) of a position in the source
+    fn location(&self, position: usize) -> (usize, usize) {
+        let line = self.line_starts.partition_point(|&start| start <= position);
+        (line, position - self.line_starts[line - 1] + 1)
+    }
 
-        while self.position < self.source.len() {
-            let current_char = self.source[self.position];
+    /// Byte offset of a position at or after the last one asked for
+    fn byte_offset(&mut self, position: usize) -> usize {
+        let (from, offset) = self.byte_cursor;
+        let offset = offset + self.source[from..position].iter().map(|c| c.len_utf8()).sum::<usize>();
+        self.byte_cursor = (position, offset);
+        offset
+    }
 
-            if let Some(next_state) = self.get_next_state(&current_state, current_char) {
-                current_state = next_state;
+    /// All remaining tokens in order, with errors collected as get_next_token does
+    pub fn tokenize_all(&mut self) -> Vec<Token> {
+        // A token and the space after it average a few characters
+        let mut tokens = Vec::with_capacity((self.source.len() - self.position) / 4);
+        while let Some(token) = self.get_next_token() {
+            tokens.push(token);
+        }
+        tokens
+    }
+
+    pub fn get_next_token(&mut self) -> Option<Token> {
+        // Comments and over-long tokens are skipped by going round again rather than
+        // recursing, so a long run of them cannot exhaust the stack
+        'token: loop {
+            while self.position < self.source.len() && self.source[self.position].is_whitespace() {
                 self.position += 1;
+            }
 
-                if self.dfa.final_states.contains_key(&current_state) {
-                    last_final_state = Some((current_state.clone(), self.position));
-                }
+            if self.position >= self.source.len() {
+                return None;
+            }
+
+            let mut current_state = self.table.start;
+            let start_pos = self.position;
+            let mut last_final_state: Option<(u32, usize)> = None;
+
+            while self.position < self.source.len() {
+                let current_char = self.source[self.position];
+
+                if let Some(next_state) = self.table.next_state(current_state, current_char) {
+                    current_state = next_state;
+                    self.position += 1;
+
+                    if self.table.final_type(current_state).is_some() {
+                        last_final_state = Some((current_state, self.position));
+                    }
 
-                if current_state == "S_Start" {
-                    let token_value: String = self.source[start_pos..self.position].iter().collect();
-                    if token_value.starts_with('{') || token_value.starts_with("(*") {
-                        return self.get_next_token();
+                    if current_state == self.table.start {
+                        let token_start = &self.source[start_pos..self.position];
+                        if token_start.starts_with(&['{']) || token_start.starts_with(&['(', '*']) {
+                            continue 'token;
+                        }
                     }
+                } else {
+                    break;
                 }
-            } else {
-                break;
             }
-        }
 
-        if let Some((final_state, end_pos)) = last_final_state {
-            let value: String = self.source[start_pos..end_pos].iter().collect();
-            self.position = end_pos;
+            if let Some((final_state, mut end_pos)) = last_final_state {
+                // `5.` is a real, but in `5..10` the dot starts the range operator
+                if end_pos - start_pos > 1
+                    && self.source[end_pos - 1] == '.'
+                    && self.source.get(end_pos) == Some(&'.')
+                {
+                    end_pos -= 1;
+                }
+                if end_pos - start_pos > self.max_token_length {
+                    self.token_too_long_error(start_pos, end_pos);
+                    continue 'token;
+                }
+                let value: String = self.source[start_pos..end_pos].iter().collect();
+                self.position = end_pos;
+                let offset = self.byte_offset(start_pos);
+
+                if let Some(token_type_str) = self.table.final_type(final_state) {
+                    let (line, column) = self.location(start_pos);
+                    let mut token = self.create_token(token_type_str, value, line, column, offset);
 
-            if let Some(token_type_str) = self.dfa.final_states.get(&final_state) {
-                let mut token = self.create_token(token_type_str, value);
+                    if token.token_type == TokenType::Identifier {
+                        if self.source.get(end_pos).is_some_and(|c| c.is_alphabetic()) {
+                            self.non_ascii_identifier_error(start_pos);
+                            return None;
+                        }
+                        self.check_identifier(&mut token);
+                    }
 
-                if token.token_type == TokenType::Identifier {
-                    self.check_identifier(&mut token);
-                }
+                    if token.token_type == TokenType::Number && token.value.starts_with('&') {
+                        // &O17 or &B1010 must not run on into digits outside the radix
+                        if let Some(&digit) = self.source.get(end_pos)
+                            && digit.is_ascii_alphanumeric()
+                        {
+                            self.radix_digit_error(start_pos, digit);
+                            return None;
+                        }
+                    }
 
-                if token.token_type == TokenType::StringLiteral {
-                    let content = &token.value[1..&token.value.len() - 1];
-                    if content.chars().count() == 1 {
-                        token.token_type = TokenType::CharLiteral;
+                    if token.token_type == TokenType::StringLiteral {
+                        // A doubled quote inside the literal stands for one quote character
+                        let content = token.value[1..token.value.len() - 1].replace("''", "'");
+                        if content.chars().count() == 1 {
+                            token.token_type = TokenType::CharLiteral;
+                        }
                     }
+
+                    return Some(token);
                 }
+            }
 
-                return Some(token);
+            if self.source[start_pos].is_alphabetic() {
+                self.non_ascii_identifier_error(start_pos);
+                return None;
             }
-        }
 
-        if self.position < self.source.len() {
-            eprintln!("Error: Invalid token starting with '{}' at position {}", self.source[start_pos], start_pos);
+            // No final state was reached, even if the DFA ran to the end of the input
+            let (line, column) = self.location(start_pos);
+            let prefix = self.stuck_prefix(start_pos);
+            self.errors.push(LexError {
+                message: format!("Invalid token near \"{}\"", prefix),
+                prefix,
+                position: start_pos,
+                line,
+                column,
+            });
             self.position = self.source.len();
+
+            return None;
         }
+    }
+
+    /// Report a token over the length limit without building it, lexing resumes after it
+    fn token_too_long_error(&mut self, start_pos: usize, end_pos: usize) {
+        let prefix: String = self.source[start_pos..].iter().take(16).collect();
+        let (line, column) = self.location(start_pos);
 
-        None
+        self.errors.push(LexError {
+            message: format!(
+                "Token too long near \"{}...\" ({} characters, at most {})",
+                prefix,
+                end_pos - start_pos,
+                self.max_token_length
+            ),
+            prefix,
+            position: start_pos,
+            line,
+            column,
+        });
+        self.position = end_pos;
     }
 
-    fn get_next_state(&self, current_state: &str, ch: char) -> Option<String> {
-        if let Some(transitions) = self.dfa.transitions.get(current_state) {
-            if let Some(next_state) = transitions.get(&ch.to_string()) {
-                return Some(next_state.clone());
-            }
+    /// Report a digit that does not belong to the radix of a prefixed literal
+    fn radix_digit_error(&mut self, start_pos: usize, digit: char) {
+        let end = start_pos
+            + self.source[start_pos + 1..]
+                .iter()
+                .take_while(|c| c.is_ascii_alphanumeric())
+                .count()
+            + 1;
+        let prefix: String = self.source[start_pos..end].iter().collect();
+        let radix = if prefix[1..].starts_with(['O', 'o']) { "octal" } else { "binary" };
+        let (line, column) = self.location(start_pos);
 
-            for (key, next_state) in transitions {
-                if key.contains('-') && key.len() == 3 {
-                    let mut parts = key.chars();
-                    let start = parts.next()?;
-                    parts.next();
-                    let end = parts.next()?;
-                    if ch >= start && ch <= end {
-                        return Some(next_state.clone());
-                    }
-                } else if key.contains(ch) && !key.contains('-') {
-                    return Some(next_state.clone());
-                }
-            }
+        self.errors.push(LexError {
+            message: format!("Invalid digit '{}' in {} literal \"{}\"", digit, radix, prefix),
+            prefix,
+            position: start_pos,
+            line,
+            column,
+        });
+        self.position = self.source.len();
+    }
 
-            if let Some(next_state) = transitions.get("any") {
-                return Some(next_state.clone());
-            }
-        }
+    /// Report an identifier containing a letter outside ASCII. Identifiers are
+    /// ASCII only, as in Pascal, while strings and comments may hold any character
+    fn non_ascii_identifier_error(&mut self, start_pos: usize) {
+        let word: String = self.source[start_pos..]
+            .iter()
+            .take_while(|c| c.is_alphanumeric() || **c == '_')
+            .collect();
+        let letter = word.chars().find(|c| !c.is_ascii()).unwrap_or_default();
+        let (line, column) = self.location(start_pos);
 
-        None
+        self.errors.push(LexError {
+            message: format!(
+                "Non-ASCII letter '{}' in identifier \"{}\" (identifiers are ASCII only)",
+                letter, word
+            ),
+            prefix: word,
+            position: start_pos,
+            line,
+            column,
+        });
+        self.position = self.source.len();
+    }
+
+    /// Characters consumed before the DFA got stuck, at least the first one,
+    /// extended over following characters that cannot start a token either.
+    /// Cut at the end of the line, an unterminated string would otherwise run to the end of the file
+    fn stuck_prefix(&self, start_pos: usize) -> String {
+        let mut end = self.position.max(start_pos + 1);
+        while end < self.source.len()
+            && !self.source[end].is_whitespace()
+            && self.table.next_state(self.table.start, self.source[end]).is_none()
+        {
+            end += 1;
+        }
+        let prefix: String = self.source[start_pos..end].iter().collect();
+        prefix.lines().next().unwrap_or_default().trim_end().to_string()
     }
 
-    fn create_token(&self, token_type_str: &str, value: String) -> Token {
-        let token_type = match token_type_str {
-            "IDENTIFIER" => TokenType::Identifier,
-            "NUMBER" => TokenType::Number,
-            "STRING_LITERAL" => TokenType::StringLiteral,
-            "ASSIGN_OPERATOR" => TokenType::AssignOperator,
-            "RELATIONAL_OPERATOR" => TokenType::RelationalOperator,
-            "ARITHMETIC_OPERATOR" => TokenType::ArithmeticOperator,
-            "COLON" => TokenType::Colon,
-            "DOT" => TokenType::Dot,
-            "RANGE_OPERATOR" => TokenType::RangeOperator,
-            "SEMICOLON" => TokenType::Semicolon,
-            "COMMA" => TokenType::Comma,
-            "LPARENTHESIS" => TokenType::LParenthesis,
-            "RPARENTHESIS" => TokenType::RParenthesis,
-            "LBRACKET" => TokenType::LBracket,
-            "RBRACKET" => TokenType::RBracket,
-            _ => panic!("Unknown token type: {}", token_type_str),
-        };
-        Token { token_type, value }
+    fn create_token(
+        &self,
+        token_type_str: &str,
+        value: String,
+        line: usize,
+        column: usize,
+        offset: usize,
+    ) -> Token {
+        // Dfa::from_file rejects rules with unknown token types
+        let token_type = TokenType::from_dfa_name(token_type_str)
+            .unwrap_or_else(|| panic!("Unknown token type: {}", token_type_str));
+        Token { token_type, value, line, column, offset, keyword: None }
     }
 
     fn check_identifier(&self, token: &mut Token) {
@@ -133,6 +289,11 @@ impl Lexer {
             token.token_type = TokenType::LogicalOperator;
         } else if self.dfa.word_arithmetic_operators.contains(&token.value) {
             token.token_type = TokenType::ArithmeticOperator;
+        } else if self.dfa.word_relational_operators.contains(&token.value) {
+            token.token_type = TokenType::RelationalOperator;
+        } else {
+            return;
         }
+        token.keyword = self.keywords.role(&token.value);
     }
 }
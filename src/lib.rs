@@ -0,0 +1,358 @@
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use crate::{
+    call_graph::{CallGraph, CallGraphScope},
+    dfa::Dfa, diagnostic::Diagnostic, keyword::KeywordSet, lexer::Lexer, parser::Parser,
+    semantic_analyzer::SemanticAnalyzer, source_map::to_json_lines, token::Token,
+    types::IntWidth,
+};
+
+pub mod ast;
+pub mod builtin;
+pub mod call_graph;
+pub mod const_eval;
+pub mod dfa;
+pub mod diagnostic;
+pub mod keyword;
+pub mod lexer;
+pub mod listing;
+pub mod lower;
+pub mod node;
+pub mod operator;
+pub mod parser;
+pub mod repl;
+pub mod semantic_analyzer;
+pub mod semantic_error;
+pub mod simplify;
+pub mod source_map;
+pub mod symbol_table;
+pub mod token;
+pub mod types;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum ErrorFormat {
+    Human,
+    Json,
+}
+
+/// How the decorated AST is written to the report
+#[derive(PartialEq, Clone, Copy)]
+pub enum AstFormat {
+    Tree,
+    Json,
+}
+
+/// Settings that change what compilation reports
+#[derive(Clone, Copy)]
+pub struct CompileOptions {
+    pub error_format: ErrorFormat,
+    pub ast_format: AstFormat,
+    pub warn_shadow: bool,
+    pub warn_widening: bool,
+    pub warn_ignored_result: bool,
+    pub warn_non_exhaustive: bool,
+    pub strict_semicolons: bool,
+    pub strict_decl_order: bool,
+    pub char_ordinals: bool,
+    pub int_width: IntWidth,
+    pub call_graph: Option<CallGraphScope>,
+    pub source_map: bool,
+    pub symtab_only: bool, // Report only the symbol table, built as far as analysis got
+    pub listing: bool, // Report the source with each diagnostic under its line instead
+    pub simplify: bool,
+    pub short_circuit: bool,
+}
+
+impl Default for CompileOptions {
+    fn default() -> Self {
+        CompileOptions {
+            error_format: ErrorFormat::Human,
+            ast_format: AstFormat::Tree,
+            warn_shadow: false,
+            warn_widening: false,
+            warn_ignored_result: false,
+            warn_non_exhaustive: false,
+            strict_semicolons: false,
+            strict_decl_order: false,
+            char_ordinals: false,
+            int_width: IntWidth::default(),
+            call_graph: None,
+            source_map: false,
+            symtab_only: false,
+            listing: false,
+            simplify: false,
+            short_circuit: true,
+        }
+    }
+}
+
+/// How far compilation got
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    SyntaxError,
+    SemanticErrors(usize),
+    Success,
+}
+
+/// Phase output a section holds, for writing it apart from the rest of the report
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SectionKind {
+    Tokens,
+    SymbolTable,
+    Ast,
+    Other,
+}
+
+/// Block of the report, error blocks go to stderr on the console
+pub struct Section {
+    pub text: String,
+    pub is_error: bool,
+    pub kind: SectionKind,
+}
+
+/// Size of the program at each phase, zero for phases that did not run
+#[derive(Debug, Default)]
+pub struct Stats {
+    pub tokens: usize,
+    pub parse_nodes: usize,
+    pub tree_depth: usize,
+    pub symbols: usize,
+}
+
+/// Everything produced by running all phases on one source
+pub struct Compilation {
+    pub sections: Vec<Section>,
+    pub diagnostics: Vec<Diagnostic>,
+    pub timings: Vec<(&'static str, Duration)>,
+    pub stats: Stats,
+    pub status: Status,
+    pub lex_errors: usize,
+}
+
+impl Compilation {
+    /// Whether any phase reported an error, invalid tokens included
+    pub fn failed(&self) -> bool {
+        self.status != Status::Success || self.lex_errors > 0
+    }
+
+    /// Full report as written to the output file
+    pub fn report(&self) -> String {
+        self.sections.iter().map(|section| section.text.as_str()).collect()
+    }
+
+    /// Sections of one kind, in report order
+    pub fn report_of(&self, kind: SectionKind) -> String {
+        self.sections
+            .iter()
+            .filter(|section| section.kind == kind)
+            .map(|section| section.text.as_str())
+            .collect()
+    }
+
+    fn push(&mut self, text: String) {
+        self.push_kind(SectionKind::Other, text);
+    }
+
+    fn push_kind(&mut self, kind: SectionKind, text: String) {
+        self.sections.push(Section { text, is_error: false, kind });
+    }
+
+    fn push_error(&mut self, text: String) {
+        self.sections.push(Section { text, is_error: true, kind: SectionKind::Other });
+    }
+}
+
+/// Run the lexer, parser and semantic analyzer on `source`
+pub fn compile(source: String, dfa: Dfa, options: CompileOptions) -> Compilation {
+    let json_errors = options.error_format == ErrorFormat::Json;
+    // In a listing the diagnostics appear under their lines, not in sections of their own
+    let error_sections = !json_errors && !options.listing;
+    let full_report = !options.symtab_only && !options.listing;
+    let listing_source = if options.listing { source.clone() } else { String::new() };
+    let mut compilation = Compilation {
+        sections: Vec::new(),
+        diagnostics: Vec::new(),
+        timings: Vec::new(),
+        stats: Stats::default(),
+        status: Status::SyntaxError,
+        lex_errors: 0,
+    };
+
+    let keywords = KeywordSet::from_dfa(&dfa);
+    let lex_start = Instant::now();
+    let mut lexer = Lexer::new(source, dfa);
+    let tokens = lexer.tokenize_all();
+    compilation.timings.push(("lexer", lex_start.elapsed()));
+    compilation.stats.tokens = tokens.len();
+    compilation.lex_errors = lexer.errors.len();
+
+    compilation.diagnostics.extend(lexer.errors.iter().map(Diagnostic::from));
+    if error_sections && !lexer.errors.is_empty() {
+        let mut text = String::new();
+        for error in &lexer.errors {
+            writeln!(text, "{}", error).unwrap();
+        }
+        compilation.push_error(text);
+    }
+
+    if full_report {
+        let mut text = String::new();
+        writeln!(text, "---TOKENS---").unwrap();
+        writeln!(text, "{}", Token::table_header()).unwrap();
+        for (index, token) in tokens.iter().enumerate() {
+            writeln!(text, "{}", token.fmt_table_row(index)).unwrap();
+        }
+        writeln!(text, "------------").unwrap();
+        compilation.push_kind(SectionKind::Tokens, text);
+    }
+
+    let mut parser = Parser::new(tokens);
+    parser.strict_semicolons = options.strict_semicolons;
+    parser.strict_decl_order = options.strict_decl_order;
+    parser.keywords = keywords.clone();
+
+    let parse_start = Instant::now();
+    let parse_tree_result = parser.parse();
+    compilation.timings.push(("parser", parse_start.elapsed()));
+
+    compilation.diagnostics.extend(parser.warnings.iter().map(Diagnostic::from));
+    if error_sections && !parser.warnings.is_empty() {
+        let mut text = String::new();
+        writeln!(text, "\n---PARSER WARNINGS---").unwrap();
+        for warning in &parser.warnings {
+            writeln!(text, "{}", warning).unwrap();
+        }
+        writeln!(text, "------------------").unwrap();
+        compilation.push_error(text);
+    }
+
+    match parse_tree_result {
+        Ok(node) => {
+            compilation.stats.parse_nodes = node.node_count();
+            compilation.stats.tree_depth = node.max_depth();
+
+            if full_report {
+                let mut text = String::new();
+                writeln!(text, "\n---PARSE TREE---").unwrap();
+                writeln!(text, "{}", node).unwrap();
+                writeln!(text, "--------------").unwrap();
+                compilation.push(text);
+            }
+
+            // Semantic Analysis
+            let mut analyzer = SemanticAnalyzer::with_keywords(keywords);
+            analyzer.warn_shadow = options.warn_shadow;
+            analyzer.warn_widening = options.warn_widening;
+            analyzer.warn_ignored_result = options.warn_ignored_result;
+            analyzer.warn_non_exhaustive = options.warn_non_exhaustive;
+            analyzer.char_ordinals = options.char_ordinals;
+            analyzer.int_width = options.int_width;
+            analyzer.simplify = options.simplify;
+            analyzer.short_circuit = options.short_circuit;
+            let semantic_start = Instant::now();
+            let analysis = analyzer.analyze(&node);
+            compilation.timings.push(("semantic", semantic_start.elapsed()));
+            compilation.stats.symbols = analyzer.symbol_table.tab.len();
+
+            compilation.diagnostics.extend(analyzer.warnings.iter().map(Diagnostic::from));
+            if error_sections && !analyzer.warnings.is_empty() {
+                let mut text = String::new();
+                writeln!(text, "\n---SEMANTIC WARNINGS---").unwrap();
+                for warning in &analyzer.warnings {
+                    writeln!(text, "{}", warning).unwrap();
+                }
+                writeln!(text, "------------------").unwrap();
+                compilation.push_error(text);
+            }
+
+            match analysis {
+                Ok(_) if !full_report => compilation.status = Status::Success,
+                Ok(ast) => {
+                    let mut text = String::new();
+                    writeln!(text, "\n---SEMANTIC ANALYSIS---").unwrap();
+                    writeln!(text, "{}", analyzer.symbol_table).unwrap();
+                    compilation.push_kind(SectionKind::SymbolTable, text);
+
+                    let mut text = String::new();
+                    writeln!(text, "\n---DECORATED AST---").unwrap();
+                    match options.ast_format {
+                        AstFormat::Tree => {
+                            writeln!(text, "{}", ast.display(&analyzer.symbol_table)).unwrap()
+                        }
+                        AstFormat::Json => {
+                            let json = serde_json::to_string_pretty(&ast).unwrap();
+                            writeln!(text, "{}", json).unwrap();
+                        }
+                    }
+                    writeln!(text, "--------------").unwrap();
+                    compilation.push_kind(SectionKind::Ast, text);
+
+                    if let Some(scope) = options.call_graph {
+                        let graph = CallGraph::build(&ast, &analyzer.symbol_table, scope);
+                        let mut text = String::new();
+                        writeln!(text, "\n---CALL GRAPH---").unwrap();
+                        write!(text, "{}", graph.to_dot()).unwrap();
+                        writeln!(text, "--------------").unwrap();
+                        compilation.push(text);
+                    }
+
+                    compilation.status = Status::Success;
+                }
+                Err(errors) => {
+                    compilation.status = Status::SemanticErrors(errors.len());
+
+                    compilation.diagnostics.extend(errors.iter().map(Diagnostic::from));
+                    if error_sections {
+                        let mut text = String::new();
+                        writeln!(text, "\n---SEMANTIC ERRORS---").unwrap();
+                        for error in &errors {
+                            writeln!(text, "{}", error).unwrap();
+                        }
+                        writeln!(text, "------------------").unwrap();
+                        compilation.push_error(text);
+                    }
+                }
+            }
+
+            // The table is emitted with errors too, so the part built before them can be graded
+            if options.symtab_only {
+                let mut text = String::new();
+                writeln!(text, "---SYMBOL TABLE---").unwrap();
+                writeln!(text, "{}", analyzer.symbol_table).unwrap();
+                writeln!(text, "--------------").unwrap();
+                compilation.push_kind(SectionKind::SymbolTable, text);
+            }
+
+            // Resolution that did succeed is still useful to an editor when there are errors
+            if options.source_map && full_report {
+                let mut text = String::new();
+                writeln!(text, "\n---SOURCE MAP---").unwrap();
+                write!(text, "{}", to_json_lines(&analyzer.references)).unwrap();
+                writeln!(text, "--------------").unwrap();
+                compilation.push(text);
+            }
+        }
+        Err(e) => {
+            compilation.diagnostics.push(Diagnostic::from(&e));
+            if error_sections {
+                let mut text = String::new();
+                writeln!(text, "\n---PARSER ERROR---").unwrap();
+                writeln!(text, "{}", e).unwrap();
+                writeln!(text, "------------------").unwrap();
+                compilation.push_error(text);
+            }
+        }
+    }
+
+    if options.listing {
+        compilation.push(listing::render(&listing_source, &compilation.diagnostics));
+    }
+
+    if json_errors {
+        let json = serde_json::to_string_pretty(&compilation.diagnostics).unwrap();
+        compilation.push_error(format!("{}\n", json));
+    }
+
+    compilation
+}
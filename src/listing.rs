@@ -0,0 +1,57 @@
+use std::fmt::Write;
+
+use crate::diagnostic::Diagnostic;
+
+/// Width of the line number column
+const NUMBER_WIDTH: usize = 4;
+
+/// Classic compiler listing: every source line numbered, each diagnostic printed
+/// under the line it belongs to with a caret at its column. Diagnostics without a
+/// line, or past the last one, follow the listing
+pub fn render(source: &str, diagnostics: &[Diagnostic]) -> String {
+    let mut listing = String::from("---LISTING---\n");
+    let lines: Vec<&str> = source.lines().collect();
+
+    for (index, line) in lines.iter().enumerate() {
+        writeln!(listing, "{:>width$}  {}", index + 1, line, width = NUMBER_WIDTH).unwrap();
+
+        let mut on_line: Vec<&Diagnostic> =
+            diagnostics.iter().filter(|diagnostic| diagnostic.line == Some(index + 1)).collect();
+        on_line.sort_by_key(|diagnostic| diagnostic.col);
+        for diagnostic in on_line {
+            // Tabs are kept so the caret lines up however wide the viewer draws them
+            let col = diagnostic.col.unwrap_or(1);
+            let padding: String = line
+                .chars()
+                .take(col.saturating_sub(1))
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            writeln!(
+                listing,
+                "{}  {}^ {}",
+                " ".repeat(NUMBER_WIDTH),
+                padding,
+                describe(diagnostic)
+            )
+            .unwrap();
+        }
+    }
+
+    for diagnostic in diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.line.is_none_or(|line| line > lines.len()))
+    {
+        writeln!(listing, "{}  {}", " ".repeat(NUMBER_WIDTH), describe(diagnostic)).unwrap();
+    }
+
+    let count = |severity: &str| {
+        diagnostics.iter().filter(|diagnostic| diagnostic.severity == severity).count()
+    };
+    writeln!(listing, "\n{} error(s), {} warning(s)", count("error"), count("warning")).unwrap();
+    writeln!(listing, "--------------").unwrap();
+    listing
+}
+
+fn describe(diagnostic: &Diagnostic) -> String {
+    format!("{} {}: {}", diagnostic.phase, diagnostic.severity, diagnostic.message)
+}
@@ -0,0 +1,95 @@
+use crate::ast::{AstNode, LiteralValue};
+use crate::operator::Operator;
+use crate::symbol_table::SymbolTable;
+use crate::types::{DataType, ObjectKind};
+
+/// Rewrite every untuk loop of a decorated AST into a selama loop, for backends that
+/// only have a conditional loop:
+///
+/// `untuk i := a ke b lakukan S` becomes `mulai i := a; selama i <= b lakukan mulai S; i := i + 1 selesai selesai`
+///
+/// and turun_ke counts down with `>=` and `i - 1`. The bound is tested on every pass
+/// instead of evaluated once, so it must not change in the body. A loop whose body
+/// continues it with lanjut is left as is, lanjut would skip the step
+pub fn lower_for_loops(node: &mut AstNode, symbol_table: &SymbolTable) {
+    lower(node, symbol_table, (0, 0));
+}
+
+/// Lower the loops under `node`, `scope` being the block index and level of the
+/// innermost block, which the blocks built for a loop belong to
+fn lower(node: &mut AstNode, symbol_table: &SymbolTable, scope: (usize, usize)) {
+    let scope = match node {
+        AstNode::Block { block_index, level, .. } => (*block_index, *level),
+        _ => scope,
+    };
+    for child in node.children_mut() {
+        lower(child, symbol_table, scope);
+    }
+
+    if let AstNode::For { start, end, is_downto, body, tab_index, .. } = node
+        && !continues_loop(body)
+    {
+        let start = std::mem::replace(start.as_mut(), AstNode::Empty);
+        let end = std::mem::replace(end.as_mut(), AstNode::Empty);
+        let body = std::mem::replace(body.as_mut(), AstNode::Empty);
+        *node = while_loop(start, end, *is_downto, body, *tab_index, symbol_table, scope);
+    }
+}
+
+/// Initialization and counting selama loop of one untuk loop, as a block
+fn while_loop(
+    start: AstNode,
+    end: AstNode,
+    is_downto: bool,
+    body: AstNode,
+    tab_index: usize,
+    symbol_table: &SymbolTable,
+    (block_index, level): (usize, usize),
+) -> AstNode {
+    let (test, step) = if is_downto { (Operator::Ge, Operator::Sub) } else { (Operator::Le, Operator::Add) };
+    let entry = &symbol_table.tab[tab_index];
+    let variable = AstNode::Var {
+        name: entry.name.clone(),
+        data_type: entry.data_type.clone(),
+        tab_index,
+        level: entry.level,
+    };
+    let assign = |value: AstNode| AstNode::Assign {
+        target: Box::new(variable.clone()),
+        value: Box::new(value),
+        data_type: entry.data_type.clone(),
+        by_reference: entry.obj == ObjectKind::Parameter && !entry.normal,
+    };
+
+    let condition = AstNode::BinOp {
+        op: test,
+        left: Box::new(variable.clone()),
+        right: Box::new(end),
+        data_type: DataType::Boolean,
+    };
+    let next = AstNode::BinOp {
+        op: step,
+        left: Box::new(variable.clone()),
+        right: Box::new(AstNode::Literal { value: LiteralValue::Integer(1), data_type: DataType::Integer }),
+        data_type: entry.data_type.clone(),
+    };
+    let body = AstNode::Block { statements: vec![body, assign(next)], block_index, level };
+
+    AstNode::Block {
+        statements: vec![
+            assign(start),
+            AstNode::While { condition: Box::new(condition), body: Box::new(body) },
+        ],
+        block_index,
+        level,
+    }
+}
+
+/// Whether `node` holds a lanjut of the loop around it, not of a loop nested inside
+fn continues_loop(node: &AstNode) -> bool {
+    match node {
+        AstNode::Continue => true,
+        AstNode::While { .. } | AstNode::Repeat { .. } | AstNode::For { .. } => false,
+        _ => node.children().into_iter().any(continues_loop),
+    }
+}
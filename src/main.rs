@@ -1,29 +1,139 @@
 use std::env;
-use std::fs::File;
-use std::io::{BufWriter, Write};
-
-use crate::{dfa::Dfa, lexer::Lexer, parser::Parser, semantic_analyzer::SemanticAnalyzer};
-
-mod ast;
-mod dfa;
-mod lexer;
-mod node;
-mod parser;
-mod semantic_analyzer;
-mod semantic_error;
-mod symbol_table;
-mod token;
-mod types;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process;
+
+use cgk_tubes_if2224::{
+    AstFormat, CompileOptions, ErrorFormat, SectionKind, Status, call_graph::CallGraphScope, compile, dfa::Dfa,
+    repl::Repl, types::IntWidth,
+};
+
+/// Command-line options, flags may appear anywhere among the paths
+struct Options {
+    filepath: String,
+    pathtooutput: String,
+    compile: CompileOptions,
+    redirects: Vec<(SectionKind, String)>, // Sections written to a file of their own
+    time: bool,
+    stats: bool,
+    repl: bool,
+    check: bool,
+    verify_dfa: bool,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut paths = Vec::new();
+        let mut compile = CompileOptions::default();
+        let mut redirects = Vec::new();
+        let mut time = false;
+        let mut stats = false;
+        let mut repl = false;
+        let mut check = false;
+        let mut verify_dfa = false;
+
+        let mut iter = args.iter().skip(1);
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--error-format" => {
+                    compile.error_format = match iter.next().map(String::as_str) {
+                        Some("human") => ErrorFormat::Human,
+                        Some("json") => ErrorFormat::Json,
+                        _ => return Err("--error-format expects 'human' or 'json'".to_string()),
+                    };
+                }
+                "--dump-ast" => {
+                    compile.ast_format = match iter.next().map(String::as_str) {
+                        Some("tree") => AstFormat::Tree,
+                        Some("json") => AstFormat::Json,
+                        _ => return Err("--dump-ast expects 'tree' or 'json'".to_string()),
+                    };
+                }
+                "--callgraph" => {
+                    compile.call_graph = match iter.next().map(String::as_str) {
+                        Some("user") => Some(CallGraphScope::User),
+                        Some("all") => Some(CallGraphScope::All),
+                        _ => return Err("--callgraph expects 'user' or 'all'".to_string()),
+                    };
+                }
+                "--int-width" => {
+                    compile.int_width = iter
+                        .next()
+                        .and_then(|bits| IntWidth::from_bits(bits))
+                        .ok_or_else(|| "--int-width expects 16, 32 or 64".to_string())?;
+                }
+                "--tokens-out" | "--symtab-out" | "--ast-out" => {
+                    let kind = match arg.as_str() {
+                        "--tokens-out" => SectionKind::Tokens,
+                        "--symtab-out" => SectionKind::SymbolTable,
+                        _ => SectionKind::Ast,
+                    };
+                    let path = iter.next().ok_or_else(|| format!("{} expects a path", arg))?;
+                    redirects.push((kind, path.clone()));
+                }
+                "--sourcemap" => compile.source_map = true,
+                "--symtab-only" => compile.symtab_only = true,
+                "--list" => compile.listing = true,
+                "--simplify" => compile.simplify = true,
+                "--complete-boolean" => compile.short_circuit = false,
+                "--time" => time = true,
+                "--stats" => stats = true,
+                "--repl" => repl = true,
+                "--check" => check = true,
+                "--verify-dfa" => verify_dfa = true,
+                "--warn-shadow" => compile.warn_shadow = true,
+                "--warn-widening" => compile.warn_widening = true,
+                "--warn-ignored-result" => compile.warn_ignored_result = true,
+                "--warn-non-exhaustive" => compile.warn_non_exhaustive = true,
+                "--strict-semicolons" => compile.strict_semicolons = true,
+                "--strict-decl-order" => compile.strict_decl_order = true,
+                "--char-ordinals" => compile.char_ordinals = true,
+                flag if flag.starts_with("--") => {
+                    return Err(format!("Unknown option {}", flag));
+                }
+                path => paths.push(path.to_string()),
+            }
+        }
+
+        // --verify-dfa alone only checks the rules
+        if !repl && !verify_dfa && paths.is_empty() {
+            return Err("Missing input path".to_string());
+        }
+        if !repl && !check && paths.len() == 1 {
+            return Err("Missing output path".to_string());
+        }
+
+        let mut paths = paths.into_iter();
+        Ok(Options {
+            filepath: paths.next().unwrap_or_default(),
+            pathtooutput: paths.next().unwrap_or_default(),
+            compile,
+            redirects,
+            time,
+            stats,
+            repl,
+            check,
+            verify_dfa,
+        })
+    }
+}
 
 fn main() {
     let args: Vec<String> = env::args().collect();
-    if args.len() < 3 {
-        eprintln!("Usage: {} <path_to_pascal_file> <pathtooutput>", args[0]);
-        return;
-    }
+    let options = match Options::parse(&args) {
+        Ok(o) => o,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!(
+                "Usage: {} (<path_to_pascal_file> <pathtooutput> | <path_to_pascal_file> --check | --repl | --verify-dfa) [--error-format human|json] [--dump-ast tree|json] [--callgraph user|all] [--sourcemap] [--symtab-only] [--list] [--simplify] [--complete-boolean] [--int-width 16|32|64] [--tokens-out PATH] [--symtab-out PATH] [--ast-out PATH] [--time] [--stats] [--warn-shadow] [--warn-widening] [--warn-ignored-result] [--warn-non-exhaustive] [--strict-semicolons] [--strict-decl-order] [--char-ordinals]",
+                args[0]
+            );
+            return;
+        }
+    };
 
-    let filepath = &args[1];
-    let pathtooutput = &args[2];
+    let filepath = &options.filepath;
+    let pathtooutput = &options.pathtooutput;
 
     let dfa = match Dfa::from_file("dfa_rules.json") {
         Ok(d) => d,
@@ -33,6 +143,25 @@ fn main() {
         }
     };
 
+    if options.verify_dfa {
+        if let Err(failures) = dfa.self_test() {
+            eprintln!("DFA self-test failed:");
+            for failure in &failures {
+                eprintln!("  {}", failure);
+            }
+            process::exit(1);
+        }
+        if !options.repl && filepath.is_empty() {
+            println!("DFA self-test passed ({} examples)", dfa.examples.len());
+            return;
+        }
+    }
+
+    if options.repl {
+        run_repl(dfa);
+        return;
+    }
+
     let source_code = match std::fs::read_to_string(filepath) {
         Ok(s) => s,
         Err(e) => {
@@ -41,98 +170,98 @@ fn main() {
         }
     };
 
-    let mut lexer = Lexer::new(source_code, dfa);
-    let mut tokens = Vec::new();
+    let compilation = compile(source_code, dfa, options.compile);
 
-    while let Some(token) = lexer.get_next_token() {
-        tokens.push(token);
+    if options.check {
+        // Silent on success, only the diagnostics otherwise
+        if compilation.failed() {
+            for section in compilation.sections.iter().filter(|section| section.is_error) {
+                eprint!("{}", section.text);
+            }
+            process::exit(1);
+        }
+        return;
     }
 
-    println!("---TOKENS---");
-    for token in &tokens {
-        println!("{}", token);
+    for section in &compilation.sections {
+        if section.is_error {
+            eprint!("{}", section.text);
+        } else {
+            print!("{}", section.text);
+        }
     }
-    println!("------------");
 
-    let file = match File::create(pathtooutput) {
-        Ok(f) => f,
-        Err(e) => {
-            eprintln!("Error output file {}: {}", pathtooutput, e);
+    // The output path gets every section that is not redirected
+    let redirected = |kind| options.redirects.iter().any(|(redirect, _)| *redirect == kind);
+    let report: String = compilation
+        .sections
+        .iter()
+        .filter(|section| !redirected(section.kind))
+        .map(|section| section.text.as_str())
+        .collect();
+    if let Err(e) = fs::write(pathtooutput, report) {
+        eprintln!("Error output file {}: {}", pathtooutput, e);
+        return;
+    }
+    for (kind, path) in &options.redirects {
+        if let Err(e) = write_report(path, &compilation.report_of(*kind)) {
+            eprintln!("Error output file {}: {}", path, e);
             return;
         }
-    };
-    let mut writer = BufWriter::new(file);
+    }
 
-    writeln!(writer, "---TOKENS---").unwrap();
-    for token in &tokens {
-        writeln!(writer, "{}", token).unwrap();
+    match compilation.status {
+        Status::Success => println!("\nSuccessfully analyzed and wrote to {}", pathtooutput),
+        Status::SemanticErrors(count) => println!(
+            "\nSemantic analysis completed with {} error(s). Output written to {}",
+            count, pathtooutput
+        ),
+        Status::SyntaxError => {}
     }
-    writeln!(writer, "------------").unwrap();
-
-    println!("\nParsing...");
-
-    let mut parser = Parser::new(tokens);
-
-    let parse_tree_result = parser.parse();
-
-    match parse_tree_result {
-        Ok(node) => {
-            println!("\n---PARSE TREE---");
-            println!("{}", node);
-            println!("--------------");
-
-            writeln!(writer, "\n---PARSE TREE---").unwrap();
-            writeln!(writer, "{}", node).unwrap();
-            writeln!(writer, "--------------").unwrap();
-
-            // Semantic Analysis
-            println!("\nPerforming semantic analysis...");
-            let mut analyzer = SemanticAnalyzer::new();
-            
-            match analyzer.analyze(&node) {
-                Ok(ast) => {
-                    println!("\n---SEMANTIC ANALYSIS---");
-                    println!("{}", analyzer.symbol_table);
-                    println!("\n---DECORATED AST---");
-                    println!("{}", ast);
-                    println!("--------------");
-
-                    writeln!(writer, "\n---SEMANTIC ANALYSIS---").unwrap();
-                    writeln!(writer, "{}", analyzer.symbol_table).unwrap();
-                    writeln!(writer, "\n---DECORATED AST---").unwrap();
-                    writeln!(writer, "{}", ast).unwrap();
-                    writeln!(writer, "--------------").unwrap();
-
-                    println!("\nSuccessfully analyzed and wrote to {}", pathtooutput);
-                }
-                Err(errors) => {
-                    eprintln!("\n---SEMANTIC ERRORS---");
-                    for error in &errors {
-                        eprintln!("{}", error);
-                    }
-                    eprintln!("------------------");
-
-                    writeln!(writer, "\n---SEMANTIC ERRORS---").unwrap();
-                    for error in &errors {
-                        writeln!(writer, "{}", error).unwrap();
-                    }
-                    writeln!(writer, "------------------").unwrap();
-
-                    println!("\nSemantic analysis completed with {} error(s). Output written to {}", 
-                             errors.len(), pathtooutput);
-                }
-            }
-        }
-        Err(e) => {
-            eprintln!("\n---PARSER ERROR---");
-            eprintln!("{}", e);
-            eprintln!("------------------");
 
-            writeln!(writer, "\n---PARSER ERROR---").unwrap();
-            writeln!(writer, "{}", e).unwrap();
-            writeln!(writer, "------------------").unwrap();
-        }
+    if options.time {
+        let report: Vec<String> = compilation
+            .timings
+            .iter()
+            .map(|(phase, elapsed)| format!("{}: {:.3}ms", phase, elapsed.as_secs_f64() * 1000.0))
+            .collect();
+        eprintln!("{}", report.join(", "));
+    }
+
+    if options.stats {
+        let stats = &compilation.stats;
+        eprintln!(
+            "tokens: {}, parse tree nodes: {}, tree depth: {}, symbols: {}",
+            stats.tokens, stats.parse_nodes, stats.tree_depth, stats.symbols
+        );
     }
+}
 
-    writer.flush().unwrap();
+/// Write a part of the report to a file of its own
+fn write_report(path: &str, text: &str) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    writer.write_all(text.as_bytes())?;
+    writer.flush()
+}
+
+/// Read lines from stdin until EOF, printing the analysis of each
+fn run_repl(dfa: Dfa) {
+    let mut repl = Repl::new(dfa);
+    let stdin = io::stdin();
+
+    loop {
+        print!("> ");
+        io::stdout().flush().unwrap();
+
+        let mut line = String::new();
+        match stdin.lock().read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => print!("{}", repl.eval(&line)),
+            Err(e) => {
+                eprintln!("Error reading input: {}", e);
+                break;
+            }
+        }
+    }
+    println!();
 }
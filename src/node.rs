@@ -1,7 +1,8 @@
 use crate::token::Token;
 use std::fmt;
 
-#[derive(Debug, Clone)]
+/// Equality is structural: same node types and children, terminals compared by type and value
+#[derive(Debug, Clone, PartialEq)]
 pub struct ParseNode {
     pub node_type: NodeType,
     pub children: Vec<ParseNode>,
@@ -13,6 +14,7 @@ pub enum NodeType {
     Program,
     ProgramHeader,
     DeclarationPart,
+    LabelDeclaration,
     ConstDeclaration,
     TypeDeclaration,
     VarDeclaration,
@@ -20,6 +22,7 @@ pub enum NodeType {
     Type,
     ArrayType,
     RecordType,
+    SetType,
     Range,
     SubprogramDeclaration,
     ProcedureDeclaration,
@@ -32,12 +35,23 @@ pub enum NodeType {
     WhileStatement,
     ForStatement,
     RepeatStatement,
+    BreakStatement,
+    ContinueStatement,
+    LabeledStatement,
+    GotoStatement,
+    CaseStatement,
+    CaseElement,
     ProcedureOrFunctionCall,
     ParameterList,
+    FormattedArgument,
     Expression,
     SimpleExpression,
     Term,
     Factor,
+    ArrayAccess,
+    Dereference,
+    FieldAccess,
+    SetLiteral,
     // Terminal
     Terminal(Token),
 }
@@ -56,6 +70,36 @@ impl ParseNode {
             children: Vec::new(),
         }
     }
+
+    /// First token covered by this node, used to locate diagnostics
+    pub fn first_token(&self) -> Option<&Token> {
+        match &self.node_type {
+            NodeType::Terminal(token) => Some(token),
+            _ => self.children.iter().find_map(|child| child.first_token()),
+        }
+    }
+
+    /// Number of nodes in this subtree, including this one
+    pub fn node_count(&self) -> usize {
+        1 + self.children.iter().map(ParseNode::node_count).sum::<usize>()
+    }
+
+    /// Nodes on the longest path down to a leaf, a lone node has depth 1
+    pub fn max_depth(&self) -> usize {
+        1 + self.children.iter().map(ParseNode::max_depth).max().unwrap_or(0)
+    }
+}
+
+impl PartialEq for NodeType {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            // Token positions are ignored, so trees of differently laid out sources compare equal
+            (NodeType::Terminal(a), NodeType::Terminal(b)) => {
+                a.token_type == b.token_type && a.value == b.value
+            }
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
 }
 
 impl fmt::Display for NodeType {
@@ -66,6 +110,7 @@ impl fmt::Display for NodeType {
             NodeType::Program => write!(f, "<program>"),
             NodeType::ProgramHeader => write!(f, "<program-header>"),
             NodeType::DeclarationPart => write!(f, "<declaration-part>"),
+            NodeType::LabelDeclaration => write!(f, "<label-declaration>"),
             NodeType::ConstDeclaration => write!(f, "<const-declaration>"),
             NodeType::TypeDeclaration => write!(f, "<type-declaration>"),
             NodeType::VarDeclaration => write!(f, "<var-declaration>"),
@@ -73,6 +118,7 @@ impl fmt::Display for NodeType {
             NodeType::Type => write!(f, "<type>"),
             NodeType::ArrayType => write!(f, "<array-type>"),
             NodeType::RecordType => write!(f, "<record-type>"),
+            NodeType::SetType => write!(f, "<set-type>"),
             NodeType::Range => write!(f, "<range>"),
             NodeType::SubprogramDeclaration => write!(f, "<subprogram-declaration>"),
             NodeType::ProcedureDeclaration => write!(f, "<procedure-declaration>"),
@@ -85,12 +131,23 @@ impl fmt::Display for NodeType {
             NodeType::WhileStatement => write!(f, "<while-statement>"),
             NodeType::ForStatement => write!(f, "<for-statement>"),
             NodeType::RepeatStatement => write!(f, "<repeat-statement>"),
+            NodeType::BreakStatement => write!(f, "<break-statement>"),
+            NodeType::ContinueStatement => write!(f, "<continue-statement>"),
+            NodeType::LabeledStatement => write!(f, "<labeled-statement>"),
+            NodeType::GotoStatement => write!(f, "<goto-statement>"),
+            NodeType::CaseStatement => write!(f, "<case-statement>"),
+            NodeType::CaseElement => write!(f, "<case-element>"),
             NodeType::ProcedureOrFunctionCall => write!(f, "<procedure/function-call>"),
             NodeType::ParameterList => write!(f, "<parameter-list>"),
+            NodeType::FormattedArgument => write!(f, "<formatted-argument>"),
             NodeType::Expression => write!(f, "<expression>"),
             NodeType::SimpleExpression => write!(f, "<simple-expression>"),
             NodeType::Term => write!(f, "<term>"),
             NodeType::Factor => write!(f, "<factor>"),
+            NodeType::ArrayAccess => write!(f, "<array-access>"),
+            NodeType::Dereference => write!(f, "<dereference>"),
+            NodeType::FieldAccess => write!(f, "<field-access>"),
+            NodeType::SetLiteral => write!(f, "<set-literal>"),
         }
     }
 }
@@ -0,0 +1,133 @@
+use crate::keyword::{Keyword, KeywordSet};
+use crate::token::{Token, TokenType};
+use crate::types::DataType;
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+/// Unary and binary operators of Pascal-S
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Sub,
+    Mul,
+    RealDiv,
+    IntDiv,
+    Mod,
+    And,
+    Or,
+    Not,
+    Eq,
+    Neq,
+    Lt,
+    Gt,
+    Le,
+    Ge,
+    In,
+}
+
+impl Operator {
+    /// Operator spelled by an operator token, if any. Word operators go by their
+    /// keyword role, so they are found however the DFA rules spell them
+    pub fn from_token(token: &Token) -> Option<Operator> {
+        let op = match (&token.token_type, token.value.as_str(), token.keyword) {
+            (TokenType::ArithmeticOperator, "+", _) => Operator::Add,
+            (TokenType::ArithmeticOperator, "-", _) => Operator::Sub,
+            (TokenType::ArithmeticOperator, "*", _) => Operator::Mul,
+            (TokenType::ArithmeticOperator, "/", _) => Operator::RealDiv,
+            (TokenType::ArithmeticOperator, _, Some(Keyword::Div)) => Operator::IntDiv,
+            (TokenType::ArithmeticOperator, _, Some(Keyword::Mod)) => Operator::Mod,
+            (TokenType::LogicalOperator, _, Some(Keyword::And)) => Operator::And,
+            (TokenType::LogicalOperator, _, Some(Keyword::Or)) => Operator::Or,
+            (TokenType::LogicalOperator, _, Some(Keyword::Not)) => Operator::Not,
+            (TokenType::RelationalOperator, "=", _) => Operator::Eq,
+            (TokenType::RelationalOperator, "<>", _) => Operator::Neq,
+            (TokenType::RelationalOperator, "<", _) => Operator::Lt,
+            (TokenType::RelationalOperator, ">", _) => Operator::Gt,
+            (TokenType::RelationalOperator, "<=", _) => Operator::Le,
+            (TokenType::RelationalOperator, ">=", _) => Operator::Ge,
+            (TokenType::RelationalOperator, _, Some(Keyword::In)) => Operator::In,
+            _ => return None,
+        };
+        Some(op)
+    }
+
+    /// Operator as the source spells it with the reserved words of `keywords`
+    pub fn spelling<'a>(&self, keywords: &'a KeywordSet) -> &'a str {
+        match self.symbol() {
+            Ok(symbol) => symbol,
+            Err(role) => keywords.spelling(role),
+        }
+    }
+
+    /// Symbol of a symbolic operator, or the keyword role of a word operator
+    fn symbol(&self) -> Result<&'static str, Keyword> {
+        let symbol = match self {
+            Operator::Add => "+",
+            Operator::Sub => "-",
+            Operator::Mul => "*",
+            Operator::RealDiv => "/",
+            Operator::Eq => "=",
+            Operator::Neq => "<>",
+            Operator::Lt => "<",
+            Operator::Gt => ">",
+            Operator::Le => "<=",
+            Operator::Ge => ">=",
+            Operator::IntDiv => return Err(Keyword::Div),
+            Operator::Mod => return Err(Keyword::Mod),
+            Operator::And => return Err(Keyword::And),
+            Operator::Or => return Err(Keyword::Or),
+            Operator::Not => return Err(Keyword::Not),
+            Operator::In => return Err(Keyword::In),
+        };
+        Ok(symbol)
+    }
+
+    /// =, <>, <, >, <= or >=
+    pub fn is_relational(&self) -> bool {
+        matches!(
+            self,
+            Operator::Eq | Operator::Neq | Operator::Lt | Operator::Gt | Operator::Le | Operator::Ge
+        )
+    }
+
+    /// Relational operators that need an order on their operands, not just equality
+    pub fn is_ordering(&self) -> bool {
+        matches!(self, Operator::Lt | Operator::Gt | Operator::Le | Operator::Ge)
+    }
+
+    /// Result type of applying this binary operator to operands of the given types.
+    /// An unknown operand already had its error reported, so the result is unknown too
+    pub fn result_type(&self, left: &DataType, right: &DataType) -> Result<DataType, String> {
+        if *left == DataType::Unknown || *right == DataType::Unknown {
+            return Ok(DataType::Unknown);
+        }
+
+        match self {
+            Operator::Add | Operator::Sub | Operator::Mul => {
+                DataType::get_arithmetic_result_type(left, right)
+            }
+            Operator::RealDiv => DataType::get_division_result_type(left, right),
+            Operator::IntDiv | Operator::Mod => DataType::get_integer_result_type(left, right),
+            Operator::And | Operator::Or | Operator::Not => {
+                DataType::get_logical_result_type(left, right)
+            }
+            Operator::In => DataType::get_membership_result_type(left, right),
+            _ => DataType::get_relational_result_type(left, right),
+        }
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.symbol() {
+            Ok(symbol) => write!(f, "{}", symbol),
+            Err(role) => write!(f, "{}", role.default_spelling()),
+        }
+    }
+}
+
+impl Serialize for Operator {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
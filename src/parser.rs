@@ -1,3 +1,4 @@
+use crate::keyword::{Keyword, KeywordSet};
 use crate::node::{NodeType, ParseNode};
 use crate::token::{Token, TokenType};
 use std::fmt;
@@ -14,40 +15,196 @@ impl fmt::Display for ParseError {
     }
 }
 
+/// Recoverable syntax problem, parsing continues past it
+#[derive(Debug)]
+pub struct ParseWarning {
+    pub message: String,
+    pub token: Token,
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Syntax warning: {} (found {})", self.message, self.token)
+    }
+}
+
 type ParseResult = Result<ParseNode, ParseError>;
 
+/// Section of a declaration part, ordered as strict declaration order requires them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum DeclarationSection {
+    Label,
+    Const,
+    Type,
+    Var,
+    Subprogram, // Any number of procedures and functions
+}
+
+/// Keywords that open a statement
+const STATEMENT_KEYWORDS: &[Keyword] = &[
+    Keyword::If,
+    Keyword::While,
+    Keyword::For,
+    Keyword::Repeat,
+    Keyword::Case,
+    Keyword::Begin,
+    Keyword::Break,
+    Keyword::Continue,
+    Keyword::Goto,
+];
+
+/// Keywords that may directly follow a statement, so an empty statement can precede them
+const STATEMENT_FOLLOWERS: &[Keyword] = &[Keyword::End, Keyword::Until, Keyword::Else];
+
+/// Keywords that open a section of the declaration part
+const DECLARATION_KEYWORDS: &[Keyword] = &[
+    Keyword::Label,
+    Keyword::Const,
+    Keyword::Type,
+    Keyword::Var,
+    Keyword::Procedure,
+    Keyword::Function,
+];
+
+/// Built-in type names, keywords that `ukuran` accepts as its argument
+const TYPE_KEYWORDS: &[Keyword] = &[Keyword::Integer, Keyword::Real, Keyword::Boolean, Keyword::Char];
+
+/// Keywords commonly written in place of an expected keyword: (expected, found)
+const KEYWORD_CONFUSIONS: &[(Keyword, Keyword)] = &[
+    (Keyword::Then, Keyword::Do),
+    (Keyword::Do, Keyword::Then),
+    (Keyword::To, Keyword::Downto),
+    (Keyword::Of, Keyword::To),
+    (Keyword::Until, Keyword::End),
+    (Keyword::End, Keyword::Until),
+];
+
+/// Deepest nesting of expressions, statements or types, past it parsing stops with
+/// an error instead of running out of stack here or in the passes after parsing
+const MAX_NESTING: usize = 100;
+
+/// Standard Pascal words written out of habit where the rules spell a keyword
+/// otherwise: (expected, found)
+const PASCAL_SPELLINGS: &[(Keyword, &str)] = &[
+    (Keyword::Then, "then"),
+    (Keyword::Do, "do"),
+    (Keyword::Begin, "begin"),
+    (Keyword::End, "end"),
+    (Keyword::Of, "of"),
+    (Keyword::To, "to"),
+    (Keyword::Downto, "downto"),
+    (Keyword::Until, "until"),
+    (Keyword::Var, "var"),
+    (Keyword::Const, "const"),
+    (Keyword::Type, "type"),
+    (Keyword::Goto, "goto"),
+    (Keyword::Case, "case"),
+];
+
 pub struct Parser {
     tokens: Vec<Token>,
     current: usize,
+    eof: Token, // Returned by peek once all tokens are consumed
+    pub warnings: Vec<ParseWarning>,
+    pub strict_semicolons: bool, // ';' only separates statements, no empty statements
+    pub strict_decl_order: bool, // Declaration sections once each, in the standard order
+    pub keywords: KeywordSet, // Spellings of the keywords, for diagnostics
+    open_blocks: Vec<(Token, usize)>, // Openers of constructs 'selesai' closes, with the indentation of their line
+    misclosed_block: Option<Token>, // Last opener whose 'selesai' sat left of its line, likely taken from an outer block
+    depth: usize, // Expressions, statements and types being parsed inside one another
 }
 
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Self {
-        Parser { tokens, current: 0 }
+        let (line, column, offset) = match tokens.last() {
+            Some(last) => {
+                (last.line, last.column + last.value.chars().count(), last.offset + last.value.len())
+            }
+            None => (1, 1, 0),
+        };
+        let eof = Token {
+            token_type: TokenType::Eof,
+            value: String::new(),
+            line,
+            column,
+            offset,
+            keyword: None,
+        };
+
+        Parser {
+            tokens,
+            current: 0,
+            eof,
+            warnings: Vec::new(),
+            strict_semicolons: false,
+            strict_decl_order: false,
+            keywords: KeywordSet::default(),
+            open_blocks: Vec::new(),
+            misclosed_block: None,
+            depth: 0,
+        }
     }
 
     pub fn parse(&mut self) -> ParseResult {
-        let program_node = self.parse_program()?;
-
-        if !self.is_at_end() {
+        // Empty or comment-only input
+        if self.tokens.is_empty() {
             return Err(ParseError {
-                message: "Unexpected token after end of program.".to_string(),
-                token: self.peek().clone(),
+                message: format!(
+                    "Empty program: expected '{}'.",
+                    self.keywords.spelling(Keyword::Program)
+                ),
+                token: self.eof.clone(),
             });
         }
 
+        let program_node = self.parse_program()?;
+        self.expect_end("Unexpected token after end of program.")?;
+
         Ok(program_node)
     }
 
+    /// Parse a standalone expression, such as one line typed into the REPL
+    pub fn parse_expression_entry(&mut self) -> ParseResult {
+        let node = self.parse_expression()?;
+        self.expect_end("Unexpected token after end of expression.")?;
+
+        Ok(node)
+    }
+
+    /// Parse standalone declarations or a single statement, with an optional trailing ';'
+    pub fn parse_statement_entry(&mut self) -> ParseResult {
+        let node = if DECLARATION_KEYWORDS.iter().any(|keyword| self.check_keyword(*keyword)) {
+            self.parse_declaration_part()?
+        } else {
+            let statement = self.parse_statement()?;
+            self.match_token(&TokenType::Semicolon);
+            statement
+        };
+        self.expect_end("Unexpected token after end of statement.")?;
+
+        Ok(node)
+    }
+
+    fn expect_end(&self, message: &str) -> Result<(), ParseError> {
+        if self.is_at_end() {
+            Ok(())
+        } else {
+            Err(ParseError {
+                message: message.to_string(),
+                token: self.peek().clone(),
+            })
+        }
+    }
+
     fn peek(&self) -> &Token {
-        &self.tokens[self.current]
+        self.tokens.get(self.current).unwrap_or(&self.eof)
     }
 
     fn advance(&mut self) -> Token {
         if !self.is_at_end() {
             self.current += 1;
         }
-        self.tokens[self.current - 1].clone()
+        self.previous()
     }
 
     fn is_at_end(&self) -> bool {
@@ -61,6 +218,12 @@ impl Parser {
         self.peek().token_type == *token_type
     }
 
+    fn check_next(&self, token_type: &TokenType) -> bool {
+        self.tokens
+            .get(self.current + 1)
+            .is_some_and(|token| token.token_type == *token_type)
+    }
+
     fn check_value(&self, token_type: &TokenType, value: &str) -> bool {
         if self.is_at_end() {
             return false;
@@ -69,6 +232,10 @@ impl Parser {
         token.token_type == *token_type && token.value == value
     }
 
+    fn check_keyword(&self, keyword: Keyword) -> bool {
+        !self.is_at_end() && self.peek().keyword == Some(keyword)
+    }
+
     fn match_token(&mut self, token_type: &TokenType) -> bool {
         if self.check(token_type) {
             self.advance();
@@ -78,8 +245,8 @@ impl Parser {
         }
     }
 
-    fn match_keyword(&mut self, value: &str) -> bool {
-        if self.check_value(&TokenType::Keyword, value) {
+    fn match_keyword(&mut self, keyword: Keyword) -> bool {
+        if self.check_keyword(keyword) {
             self.advance();
             true
         } else {
@@ -102,23 +269,169 @@ impl Parser {
         }
     }
 
-    fn consume_keyword(
-        &mut self,
-        value: &str,
-        error_message: &str,
-    ) -> Result<ParseNode, ParseError> {
-        if self.check_value(&TokenType::Keyword, value) {
+    fn consume_keyword(&mut self, keyword: Keyword) -> Result<ParseNode, ParseError> {
+        if self.check_keyword(keyword) {
             Ok(ParseNode::new_terminal(self.advance()))
         } else {
+            let error_message = format!("Expected '{}' keyword.", self.keywords.spelling(keyword));
             Err(ParseError {
-                message: error_message.to_string(),
+                message: self.with_keyword_hint(&[keyword], &error_message),
                 token: self.peek().clone(),
             })
         }
     }
 
+    /// Consume the keyword opening a construct that 'selesai' closes
+    fn open_block(&mut self, keyword: Keyword) -> ParseResult {
+        let indent = self.line_indent(self.current);
+        let node = self.consume_keyword(keyword)?;
+        self.open_blocks.push((self.previous(), indent));
+        Ok(node)
+    }
+
+    /// Consume the 'selesai' closing the innermost open construct. A missing one is
+    /// often really missing from an inner block that took this one's 'selesai', which
+    /// shows as that 'selesai' being less indented than the line its block opened on
+    fn close_block(&mut self) -> ParseResult {
+        let Some((opener, indent)) = self.open_blocks.pop() else {
+            return self.consume_keyword(Keyword::End);
+        };
+
+        match self.consume_keyword(Keyword::End) {
+            Ok(node) => {
+                if self.previous().column < indent {
+                    self.misclosed_block = Some(opener);
+                }
+                Ok(node)
+            }
+            Err(mut error) => {
+                let suspect = self.misclosed_block.take().unwrap_or(opener);
+                error.message = format!(
+                    "{} Possibly missing '{}' for block opened at line {}.",
+                    error.message,
+                    self.keywords.spelling(Keyword::End),
+                    suspect.line
+                );
+                Err(error)
+            }
+        }
+    }
+
+    /// Run `parse` one nesting level deeper, failing past MAX_NESTING levels
+    fn nested(&mut self, parse: fn(&mut Self) -> ParseResult) -> ParseResult {
+        if self.depth >= MAX_NESTING {
+            return Err(ParseError {
+                message: format!("Nesting is too deep, at most {} levels are allowed.", MAX_NESTING),
+                token: self.peek().clone(),
+            });
+        }
+
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Column of the first token on the line of the token at `index`
+    fn line_indent(&self, index: usize) -> usize {
+        let Some(token) = self.tokens.get(index) else {
+            return self.eof.column;
+        };
+        self.tokens[..index]
+            .iter()
+            .rev()
+            .take_while(|previous| previous.line == token.line)
+            .last()
+            .unwrap_or(token)
+            .column
+    }
+
+    /// Append a "did you mean" hint when the current token is a common
+    /// confusion for one of the expected keywords
+    fn with_keyword_hint(&self, expected: &[Keyword], error_message: &str) -> String {
+        if self.is_at_end() {
+            return error_message.to_string();
+        }
+
+        let found = self.peek();
+        let confused = |keyword: &Keyword| {
+            found.keyword.is_some_and(|role| KEYWORD_CONFUSIONS.contains(&(*keyword, role)))
+                || PASCAL_SPELLINGS.contains(&(*keyword, found.value.as_str()))
+        };
+
+        match expected.iter().find(|keyword| confused(keyword)) {
+            Some(keyword) => format!(
+                "{} Did you mean '{}' instead of '{}'?",
+                error_message,
+                self.keywords.spelling(*keyword),
+                found.value
+            ),
+            None => error_message.to_string(),
+        }
+    }
+
+    /// Error listing what could appear at the current token: keywords (also used for
+    /// "did you mean" hints) followed by descriptions of other tokens
+    fn expected_one_of(&self, keywords: &[Keyword], others: &[&str]) -> ParseError {
+        let mut alternatives: Vec<String> = keywords
+            .iter()
+            .map(|keyword| format!("'{}'", self.keywords.spelling(*keyword)))
+            .collect();
+        alternatives.extend(others.iter().map(|other| other.to_string()));
+
+        let message = match alternatives.split_last() {
+            Some((last, rest)) if !rest.is_empty() => {
+                format!("Expected one of: {}, or {}.", rest.join(", "), last)
+            }
+            _ => format!("Expected {}.", alternatives.join("")),
+        };
+
+        ParseError {
+            message: self.with_keyword_hint(keywords, &message),
+            token: self.peek().clone(),
+        }
+    }
+
+    /// After a declaration part comes either another declaration section or 'mulai'
+    fn expect_block_start(&self) -> Result<(), ParseError> {
+        if self.check_keyword(Keyword::Begin) {
+            return Ok(());
+        }
+        let keywords: Vec<Keyword> =
+            DECLARATION_KEYWORDS.iter().copied().chain([Keyword::Begin]).collect();
+        Err(self.expected_one_of(&keywords, &[]))
+    }
+
+    /// In strict mode the ';' just matched must be followed by another statement,
+    /// not by a second ';' or by the keyword that closes the statement sequence
+    fn check_separator(&self) -> Result<(), ParseError> {
+        if !self.strict_semicolons {
+            return Ok(());
+        }
+
+        let message = if self.check(&TokenType::Semicolon) {
+            "Empty statement between semicolons is not allowed with strict semicolons.".to_string()
+        } else if let Some(keyword) =
+            STATEMENT_FOLLOWERS.iter().find(|keyword| self.check_keyword(**keyword))
+        {
+            format!(
+                "Semicolon before '{}' is not allowed with strict semicolons.",
+                self.keywords.spelling(*keyword)
+            )
+        } else {
+            return Ok(());
+        };
+
+        Err(ParseError { message, token: self.previous() })
+    }
+
+    /// Last consumed token, the EOF sentinel when nothing has been consumed yet
     fn previous(&self) -> Token {
-        self.tokens[self.current - 1].clone()
+        self.current
+            .checked_sub(1)
+            .and_then(|index| self.tokens.get(index))
+            .unwrap_or(&self.eof)
+            .clone()
     }
 
     // Grammar Rule Functions
@@ -127,6 +440,7 @@ impl Parser {
         let mut node = ParseNode::new(NodeType::Program);
         node.children.push(self.parse_program_header()?);
         node.children.push(self.parse_declaration_part()?);
+        self.expect_block_start()?;
         node.children.push(self.parse_compound_statement()?);
         node.children
             .push(self.consume(TokenType::Dot, "Expected '.' at the end of the program.")?);
@@ -136,7 +450,7 @@ impl Parser {
     fn parse_program_header(&mut self) -> ParseResult {
         let mut node = ParseNode::new(NodeType::ProgramHeader);
         node.children
-            .push(self.consume_keyword("program", "Expected 'program' keyword.")?);
+            .push(self.consume_keyword(Keyword::Program)?);
         node.children
             .push(self.consume(TokenType::Identifier, "Expected program name.")?);
         node.children
@@ -146,18 +460,33 @@ impl Parser {
 
     fn parse_declaration_part(&mut self) -> ParseResult {
         let mut node = ParseNode::new(NodeType::DeclarationPart);
+        let mut last_section = None;
 
         loop {
-            if self.check_value(&TokenType::Keyword, "konstanta") {
+            if self.check_keyword(Keyword::Label) {
+                self.check_section_order(&mut last_section, DeclarationSection::Label)?;
+                node.children.push(self.parse_label_declaration()?);
+            } else if self.check_keyword(Keyword::Const) {
+                self.check_section_order(&mut last_section, DeclarationSection::Const)?;
                 node.children.push(self.parse_const_declaration()?);
-            } else if self.check_value(&TokenType::Keyword, "tipe") {
+            } else if self.check_keyword(Keyword::Type) {
+                self.check_section_order(&mut last_section, DeclarationSection::Type)?;
                 node.children.push(self.parse_type_declaration()?);
-            } else if self.check_value(&TokenType::Keyword, "variabel") {
+            } else if self.check_keyword(Keyword::Var) {
+                self.check_section_order(&mut last_section, DeclarationSection::Var)?;
                 node.children.push(self.parse_var_declaration()?);
-            } else if self.check_value(&TokenType::Keyword, "prosedur")
-                || self.check_value(&TokenType::Keyword, "fungsi")
+            } else if self.check_keyword(Keyword::Procedure)
+                || self.check_keyword(Keyword::Function)
             {
+                self.check_section_order(&mut last_section, DeclarationSection::Subprogram)?;
                 node.children.push(self.parse_subprogram_declaration()?);
+            } else if self.check(&TokenType::Semicolon) {
+                // A stray ';' between declarations or before 'mulai' is skipped
+                let token = self.advance();
+                self.warnings.push(ParseWarning {
+                    message: "Stray ';' in declaration part.".to_string(),
+                    token,
+                });
             } else {
                 break;
             }
@@ -166,15 +495,86 @@ impl Parser {
         Ok(node)
     }
 
+    /// In strict mode each section may only follow the sections before it in the
+    /// standard order, and only subprograms may repeat. `last` is the state: the
+    /// section most recently entered, advanced to `section` when the move is allowed
+    fn check_section_order(
+        &self,
+        last: &mut Option<DeclarationSection>,
+        section: DeclarationSection,
+    ) -> Result<(), ParseError> {
+        if !self.strict_decl_order {
+            return Ok(());
+        }
+
+        let message = match *last {
+            Some(previous) if section == previous && section != DeclarationSection::Subprogram => {
+                format!(
+                    "Second {} is not allowed with strict declaration order.",
+                    self.describe_section(section)
+                )
+            }
+            Some(previous) if section < previous => format!(
+                "{} after {} is not allowed with strict declaration order ({}, {}, {}, {}, then procedures and functions).",
+                self.describe_section(section),
+                self.describe_section(previous),
+                self.keywords.spelling(Keyword::Label),
+                self.keywords.spelling(Keyword::Const),
+                self.keywords.spelling(Keyword::Type),
+                self.keywords.spelling(Keyword::Var)
+            ),
+            _ => {
+                *last = Some(section);
+                return Ok(());
+            }
+        };
+
+        Err(ParseError { message, token: self.peek().clone() })
+    }
+
+    fn describe_section(&self, section: DeclarationSection) -> String {
+        let keyword = match section {
+            DeclarationSection::Label => Keyword::Label,
+            DeclarationSection::Const => Keyword::Const,
+            DeclarationSection::Type => Keyword::Type,
+            DeclarationSection::Var => Keyword::Var,
+            DeclarationSection::Subprogram => return "procedure or function declaration".to_string(),
+        };
+        format!("'{}' section", self.keywords.spelling(keyword))
+    }
+
+    fn parse_label_declaration(&mut self) -> ParseResult {
+        let mut node = ParseNode::new(NodeType::LabelDeclaration);
+
+        node.children
+            .push(self.consume_keyword(Keyword::Label)?);
+        node.children
+            .push(self.consume(TokenType::Number, "Expected label number.")?);
+        while self.match_token(&TokenType::Comma) {
+            node.children.push(ParseNode::new_terminal(self.previous()));
+            node.children
+                .push(self.consume(TokenType::Number, "Expected label number after ','.")?);
+        }
+        node.children
+            .push(self.consume(TokenType::Semicolon, "Expected ';' after label declaration.")?);
+
+        Ok(node)
+    }
+
     fn parse_const_declaration(&mut self) -> ParseResult {
         let mut node = ParseNode::new(NodeType::ConstDeclaration);
 
         node.children
-            .push(self.consume_keyword("konstanta", "Expected 'konstanta' keyword.")?);
+            .push(self.consume_keyword(Keyword::Const)?);
 
         loop {
             node.children
                 .push(self.consume(TokenType::Identifier, "Expected constant identifier.")?);
+            // Typed constant: name : type = value
+            if self.check(&TokenType::Colon) {
+                node.children.push(self.consume(TokenType::Colon, "Expected ':'.")?);
+                node.children.push(self.parse_type()?);
+            }
             node.children.push(self.consume(
                 TokenType::RelationalOperator,
                 "Expected '=' in constant declaration.",
@@ -197,7 +597,7 @@ impl Parser {
         let mut node = ParseNode::new(NodeType::TypeDeclaration);
 
         node.children
-            .push(self.consume_keyword("tipe", "Expected 'tipe' keyword.")?);
+            .push(self.consume_keyword(Keyword::Type)?);
 
         loop {
             node.children
@@ -222,7 +622,7 @@ impl Parser {
         let mut node = ParseNode::new(NodeType::VarDeclaration);
 
         node.children
-            .push(self.consume_keyword("variabel", "Expected 'variabel' keyword.")?);
+            .push(self.consume_keyword(Keyword::Var)?);
 
         loop {
             node.children.push(self.parse_identifier_list()?);
@@ -258,20 +658,29 @@ impl Parser {
     }
 
     fn parse_type(&mut self) -> ParseResult {
+        self.nested(Self::parse_type_node)
+    }
+
+    fn parse_type_node(&mut self) -> ParseResult {
         let mut node = ParseNode::new(NodeType::Type);
 
-        if self.check_value(&TokenType::Keyword, "larik") {
+        if self.check_keyword(Keyword::Array) {
             node.children.push(self.parse_array_type()?);
-        } else if self.check_value(&TokenType::Keyword, "rekaman") {
+        } else if self.check_keyword(Keyword::Record) {
             node.children.push(self.parse_record_type()?);
-        } else if self.check_value(&TokenType::Keyword, "integer")
-            || self.check_value(&TokenType::Keyword, "real")
-            || self.check_value(&TokenType::Keyword, "boolean")
-            || self.check_value(&TokenType::Keyword, "char")
+        } else if self.check_keyword(Keyword::Set) {
+            node.children.push(self.parse_set_type()?);
+        } else if self.check_keyword(Keyword::Integer)
+            || self.check_keyword(Keyword::Real)
+            || self.check_keyword(Keyword::Boolean)
+            || self.check_keyword(Keyword::Char)
+            || self.check(&TokenType::Identifier)
         {
             node.children.push(ParseNode::new_terminal(self.advance()));
-        } else if self.check(&TokenType::Identifier) {
+        } else if self.check(&TokenType::Caret) {
+            // ^T, a pointer to T
             node.children.push(ParseNode::new_terminal(self.advance()));
+            node.children.push(self.parse_type()?);
         } else {
             return Err(ParseError {
                 message: "Expected type name.".to_string(),
@@ -286,9 +695,9 @@ impl Parser {
         let mut node = ParseNode::new(NodeType::ArrayType);
 
         node.children
-            .push(self.consume_keyword("larik", "Expected 'larik' keyword.")?);
-        node.children
-            .push(self.consume(TokenType::LBracket, "Expected '[' after 'larik'.")?);
+            .push(self.consume_keyword(Keyword::Array)?);
+        let message = format!("Expected '[' after '{}'.", self.keywords.spelling(Keyword::Array));
+        node.children.push(self.consume(TokenType::LBracket, &message)?);
 
         node.children.push(self.parse_range()?);
 
@@ -300,12 +709,33 @@ impl Parser {
         node.children
             .push(self.consume(TokenType::RBracket, "Expected ']' after range.")?);
         node.children
-            .push(self.consume_keyword("dari", "Expected 'dari' keyword.")?);
+            .push(self.consume_keyword(Keyword::Of)?);
         node.children.push(self.parse_type()?);
 
         Ok(node)
     }
 
+    fn parse_set_type(&mut self) -> ParseResult {
+        // himpunan dari (type | range)
+        let mut node = ParseNode::new(NodeType::SetType);
+
+        node.children
+            .push(self.consume_keyword(Keyword::Set)?);
+        node.children
+            .push(self.consume_keyword(Keyword::Of)?);
+
+        // A named base type unless a '..' follows, e.g. himpunan dari char vs himpunan dari 1..10
+        let is_named_type = (self.check(&TokenType::Keyword) || self.check(&TokenType::Identifier))
+            && !self.check_next(&TokenType::RangeOperator);
+        if is_named_type {
+            node.children.push(self.parse_type()?);
+        } else {
+            node.children.push(self.parse_range()?);
+        }
+
+        Ok(node)
+    }
+
     fn parse_range(&mut self) -> ParseResult {
         let mut node = ParseNode::new(NodeType::Range);
 
@@ -321,10 +751,10 @@ impl Parser {
         let mut node = ParseNode::new(NodeType::RecordType);
 
         node.children
-            .push(self.consume_keyword("rekaman", "Expected 'rekaman' keyword.")?);
+            .push(self.open_block(Keyword::Record)?);
 
         loop {
-            if self.check_value(&TokenType::Keyword, "selesai") {
+            if self.check_keyword(Keyword::End) {
                 break;
             }
 
@@ -333,7 +763,7 @@ impl Parser {
                 .push(self.consume(TokenType::Colon, "Expected ':' after field identifiers.")?);
             node.children.push(self.parse_type()?);
 
-            if self.check_value(&TokenType::Keyword, "selesai") {
+            if self.check_keyword(Keyword::End) {
                 break;
             }
 
@@ -343,7 +773,7 @@ impl Parser {
         }
 
         node.children
-            .push(self.consume_keyword("selesai", "Expected 'selesai' keyword.")?);
+            .push(self.close_block()?);
 
         Ok(node)
     }
@@ -351,13 +781,17 @@ impl Parser {
     fn parse_subprogram_declaration(&mut self) -> ParseResult {
         let mut node = ParseNode::new(NodeType::SubprogramDeclaration);
 
-        if self.check_value(&TokenType::Keyword, "prosedur") {
+        if self.check_keyword(Keyword::Procedure) {
             node.children.push(self.parse_procedure_declaration()?);
-        } else if self.check_value(&TokenType::Keyword, "fungsi") {
+        } else if self.check_keyword(Keyword::Function) {
             node.children.push(self.parse_function_declaration()?);
         } else {
             return Err(ParseError {
-                message: "Expected 'prosedur' or 'fungsi' keyword.".to_string(),
+                message: format!(
+                    "Expected '{}' or '{}' keyword.",
+                    self.keywords.spelling(Keyword::Procedure),
+                    self.keywords.spelling(Keyword::Function)
+                ),
                 token: self.peek().clone(),
             });
         }
@@ -369,7 +803,7 @@ impl Parser {
         let mut node = ParseNode::new(NodeType::ProcedureDeclaration);
 
         node.children
-            .push(self.consume_keyword("prosedur", "Expected 'prosedur' keyword.")?);
+            .push(self.consume_keyword(Keyword::Procedure)?);
         node.children
             .push(self.consume(TokenType::Identifier, "Expected procedure name.")?);
 
@@ -380,6 +814,7 @@ impl Parser {
         node.children
             .push(self.consume(TokenType::Semicolon, "Expected ';' after procedure header.")?);
         node.children.push(self.parse_declaration_part()?);
+        self.expect_block_start()?;
         node.children.push(self.parse_compound_statement()?);
         node.children
             .push(self.consume(TokenType::Semicolon, "Expected ';' after procedure body.")?);
@@ -391,7 +826,7 @@ impl Parser {
         let mut node = ParseNode::new(NodeType::FunctionDeclaration);
 
         node.children
-            .push(self.consume_keyword("fungsi", "Expected 'fungsi' keyword.")?);
+            .push(self.consume_keyword(Keyword::Function)?);
         node.children
             .push(self.consume(TokenType::Identifier, "Expected function name.")?);
 
@@ -405,6 +840,7 @@ impl Parser {
         node.children
             .push(self.consume(TokenType::Semicolon, "Expected ';' after function header.")?);
         node.children.push(self.parse_declaration_part()?);
+        self.expect_block_start()?;
         node.children.push(self.parse_compound_statement()?);
         node.children
             .push(self.consume(TokenType::Semicolon, "Expected ';' after function body.")?);
@@ -420,7 +856,7 @@ impl Parser {
             "Expected '(' to start parameter list.",
         )?);
 
-        if self.match_keyword("variabel") {
+        if self.match_keyword(Keyword::Var) {
             node.children.push(ParseNode::new_terminal(self.previous()));
         }
 
@@ -434,7 +870,7 @@ impl Parser {
         while self.match_token(&TokenType::Semicolon) {
             node.children.push(ParseNode::new_terminal(self.previous()));
 
-            if self.match_keyword("variabel") {
+            if self.match_keyword(Keyword::Var) {
                 node.children.push(ParseNode::new_terminal(self.previous()));
             }
 
@@ -458,12 +894,12 @@ impl Parser {
         let mut node = ParseNode::new(NodeType::CompoundStatement);
 
         node.children
-            .push(self.consume_keyword("mulai", "Expected 'mulai' keyword.")?);
+            .push(self.open_block(Keyword::Begin)?);
 
         node.children.push(self.parse_statement_list()?);
 
         node.children
-            .push(self.consume_keyword("selesai", "Expected 'selesai' keyword.")?);
+            .push(self.close_block()?);
 
         Ok(node)
     }
@@ -471,13 +907,14 @@ impl Parser {
     fn parse_statement_list(&mut self) -> ParseResult {
         let mut node = ParseNode::new(NodeType::StatementList);
 
-        if !self.check_value(&TokenType::Keyword, "selesai") {
+        if !self.check_keyword(Keyword::End) {
             node.children.push(self.parse_statement()?);
 
             while self.match_token(&TokenType::Semicolon) {
                 node.children.push(ParseNode::new_terminal(self.previous()));
+                self.check_separator()?;
 
-                if self.check_value(&TokenType::Keyword, "selesai") {
+                if self.check_keyword(Keyword::End) {
                     break;
                 }
 
@@ -489,58 +926,161 @@ impl Parser {
     }
 
     fn parse_statement(&mut self) -> ParseResult {
-        if self.check_value(&TokenType::Keyword, "jika") {
+        self.nested(Self::parse_statement_node)
+    }
+
+    fn parse_statement_node(&mut self) -> ParseResult {
+        if self.check(&TokenType::Number) && self.check_next(&TokenType::Colon) {
+            // 10: statement
+            let mut node = ParseNode::new(NodeType::LabeledStatement);
+            node.children.push(ParseNode::new_terminal(self.advance()));
+            node.children.push(ParseNode::new_terminal(self.advance()));
+            node.children.push(self.parse_statement()?);
+            Ok(node)
+        } else if self.check_keyword(Keyword::If) {
             self.parse_if_statement()
-        } else if self.check_value(&TokenType::Keyword, "selama") {
+        } else if self.check_keyword(Keyword::While) {
             self.parse_while_statement()
-        } else if self.check_value(&TokenType::Keyword, "untuk") {
+        } else if self.check_keyword(Keyword::For) {
             self.parse_for_statement()
-        } else if self.check_value(&TokenType::Keyword, "ulangi") {
+        } else if self.check_keyword(Keyword::Repeat) {
             self.parse_repeat_statement()
-        } else if self.check_value(&TokenType::Keyword, "mulai") {
+        } else if self.check_keyword(Keyword::Case) {
+            self.parse_case_statement()
+        } else if self.check_keyword(Keyword::Begin) {
             self.parse_compound_statement()
+        } else if self.match_keyword(Keyword::Break) {
+            let mut node = ParseNode::new(NodeType::BreakStatement);
+            node.children.push(ParseNode::new_terminal(self.previous()));
+            Ok(node)
+        } else if self.match_keyword(Keyword::Continue) {
+            let mut node = ParseNode::new(NodeType::ContinueStatement);
+            node.children.push(ParseNode::new_terminal(self.previous()));
+            Ok(node)
+        } else if self.match_keyword(Keyword::Goto) {
+            let mut node = ParseNode::new(NodeType::GotoStatement);
+            node.children.push(ParseNode::new_terminal(self.previous()));
+            let message =
+                format!("Expected label after '{}'.", self.keywords.spelling(Keyword::Goto));
+            node.children.push(self.consume(TokenType::Number, &message)?);
+            Ok(node)
         } else if self.check(&TokenType::Identifier) {
+            // Look past selectors such as a[i].x^ for ':=' to tell assignments from calls
             let saved_pos = self.current;
             self.advance();
+            loop {
+                if self.check(&TokenType::LBracket) {
+                    self.skip_brackets();
+                } else if self.check(&TokenType::Dot) && self.check_next(&TokenType::Identifier) {
+                    self.advance();
+                    self.advance();
+                } else if self.check(&TokenType::Caret) {
+                    self.advance();
+                } else {
+                    break;
+                }
+            }
+
+            let is_assignment = self.check(&TokenType::AssignOperator);
+            self.current = saved_pos;
 
-            if self.check(&TokenType::AssignOperator) {
-                self.current = saved_pos;
+            if is_assignment {
                 self.parse_assignment_statement()
-            } else if self.check(&TokenType::LParenthesis) {
-                self.current = saved_pos;
-                self.parse_procedure_or_function_call()
             } else {
-                self.current = saved_pos;
                 self.parse_procedure_or_function_call()
             }
-        } else {
+        } else if self.check(&TokenType::Semicolon)
+            || STATEMENT_FOLLOWERS.iter().any(|keyword| self.check_keyword(*keyword))
+        {
+            // Empty statement
             Ok(ParseNode::new(NodeType::StatementList))
+        } else {
+            let keywords: Vec<Keyword> =
+                STATEMENT_KEYWORDS.iter().chain(STATEMENT_FOLLOWERS).copied().collect();
+            Err(self.expected_one_of(&keywords, &["an identifier", "';'"]))
+        }
+    }
+
+    /// Advance past a `[`...`]` group including nested brackets, used only for lookahead
+    fn skip_brackets(&mut self) {
+        let mut depth = 0;
+        while !self.is_at_end() {
+            match self.advance().token_type {
+                TokenType::LBracket => depth += 1,
+                TokenType::RBracket => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return;
+                    }
+                }
+                _ => {}
+            }
         }
     }
 
     fn parse_assignment_statement(&mut self) -> ParseResult {
         let mut node = ParseNode::new(NodeType::AssignmentStatement);
 
-        node.children
-            .push(self.consume(TokenType::Identifier, "Expected identifier.")?);
-        node.children
-            .push(self.consume(TokenType::AssignOperator, "Expected ':=' operator.")?);
-        node.children.push(self.parse_expression()?);
+        let target = self.consume(TokenType::Identifier, "Expected identifier.")?;
+        let target = self.parse_selectors(target)?;
+        let assign = self.consume(TokenType::AssignOperator, "Expected ':=' operator.")?;
+        let value = self.parse_expression()?;
+
+        let (assign, value) = match assign.node_type {
+            NodeType::Terminal(ref token) if token.value != ":=" => {
+                Self::expand_compound_assignment(&target, token, value)
+            }
+            _ => (assign, value),
+        };
+        node.children.push(target);
+        node.children.push(assign);
+        node.children.push(value);
 
         Ok(node)
     }
 
+    /// `x += e` is sugar for `x := x + (e)`, likewise `-=`, `*=` and `/=`. The tree
+    /// is the one the expanded form parses to, so the analyzer checks it as written out
+    fn expand_compound_assignment(target: &ParseNode, token: &Token, value: ParseNode) -> (ParseNode, ParseNode) {
+        let terminal = |token_type: TokenType, value: &str| {
+            ParseNode::new_terminal(Token { token_type, value: value.to_string(), ..token.clone() })
+        };
+        let wrap = |node_type: NodeType, children: Vec<ParseNode>| ParseNode { node_type, children };
+
+        let symbol = &token.value[..1];
+        let operator = terminal(TokenType::ArithmeticOperator, symbol);
+        let left = wrap(NodeType::Factor, vec![target.clone()]);
+        let right = wrap(
+            NodeType::Factor,
+            vec![terminal(TokenType::LParenthesis, "("), value, terminal(TokenType::RParenthesis, ")")],
+        );
+
+        // + and - join terms, * and / join factors
+        let simple_expression = if matches!(symbol, "+" | "-") {
+            let left = wrap(NodeType::Term, vec![left]);
+            let right = wrap(NodeType::Term, vec![right]);
+            wrap(NodeType::SimpleExpression, vec![left, operator, right])
+        } else {
+            wrap(NodeType::SimpleExpression, vec![wrap(NodeType::Term, vec![left, operator, right])])
+        };
+
+        (
+            terminal(TokenType::AssignOperator, ":="),
+            wrap(NodeType::Expression, vec![simple_expression]),
+        )
+    }
+
     fn parse_if_statement(&mut self) -> ParseResult {
         let mut node = ParseNode::new(NodeType::IfStatement);
 
         node.children
-            .push(self.consume_keyword("jika", "Expected 'jika' keyword.")?);
+            .push(self.consume_keyword(Keyword::If)?);
         node.children.push(self.parse_expression()?);
         node.children
-            .push(self.consume_keyword("maka", "Expected 'maka' keyword.")?);
+            .push(self.consume_keyword(Keyword::Then)?);
         node.children.push(self.parse_statement()?);
 
-        if self.match_keyword("selain_itu") {
+        if self.match_keyword(Keyword::Else) {
             node.children.push(ParseNode::new_terminal(self.previous()));
             node.children.push(self.parse_statement()?);
         }
@@ -552,10 +1092,10 @@ impl Parser {
         let mut node = ParseNode::new(NodeType::WhileStatement);
 
         node.children
-            .push(self.consume_keyword("selama", "Expected 'selama' keyword.")?);
+            .push(self.consume_keyword(Keyword::While)?);
         node.children.push(self.parse_expression()?);
         node.children
-            .push(self.consume_keyword("lakukan", "Expected 'lakukan' keyword.")?);
+            .push(self.consume_keyword(Keyword::Do)?);
         node.children.push(self.parse_statement()?);
 
         Ok(node)
@@ -565,27 +1105,87 @@ impl Parser {
         let mut node = ParseNode::new(NodeType::ForStatement);
 
         node.children
-            .push(self.consume_keyword("untuk", "Expected 'untuk' keyword.")?);
+            .push(self.consume_keyword(Keyword::For)?);
         node.children
             .push(self.consume(TokenType::Identifier, "Expected loop variable.")?);
-        node.children
-            .push(self.consume(TokenType::AssignOperator, "Expected ':=' operator.")?);
+        if !self.check_value(&TokenType::AssignOperator, ":=") {
+            return Err(ParseError {
+                message: "Expected ':=' operator.".to_string(),
+                token: self.peek().clone(),
+            });
+        }
+        node.children.push(ParseNode::new_terminal(self.advance()));
         node.children.push(self.parse_expression()?);
 
-        if self.match_keyword("ke") {
-            node.children.push(ParseNode::new_terminal(self.previous()));
-        } else if self.match_keyword("turun_ke") {
+        if self.match_keyword(Keyword::To) || self.match_keyword(Keyword::Downto) {
             node.children.push(ParseNode::new_terminal(self.previous()));
         } else {
+            let message = format!(
+                "Expected '{}' or '{}' keyword.",
+                self.keywords.spelling(Keyword::To),
+                self.keywords.spelling(Keyword::Downto)
+            );
             return Err(ParseError {
-                message: "Expected 'ke' or 'turun_ke' keyword.".to_string(),
+                message: self.with_keyword_hint(&[Keyword::To, Keyword::Downto], &message),
                 token: self.peek().clone(),
             });
         }
 
         node.children.push(self.parse_expression()?);
         node.children
-            .push(self.consume_keyword("lakukan", "Expected 'lakukan' keyword.")?);
+            .push(self.consume_keyword(Keyword::Do)?);
+        node.children.push(self.parse_statement()?);
+
+        Ok(node)
+    }
+
+    fn parse_case_statement(&mut self) -> ParseResult {
+        let mut node = ParseNode::new(NodeType::CaseStatement);
+
+        node.children
+            .push(self.open_block(Keyword::Case)?);
+        node.children.push(self.parse_expression()?);
+        node.children
+            .push(self.consume_keyword(Keyword::Of)?);
+
+        while !self.check_keyword(Keyword::End)
+            && !self.check_keyword(Keyword::Else)
+        {
+            node.children.push(self.parse_case_element()?);
+
+            if self.match_token(&TokenType::Semicolon) {
+                node.children.push(ParseNode::new_terminal(self.previous()));
+            } else {
+                break;
+            }
+        }
+
+        if self.match_keyword(Keyword::Else) {
+            node.children.push(ParseNode::new_terminal(self.previous()));
+            node.children.push(self.parse_statement()?);
+
+            if self.match_token(&TokenType::Semicolon) {
+                node.children.push(ParseNode::new_terminal(self.previous()));
+            }
+        }
+
+        node.children
+            .push(self.close_block()?);
+
+        Ok(node)
+    }
+
+    fn parse_case_element(&mut self) -> ParseResult {
+        // label (, label)* : statement
+        let mut node = ParseNode::new(NodeType::CaseElement);
+
+        node.children.push(self.parse_expression()?);
+        while self.match_token(&TokenType::Comma) {
+            node.children.push(ParseNode::new_terminal(self.previous()));
+            node.children.push(self.parse_expression()?);
+        }
+        node.children
+            .push(self.consume(TokenType::Colon, "Expected ':' after case label.")?);
         node.children.push(self.parse_statement()?);
 
         Ok(node)
@@ -595,29 +1195,39 @@ impl Parser {
         let mut node = ParseNode::new(NodeType::RepeatStatement);
 
         node.children
-            .push(self.consume_keyword("ulangi", "Expected 'ulangi' keyword.")?);
+            .push(self.consume_keyword(Keyword::Repeat)?);
 
         let mut statements = Vec::new();
         loop {
-            if self.check_value(&TokenType::Keyword, "sampai") {
+            if self.check_keyword(Keyword::Until) {
                 break;
             }
 
+            let start = self.current;
             statements.push(self.parse_statement()?);
 
-            if self.check_value(&TokenType::Keyword, "sampai") {
+            if self.check_keyword(Keyword::Until) {
                 break;
             }
 
             if self.match_token(&TokenType::Semicolon) {
                 statements.push(ParseNode::new_terminal(self.previous()));
+                self.check_separator()?;
+            } else if self.strict_semicolons {
+                return Err(ParseError {
+                    message: "Expected ';' between statements.".to_string(),
+                    token: self.peek().clone(),
+                });
+            } else if self.current == start {
+                // An empty statement with nothing after it, 'sampai' is what is missing
+                break;
             }
         }
 
         node.children.extend(statements);
 
         node.children
-            .push(self.consume_keyword("sampai", "Expected 'sampai' keyword.")?);
+            .push(self.consume_keyword(Keyword::Until)?);
         node.children.push(self.parse_expression()?);
 
         Ok(node)
@@ -650,9 +1260,29 @@ impl Parser {
     fn parse_parameter_list(&mut self) -> ParseResult {
         let mut node = ParseNode::new(NodeType::ParameterList);
 
-        node.children.push(self.parse_expression()?);
+        node.children.push(self.parse_argument()?);
 
         while self.match_token(&TokenType::Comma) {
+            node.children.push(ParseNode::new_terminal(self.previous()));
+            node.children.push(self.parse_argument()?);
+        }
+
+        Ok(node)
+    }
+
+    /// Call argument, optionally with the write format `expr:width` or `expr:width:precision`
+    fn parse_argument(&mut self) -> ParseResult {
+        let expression = self.parse_expression()?;
+        if !self.check(&TokenType::Colon) {
+            return Ok(expression);
+        }
+
+        let mut node = ParseNode::new(NodeType::FormattedArgument);
+        node.children.push(expression);
+        for _ in 0..2 {
+            if !self.match_token(&TokenType::Colon) {
+                break;
+            }
             node.children.push(ParseNode::new_terminal(self.previous()));
             node.children.push(self.parse_expression()?);
         }
@@ -660,11 +1290,33 @@ impl Parser {
         Ok(node)
     }
 
+    /// Operator precedence, loosest first:
+    /// 1. relational `= <> < <= > >=` (non-associative)
+    /// 2. additive `+ - atau`
+    /// 3. multiplicative `* / bagi mod dan`
+    /// 4. unary `tidak`, applied to the following factor only
+    ///
+    /// `tidak a = b` would read as `(tidak a) = b`, which is rarely what is meant,
+    /// so a relational operator after a `tidak` operand is rejected in favour of
+    /// the explicit `tidak (a = b)` or `(tidak a) = b`.
     fn parse_expression(&mut self) -> ParseResult {
         let mut node = ParseNode::new(NodeType::Expression);
 
         let left_node = self.parse_simple_expression()?;
 
+        let starts_with_not = left_node
+            .first_token()
+            .is_some_and(|token| token.keyword == Some(Keyword::Not));
+        if starts_with_not && self.check(&TokenType::RelationalOperator) {
+            let not = self.keywords.spelling(Keyword::Not);
+            return Err(ParseError {
+                message: format!(
+                    "Ambiguous '{not}' before a comparison, write '{not} (a = b)' or '({not} a) = b'."
+                ),
+                token: self.peek().clone(),
+            });
+        }
+
         if self.check(&TokenType::RelationalOperator) {
             node.children.push(left_node);
             node.children.push(self.parse_relational_operator()?);
@@ -708,24 +1360,27 @@ impl Parser {
     }
 
     fn parse_factor(&mut self) -> ParseResult {
+        self.nested(Self::parse_factor_node)
+    }
+
+    fn parse_factor_node(&mut self) -> ParseResult {
         let mut node = ParseNode::new(NodeType::Factor);
 
-        if self.match_token(&TokenType::Number) {
-            node.children.push(ParseNode::new_terminal(self.previous()));
-        } else if self.match_token(&TokenType::CharLiteral) {
-            node.children.push(ParseNode::new_terminal(self.previous()));
-        } else if self.match_token(&TokenType::StringLiteral) {
+        if self.match_token(&TokenType::Number)
+            || self.match_token(&TokenType::CharLiteral)
+            || self.match_token(&TokenType::StringLiteral)
+        {
             node.children.push(ParseNode::new_terminal(self.previous()));
         } else if self.match_token(&TokenType::LParenthesis) {
             node.children.push(ParseNode::new_terminal(self.previous()));
             node.children.push(self.parse_expression()?);
             node.children
                 .push(self.consume(TokenType::RParenthesis, "Expected ')' after expression.")?);
-        } else if self.check_value(&TokenType::Keyword, "true")
-            || self.check_value(&TokenType::Keyword, "false")
-        {
+        } else if self.check(&TokenType::LBracket) {
+            node.children.push(self.parse_set_literal()?);
+        } else if self.check_keyword(Keyword::True) || self.check_keyword(Keyword::False) {
             node.children.push(ParseNode::new_terminal(self.advance()));
-        } else if self.check_value(&TokenType::LogicalOperator, "tidak") {
+        } else if self.check_keyword(Keyword::Not) {
             node.children.push(ParseNode::new_terminal(self.advance()));
             node.children.push(self.parse_factor()?);
         } else if self.check(&TokenType::Identifier) {
@@ -741,7 +1396,18 @@ impl Parser {
                     .children
                     .push(self.consume(TokenType::LParenthesis, "Expected '('.")?);
 
-                if !self.check(&TokenType::RParenthesis) {
+                let type_argument = func_call_node.children[0].first_token().is_some_and(|name| {
+                    name.value == "ukuran"
+                        && TYPE_KEYWORDS.iter().any(|keyword| self.check_keyword(*keyword))
+                });
+                if type_argument {
+                    // ukuran(integer), the argument is a type rather than an expression
+                    let mut type_node = ParseNode::new(NodeType::Type);
+                    type_node.children.push(ParseNode::new_terminal(self.advance()));
+                    let mut param_list = ParseNode::new(NodeType::ParameterList);
+                    param_list.children.push(type_node);
+                    func_call_node.children.push(param_list);
+                } else if !self.check(&TokenType::RParenthesis) {
                     func_call_node.children.push(self.parse_parameter_list()?);
                 }
 
@@ -751,24 +1417,91 @@ impl Parser {
 
                 node.children.push(func_call_node);
             } else {
-                node.children
-                    .push(ParseNode::new_terminal(identifier_token));
+                let target = self.parse_selectors(ParseNode::new_terminal(identifier_token))?;
+                node.children.push(target);
+            }
+        } else {
+            let not = format!("'{}'", self.keywords.spelling(Keyword::Not));
+            return Err(self.expected_one_of(
+                &[Keyword::True, Keyword::False],
+                &["a number", "a character", "a string", "an identifier", "'('", "'['", &not],
+            ));
+        }
+
+        Ok(node)
+    }
+
+    /// Apply any trailing `[...]` indexes, `.field` selections and `^` dereferences to `target`
+    fn parse_selectors(&mut self, mut target: ParseNode) -> ParseResult {
+        loop {
+            if self.check(&TokenType::LBracket) {
+                target = self.parse_array_access(target)?;
+            } else if self.check(&TokenType::Dot) && self.check_next(&TokenType::Identifier) {
+                let mut node = ParseNode::new(NodeType::FieldAccess);
+                node.children.push(target);
+                node.children.push(ParseNode::new_terminal(self.advance()));
+                node.children.push(ParseNode::new_terminal(self.advance()));
+                target = node;
+            } else if self.check(&TokenType::Caret) {
+                let mut node = ParseNode::new(NodeType::Dereference);
+                node.children.push(target);
+                node.children.push(ParseNode::new_terminal(self.advance()));
+                target = node;
+            } else {
+                return Ok(target);
+            }
+        }
+    }
+
+    fn parse_array_access(&mut self, target: ParseNode) -> ParseResult {
+        let mut node = ParseNode::new(NodeType::ArrayAccess);
+
+        node.children.push(target);
+        node.children
+            .push(self.consume(TokenType::LBracket, "Expected '[' to start array index.")?);
+        node.children.push(self.parse_expression()?);
+
+        while self.match_token(&TokenType::Comma) {
+            node.children.push(ParseNode::new_terminal(self.previous()));
+            node.children.push(self.parse_expression()?);
+        }
+
+        node.children
+            .push(self.consume(TokenType::RBracket, "Expected ']' after array index.")?);
+
+        Ok(node)
+    }
 
-                while self.match_token(&TokenType::Dot) {
-                    node.children.push(ParseNode::new_terminal(self.previous()));
-                    node.children.push(
-                        self.consume(TokenType::Identifier, "Expected field name after '.'.")?,
-                    );
+    fn parse_set_literal(&mut self) -> ParseResult {
+        // [ (element (, element)*)? ] where element is expression or expression .. expression
+        let mut node = ParseNode::new(NodeType::SetLiteral);
+
+        node.children
+            .push(self.consume(TokenType::LBracket, "Expected '[' to start set.")?);
+
+        if !self.check(&TokenType::RBracket) {
+            loop {
+                let element = self.parse_expression()?;
+                if self.match_token(&TokenType::RangeOperator) {
+                    let mut range = ParseNode::new(NodeType::Range);
+                    range.children.push(element);
+                    range.children.push(ParseNode::new_terminal(self.previous()));
+                    range.children.push(self.parse_expression()?);
+                    node.children.push(range);
+                } else {
+                    node.children.push(element);
+                }
+
+                if !self.match_token(&TokenType::Comma) {
+                    break;
                 }
+                node.children.push(ParseNode::new_terminal(self.previous()));
             }
-        } else {
-            return Err(ParseError {
-                message: "Expected a factor (e.g., number, identifier, or '(expression)')."
-                    .to_string(),
-                token: self.peek().clone(),
-            });
         }
 
+        node.children
+            .push(self.consume(TokenType::RBracket, "Expected ']' after set elements.")?);
+
         Ok(node)
     }
 
@@ -786,10 +1519,9 @@ impl Parser {
     fn match_additive_operator(&mut self) -> Option<Token> {
         if self.check_value(&TokenType::ArithmeticOperator, "+")
             || self.check_value(&TokenType::ArithmeticOperator, "-")
+            || self.check_keyword(Keyword::Or)
         {
             Some(self.advance())
-        } else if self.check_value(&TokenType::LogicalOperator, "atau") {
-            Some(self.advance())
         } else {
             None
         }
@@ -798,14 +1530,11 @@ impl Parser {
     fn match_multiplicative_operator(&mut self) -> Option<Token> {
         if self.check_value(&TokenType::ArithmeticOperator, "*")
             || self.check_value(&TokenType::ArithmeticOperator, "/")
+            || self.check_keyword(Keyword::Div)
+            || self.check_keyword(Keyword::Mod)
+            || self.check_keyword(Keyword::And)
         {
             Some(self.advance())
-        } else if self.check_value(&TokenType::Keyword, "bagi")
-            || self.check_value(&TokenType::Keyword, "mod")
-        {
-            Some(self.advance())
-        } else if self.check_value(&TokenType::LogicalOperator, "dan") {
-            Some(self.advance())
         } else {
             None
         }
@@ -0,0 +1,118 @@
+use std::fmt::Write;
+
+use crate::{
+    ast::AstNode,
+    dfa::Dfa,
+    keyword::{Keyword, KeywordSet},
+    lexer::Lexer,
+    parser::Parser,
+    semantic_analyzer::SemanticAnalyzer,
+    semantic_error::{SemanticError, SemanticErrorKind},
+    token::{Token, TokenType},
+};
+
+/// Interactive session that type-checks one line at a time, declarations persist across lines
+pub struct Repl {
+    dfa: Dfa,
+    keywords: KeywordSet,
+    analyzer: SemanticAnalyzer,
+}
+
+impl Repl {
+    pub fn new(dfa: Dfa) -> Self {
+        let keywords = KeywordSet::from_dfa(&dfa);
+        Repl {
+            analyzer: SemanticAnalyzer::with_keywords(keywords.clone()),
+            keywords,
+            dfa,
+        }
+    }
+
+    /// Analyze one line and describe the result: the type of an expression,
+    /// the decorated AST of declarations and statements, or the errors found
+    pub fn eval(&mut self, line: &str) -> String {
+        let mut lexer = Lexer::new(line.to_string(), self.dfa.clone());
+        let tokens = lexer.tokenize_all();
+
+        let mut text = String::new();
+        if !lexer.errors.is_empty() {
+            for error in &lexer.errors {
+                writeln!(text, "{}", error).unwrap();
+            }
+            return text;
+        }
+        if tokens.is_empty() {
+            return text;
+        }
+
+        // Lines that assign or open with a keyword are statements, the rest are expressions
+        let mut is_statement = tokens.iter().any(|token| token.token_type == TokenType::AssignOperator)
+            || (tokens[0].token_type == TokenType::Keyword
+                && !matches!(tokens[0].keyword, Some(Keyword::True | Keyword::False)));
+
+        let mut analysis = self.analyze(tokens.clone(), is_statement, &mut text);
+        // A procedure call such as writeln(x) reads as an expression without a value,
+        // so it is tried again as a statement
+        let no_value = |errors: &[SemanticError]| {
+            errors.iter().any(|error| matches!(error.kind(), SemanticErrorKind::ProcedureHasNoValue(_)))
+        };
+        if !is_statement && matches!(&analysis, Some(Err(errors)) if no_value(errors)) {
+            let mut statement_text = String::new();
+            let statement = self.analyze(tokens, true, &mut statement_text);
+            if matches!(statement, Some(Ok(_))) {
+                (analysis, text, is_statement) = (statement, statement_text, true);
+            }
+        }
+
+        let Some(analysis) = analysis else {
+            return text;
+        };
+        match analysis {
+            Ok(nodes) if is_statement => {
+                for ast in nodes.iter().filter(|ast| !matches!(ast, AstNode::Empty)) {
+                    write!(text, "{}", ast.display(&self.analyzer.symbol_table)).unwrap();
+                }
+            }
+            Ok(nodes) => {
+                for ast in &nodes {
+                    writeln!(text, "{}", self.analyzer.get_expr_type(ast)).unwrap();
+                }
+            }
+            Err(errors) => {
+                for error in &errors {
+                    writeln!(text, "{}", error).unwrap();
+                }
+            }
+        }
+
+        text
+    }
+
+    /// Parse `tokens` as a statement or an expression and analyze it. Parser warnings
+    /// and a syntax error go to `text`, and a syntax error leaves nothing to analyze
+    fn analyze(
+        &mut self,
+        tokens: Vec<Token>,
+        as_statement: bool,
+        text: &mut String,
+    ) -> Option<Result<Vec<AstNode>, Vec<SemanticError>>> {
+        let mut parser = Parser::new(tokens);
+        parser.keywords = self.keywords.clone();
+        let parsed = if as_statement {
+            parser.parse_statement_entry()
+        } else {
+            parser.parse_expression_entry()
+        };
+        for warning in &parser.warnings {
+            writeln!(text, "{}", warning).unwrap();
+        }
+
+        match parsed {
+            Ok(node) => Some(self.analyzer.analyze_entry(&node)),
+            Err(error) => {
+                writeln!(text, "{}", error).unwrap();
+                None
+            }
+        }
+    }
+}
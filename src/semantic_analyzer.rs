@@ -1,37 +1,200 @@
 use crate::ast::{AstNode, LiteralValue};
+use crate::builtin::{self, ArgKind, Builtin, ResultType};
+use crate::const_eval::{ConstEvaluator, NotConstant};
+use crate::dfa::Dfa;
+use crate::keyword::{Keyword, KeywordSet};
+use crate::lexer::{LexError, Lexer};
 use crate::node::{NodeType, ParseNode};
-use crate::semantic_error::{SemanticError, SemanticErrorKind};
-use crate::symbol_table::{ATabEntry, SymbolTable, TabEntry};
-use crate::token::TokenType;
-use crate::types::{DataType, ObjectKind};
+use crate::operator::Operator;
+use crate::parser::{ParseError, Parser};
+use crate::semantic_error::{SemanticError, SemanticErrorKind, SemanticWarning};
+use crate::simplify;
+use crate::source_map::{Reference, ReferenceKind};
+use crate::symbol_table::{ATabEntry, FIRST_USER_INDEX, SymbolTable, TabEntry};
+use crate::token::{Token, TokenType};
+use crate::types::{DataType, IntWidth, ObjectKind};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Largest array, in storage units, that a declaration may allocate
+const MAX_ARRAY_SIZE: usize = 1 << 24;
+
+/// Built-in type names, suggested for a misspelled type
+const BUILTIN_TYPES: [Keyword; 4] = [Keyword::Integer, Keyword::Real, Keyword::Boolean, Keyword::Char];
+
+/// Why `analyze_source` or `infer_expression_type` produced no result
+#[derive(Debug)]
+pub enum AnalysisError {
+    Lexical(Vec<LexError>), // Neither parsing nor analysis ran
+    Syntax(ParseError), // Analysis did not run
+    Semantic(Vec<SemanticError>),
+}
+
+impl fmt::Display for AnalysisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnalysisError::Lexical(errors) => {
+                for (i, error) in errors.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(f)?;
+                    }
+                    write!(f, "{}", error)?;
+                }
+                Ok(())
+            }
+            AnalysisError::Syntax(error) => write!(f, "{}", error),
+            AnalysisError::Semantic(errors) => {
+                for (i, error) in errors.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(f)?;
+                    }
+                    write!(f, "{}", error)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Labels declared by one routine, with where each is defined and jumped to from
+#[derive(Default)]
+struct LabelScope {
+    declared: Vec<String>,
+    defined: HashMap<String, Vec<usize>>, // Structure path of the labeled statement
+    gotos: Vec<(String, Token, Vec<usize>)>, // Target label, pergi_ke token and its structure path
+}
 
 /// Semantic analyzer that transforms parse tree to decorated AST
 pub struct SemanticAnalyzer {
     pub symbol_table: SymbolTable,
     pub errors: Vec<SemanticError>,
+    pub warnings: Vec<SemanticWarning>,
+    pub warn_shadow: bool, // Warn when a declaration hides an outer identifier
+    pub warn_widening: bool, // Note integer values assigned to real targets
+    pub char_ordinals: bool, // Compare char with integer by the char's ordinal, strict (an error) by default
+    pub warn_ignored_result: bool, // Warn when a function is called as a statement
+    pub warn_non_exhaustive: bool, // Warn when a boolean case without selain_itu misses a value
+    pub int_width: IntWidth, // Range of integer literals and folded constants
+    pub simplify: bool, // Fold constant expressions and drop branches that never run
+    pub short_circuit: bool, // dan/atau stop at the left operand when it decides the result
+    pub references: Vec<Reference>, // Declarations and resolved uses of identifiers, in visiting order
+    keywords: KeywordSet, // Spellings of the reserved words, from the DFA rules
     current_proc: Option<String>,
+    loop_depth: usize, // Loops enclosing the statement being visited
+    const_values: HashMap<usize, LiteralValue>, // Known values of constants by tab index
+    decl_tokens: HashMap<usize, Token>, // Declaring token of variables and subprograms by tab index
+    folded_uses: HashSet<usize>, // Tab indices used by operands folded away, as in ukuran(v)
+    pending_constants: Vec<String>, // While visiting a constant's value: it, then the later ones of its section
+    pending_types: Option<Vec<Token>>, // While visiting a type section: names used before their declaration
+    label_scopes: Vec<LabelScope>, // One per routine being visited, innermost last
+    structure_path: Vec<usize>, // Structured statements enclosing the statement being visited
+    structure_count: usize, // Structured statements visited so far, numbers the path entries
+}
+
+impl Default for SemanticAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SemanticAnalyzer {
     pub fn new() -> Self {
+        SemanticAnalyzer::with_keywords(KeywordSet::default())
+    }
+
+    /// Analyzer whose reserved words are spelled as in `keywords`
+    pub fn with_keywords(keywords: KeywordSet) -> Self {
         SemanticAnalyzer {
-            symbol_table: SymbolTable::new(),
+            symbol_table: SymbolTable::with_keywords(&keywords),
             errors: Vec::new(),
+            warnings: Vec::new(),
+            warn_shadow: false,
+            warn_widening: false,
+            char_ordinals: false,
+            warn_ignored_result: false,
+            warn_non_exhaustive: false,
+            int_width: IntWidth::default(),
+            simplify: false,
+            short_circuit: true,
+            references: Vec::new(),
+            keywords,
             current_proc: None,
+            loop_depth: 0,
+            const_values: HashMap::new(),
+            decl_tokens: HashMap::new(),
+            folded_uses: HashSet::new(),
+            pending_constants: Vec::new(),
+            pending_types: None,
+            label_scopes: Vec::new(),
+            structure_path: Vec::new(),
+            structure_count: 0,
         }
     }
 
     /// Main entry point for semantic analysis
     pub fn analyze(&mut self, parse_tree: &ParseNode) -> Result<AstNode, Vec<SemanticError>> {
-        let ast = self.visit_program(parse_tree);
+        let mut ast = self.visit_program(parse_tree);
+        debug_assert_eq!(self.symbol_table.current_level(), 0, "analysis left a block open");
+        debug_assert_eq!(self.loop_depth, 0, "analysis left a loop open");
+        self.check_unused(&ast);
 
         if self.errors.is_empty() {
+            // Only a checked tree is rewritten, and after the unused checks saw all of it
+            if self.simplify {
+                simplify::simplify(&mut ast, &self.evaluator());
+            }
             Ok(ast)
         } else {
             Err(self.errors.clone())
         }
     }
 
+    /// Parse `tokens` as a program and analyze it, for callers that do not need the parse tree
+    pub fn analyze_source(&mut self, tokens: Vec<Token>) -> Result<AstNode, AnalysisError> {
+        let mut parser = Parser::new(tokens);
+        parser.keywords = self.keywords.clone();
+        let parse_tree = parser.parse().map_err(AnalysisError::Syntax)?;
+        self.analyze(&parse_tree).map_err(AnalysisError::Semantic)
+    }
+
+    /// Type of a standalone expression such as `a + 1`, checked against the
+    /// declarations analyzed so far
+    pub fn infer_expression_type(&mut self, expr_src: &str, dfa: &Dfa) -> Result<DataType, AnalysisError> {
+        let mut lexer = Lexer::new(expr_src.to_string(), dfa.clone());
+        let tokens = lexer.tokenize_all();
+        if !lexer.errors.is_empty() {
+            return Err(AnalysisError::Lexical(lexer.errors));
+        }
+
+        let mut parser = Parser::new(tokens);
+        parser.keywords = self.keywords.clone();
+        let node = parser.parse_expression_entry().map_err(AnalysisError::Syntax)?;
+        let expr = self.visit_expression(&node);
+        let errors = std::mem::take(&mut self.errors);
+        if errors.is_empty() {
+            Ok(self.get_expr_type(&expr))
+        } else {
+            Err(AnalysisError::Semantic(errors))
+        }
+    }
+
+    /// Analyze one standalone entry (declarations, a statement or an expression),
+    /// keeping the symbol table so later entries see earlier declarations
+    pub fn analyze_entry(&mut self, node: &ParseNode) -> Result<Vec<AstNode>, Vec<SemanticError>> {
+        let nodes = match node.node_type {
+            NodeType::DeclarationPart => self.visit_declaration_part(node),
+            NodeType::Expression => vec![self.visit_expression(node)],
+            _ => vec![self.visit_statement(node)],
+        };
+
+        let errors = std::mem::take(&mut self.errors);
+        if errors.is_empty() {
+            Ok(nodes)
+        } else {
+            Err(errors)
+        }
+    }
+
     /// Visit program node
     fn visit_program(&mut self, node: &ParseNode) -> AstNode {
         // program -> program-header declaration-part compound-statement DOT
@@ -49,15 +212,20 @@ impl SemanticAnalyzer {
                 level: 0,
                 address: 0,
             });
+            if let Some(token) = node.children[0].children.get(1).and_then(ParseNode::first_token) {
+                self.reference(ReferenceKind::Decl, tab_index, token);
+            }
 
             // Process declarations
+            self.label_scopes.push(LabelScope::default());
             let declarations = self.visit_declaration_part(&node.children[1]);
 
             // Enter new block for main compound statement (btab[1])
-            let main_block_index = self.symbol_table.enter_block();
+            self.symbol_table.enter_block();
 
             // Process main compound statement
             let body = self.visit_compound_statement(&node.children[2]);
+            self.close_label_scope();
 
             // Exit main block
             self.symbol_table.exit_block();
@@ -88,6 +256,9 @@ impl SemanticAnalyzer {
 
         for child in &node.children {
             match &child.node_type {
+                NodeType::LabelDeclaration => {
+                    declarations.push(self.visit_label_declaration(child));
+                }
                 NodeType::ConstDeclaration => {
                     declarations.extend(self.visit_const_declaration(child));
                 }
@@ -109,6 +280,52 @@ impl SemanticAnalyzer {
         declarations
     }
 
+    /// Visit label declaration, labels go into tab under their number
+    fn visit_label_declaration(&mut self, node: &ParseNode) -> AstNode {
+        // label NUMBER (, NUMBER)* ;
+        let mut labels = Vec::new();
+        let mut tab_indices = Vec::new();
+
+        for child in &node.children {
+            let NodeType::Terminal(token) = &child.node_type else {
+                continue;
+            };
+            if token.token_type != TokenType::Number {
+                continue;
+            }
+            let Some(label) = self.label_name(token) else {
+                continue;
+            };
+
+            if self.symbol_table.lookup_current_scope(&label).is_some() {
+                self.errors.push(SemanticError::new(
+                    SemanticErrorKind::LabelRedeclared(label),
+                    Some(token.clone()),
+                ));
+                continue;
+            }
+
+            let tab_index = self.symbol_table.insert(TabEntry {
+                name: label.clone(),
+                link: None,
+                obj: ObjectKind::Label,
+                data_type: DataType::Void,
+                ref_index: None,
+                normal: true,
+                level: self.symbol_table.current_level(),
+                address: 0,
+            });
+            self.reference(ReferenceKind::Decl, tab_index, token);
+            if let Some(scope) = self.label_scopes.last_mut() {
+                scope.declared.push(label.clone());
+            }
+            labels.push(label);
+            tab_indices.push(tab_index);
+        }
+
+        AstNode::LabelDecl { labels, tab_indices }
+    }
+
     /// Visit variable declaration
     fn visit_var_declaration(&mut self, node: &ParseNode) -> Vec<AstNode> {
         let mut declarations = Vec::new();
@@ -116,7 +333,7 @@ impl SemanticAnalyzer {
 
         while i < node.children.len() {
             // Get identifier list
-            let id_list = self.get_identifier_list(&node.children[i]);
+            let id_tokens = self.get_identifier_tokens(&node.children[i]);
             i += 1; // Skip identifier list
 
             // Skip colon
@@ -132,15 +349,18 @@ impl SemanticAnalyzer {
             // Insert variables into symbol table and create separate VarDecl for each
             let level = self.symbol_table.current_level();
 
-            for name in &id_list {
+            for token in &id_tokens {
+                let name = &token.value;
+
                 // Check for redeclaration
-                if let Some(_) = self.symbol_table.lookup_current_scope(name) {
+                if self.symbol_table.lookup_current_scope(name).is_some() {
                     self.errors.push(SemanticError::redeclared(
                         name.clone(),
-                        None,
+                        Some(token.clone()),
                     ));
                     continue;
                 }
+                self.check_shadowing(token);
 
                 let tab_index = self.symbol_table.insert(TabEntry {
                     name: name.clone(),
@@ -152,6 +372,8 @@ impl SemanticAnalyzer {
                     level,
                     address: 0,  // TODO: change
                 });
+                self.reference(ReferenceKind::Decl, tab_index, token);
+                self.decl_tokens.insert(tab_index, token.clone());
 
                 // Create individual VarDecl for each variable
                 declarations.push(AstNode::VarDecl {
@@ -174,32 +396,81 @@ impl SemanticAnalyzer {
         let mut declarations = Vec::new();
         let mut i = 1; // Skip "konstanta" keyword
 
+        // Names are the identifiers directly under the section, in declaration order
+        let names: Vec<&String> = node
+            .children
+            .iter()
+            .filter_map(|child| match &child.node_type {
+                NodeType::Terminal(token) if token.token_type == TokenType::Identifier => {
+                    Some(&token.value)
+                }
+                _ => None,
+            })
+            .collect();
+        let mut position = 0;
+
         while i < node.children.len() {
             // Get identifier
-            let name = if let NodeType::Terminal(token) = &node.children[i].node_type {
-                token.value.clone()
+            let name_token = if let NodeType::Terminal(token) = &node.children[i].node_type {
+                token.clone()
             } else {
                 i += 1;
                 continue;
             };
+            let name = name_token.value.clone();
             i += 1;
 
+            // Get declared type of a typed constant
+            let declared_type = if matches!(
+                &node.children[i].node_type,
+                NodeType::Terminal(token) if token.token_type == TokenType::Colon
+            ) {
+                let declared_type = self.get_type(&node.children[i + 1]);
+                i += 2;
+                Some(declared_type)
+            } else {
+                None
+            };
+
             // Skip '='
             i += 1;
 
-            // Get value expression
+            // Get value expression, it may only use constants declared before this one.
+            // A redeclared name already has a value, its error is reported below
+            if !names[..position].contains(&&name) {
+                self.pending_constants = names[position..].iter().map(|name| name.to_string()).collect();
+            }
+            position += 1;
+            let value_token = node.children[i].first_token().cloned();
             let value_expr = self.visit_expression(&node.children[i]);
-            let data_type = self.get_expr_type(&value_expr);
+            self.pending_constants.clear();
+            let value_type = self.get_expr_type(&value_expr);
             i += 1;
 
+            // A typed constant takes its declared type if the value fits
+            let data_type = match declared_type {
+                Some(declared_type) => {
+                    if value_type != DataType::Unknown && !self.can_assign(&declared_type, &value_type) {
+                        self.errors.push(SemanticError::type_mismatch(
+                            self.symbol_table.describe_type(&declared_type),
+                            self.symbol_table.describe_type(&value_type),
+                            Some(name_token.clone()),
+                        ));
+                    }
+                    declared_type
+                }
+                None => value_type,
+            };
+
             // Skip semicolon
             i += 1;
 
             // Check for redeclaration
-            if let Some(_) = self.symbol_table.lookup_current_scope(&name) {
-                self.errors.push(SemanticError::redeclared(name.clone(), None));
+            if self.symbol_table.lookup_current_scope(&name).is_some() {
+                self.errors.push(SemanticError::redeclared(name.clone(), Some(name_token)));
                 continue;
             }
+            self.check_shadowing(&name_token);
 
             let tab_index = self.symbol_table.insert(TabEntry {
                 name: name.clone(),
@@ -211,6 +482,21 @@ impl SemanticAnalyzer {
                 level: self.symbol_table.current_level(),
                 address: 0,
             });
+            self.reference(ReferenceKind::Decl, tab_index, &name_token);
+
+            match self.evaluator().evaluate(&value_expr) {
+                Ok(value) => {
+                    debug_assert_eq!(self.symbol_table.tab[tab_index].obj, ObjectKind::Constant);
+                    let value = match (&data_type, value) {
+                        (DataType::Real, LiteralValue::Integer(v)) => LiteralValue::Real(v as f64),
+                        (_, value) => value,
+                    };
+                    self.const_values.insert(tab_index, value);
+                }
+                Err(reason) => {
+                    self.report_not_constant(format!("Value of '{}'", name), reason, value_token);
+                }
+            }
 
             declarations.push(AstNode::ConstDecl {
                 name,
@@ -228,14 +514,20 @@ impl SemanticAnalyzer {
         let mut declarations = Vec::new();
         let mut i = 1; // Skip "tipe" keyword
 
+        // Types of the section may refer to each other in any order
+        let tab_start = self.symbol_table.tab.len();
+        let atab_start = self.symbol_table.atab.len();
+        self.pending_types = Some(Vec::new());
+
         while i < node.children.len() {
             // Get identifier
-            let name = if let NodeType::Terminal(token) = &node.children[i].node_type {
-                token.value.clone()
+            let name_token = if let NodeType::Terminal(token) = &node.children[i].node_type {
+                token.clone()
             } else {
                 i += 1;
                 continue;
             };
+            let name = name_token.value.clone();
             i += 1;
 
             // Skip '='
@@ -249,10 +541,11 @@ impl SemanticAnalyzer {
             i += 1;
 
             // Check for redeclaration
-            if let Some(_) = self.symbol_table.lookup_current_scope(&name) {
-                self.errors.push(SemanticError::redeclared(name.clone(), None));
+            if self.symbol_table.lookup_current_scope(&name).is_some() {
+                self.errors.push(SemanticError::redeclared(name.clone(), Some(name_token)));
                 continue;
             }
+            self.check_shadowing(&name_token);
 
             let tab_index = self.symbol_table.insert(TabEntry {
                 name: name.clone(),
@@ -264,6 +557,18 @@ impl SemanticAnalyzer {
                 level: self.symbol_table.current_level(),
                 address: 0,
             });
+            self.reference(ReferenceKind::Decl, tab_index, &name_token);
+
+            // A type may not contain itself by value (directly or through other types)
+            if self
+                .type_size(&DataType::UserDefined(name.clone()), &mut Vec::new())
+                .is_err()
+            {
+                self.errors.push(SemanticError::new(
+                    SemanticErrorKind::RecursiveType(name.clone()),
+                    Some(name_token),
+                ));
+            }
 
             declarations.push(AstNode::TypeDecl {
                 name,
@@ -272,6 +577,28 @@ impl SemanticAnalyzer {
             });
         }
 
+        for token in self.pending_types.take().unwrap_or_default() {
+            if let Some(idx) = self.symbol_table.lookup(&token.value)
+                && self.symbol_table.tab[idx].obj == ObjectKind::Type
+            {
+                continue;
+            }
+            self.unknown_type(&token);
+
+            // Later uses of the section's types see an erroneous type, not the bad name
+            let unknown = DataType::UserDefined(token.value.clone());
+            for entry in &mut self.symbol_table.tab[tab_start..] {
+                if entry.data_type == unknown {
+                    entry.data_type = DataType::Unknown;
+                }
+            }
+            for entry in &mut self.symbol_table.atab[atab_start..] {
+                if entry.element_type == unknown {
+                    entry.element_type = DataType::Unknown;
+                }
+            }
+        }
+
         declarations
     }
 
@@ -294,6 +621,7 @@ impl SemanticAnalyzer {
         // prosedur IDENTIFIER (params)? SEMICOLON declarations compound-statement SEMICOLON
         let mut idx = 1; // Skip "prosedur" keyword
 
+        let name_token = node.children[idx].first_token().cloned();
         let name = if let NodeType::Terminal(token) = &node.children[idx].node_type {
             token.value.clone()
         } else {
@@ -302,11 +630,12 @@ impl SemanticAnalyzer {
         idx += 1;
 
         // Check for redeclaration
-        if let Some(_) = self.symbol_table.lookup_current_scope(&name) {
-            self.errors.push(SemanticError::redeclared(name.clone(), None));
+        if self.symbol_table.lookup_current_scope(&name).is_some() {
+            self.errors.push(SemanticError::redeclared(name.clone(), name_token.clone()));
         }
 
         // Enter new block
+        let outer_level = self.symbol_table.current_level();
         let block_index = self.symbol_table.enter_block();
 
         // Check if parameters exist and save the node index
@@ -323,6 +652,9 @@ impl SemanticAnalyzer {
 
         // Insert procedure into symbol table (at parent level)
         self.symbol_table.exit_block();
+        if let Some(token) = &name_token {
+            self.check_shadowing(token);
+        }
         let tab_index = self.symbol_table.insert(TabEntry {
             name: name.clone(),
             link: None,
@@ -333,9 +665,13 @@ impl SemanticAnalyzer {
             level: self.symbol_table.current_level(),
             address: 0,
         });
+        if let Some(token) = name_token {
+            self.reference(ReferenceKind::Decl, tab_index, &token);
+            self.decl_tokens.insert(tab_index, token);
+        }
         
-        // Re-enter block for procedure body
-        self.symbol_table.enter_block();
+        // Re-enter the procedure's block for its parameters and body
+        self.symbol_table.reenter_block(block_index);
         
         // process parameters
         let params = if let Some(param_idx) = param_node_idx {
@@ -345,14 +681,22 @@ impl SemanticAnalyzer {
         };
 
         // Process declarations
+        self.label_scopes.push(LabelScope::default());
         let declarations = self.visit_declaration_part(&node.children[idx]);
         idx += 1;
 
         // Process body
         let body = self.visit_compound_statement(&node.children[idx]);
+        self.close_label_scope();
 
         // Exit block
         self.symbol_table.exit_block();
+        debug_assert_eq!(
+            self.symbol_table.current_level(),
+            outer_level,
+            "'{}' did not close the blocks it opened",
+            name
+        );
 
         AstNode::ProcDecl {
             name,
@@ -369,6 +713,7 @@ impl SemanticAnalyzer {
         // fungsi IDENTIFIER (params)? COLON type SEMICOLON declarations compound-statement SEMICOLON
         let mut idx = 1; // Skip "fungsi" keyword
 
+        let name_token = node.children[idx].first_token().cloned();
         let name = if let NodeType::Terminal(token) = &node.children[idx].node_type {
             token.value.clone()
         } else {
@@ -377,11 +722,12 @@ impl SemanticAnalyzer {
         idx += 1;
 
         // Check for redeclaration
-        if let Some(_) = self.symbol_table.lookup_current_scope(&name) {
-            self.errors.push(SemanticError::redeclared(name.clone(), None));
+        if self.symbol_table.lookup_current_scope(&name).is_some() {
+            self.errors.push(SemanticError::redeclared(name.clone(), name_token.clone()));
         }
 
         // Enter new block for function
+        let outer_level = self.symbol_table.current_level();
         let block_index = self.symbol_table.enter_block();
 
         // Check if parameters exist and save the node index
@@ -405,6 +751,9 @@ impl SemanticAnalyzer {
 
         // Insert function into symbol table (at parent level)
         self.symbol_table.exit_block();
+        if let Some(token) = &name_token {
+            self.check_shadowing(token);
+        }
         let tab_index = self.symbol_table.insert(TabEntry {
             name: name.clone(),
             link: None,
@@ -415,7 +764,11 @@ impl SemanticAnalyzer {
             level: self.symbol_table.current_level(),
             address: 0,
         });
-        self.symbol_table.enter_block();
+        if let Some(token) = name_token {
+            self.reference(ReferenceKind::Decl, tab_index, &token);
+            self.decl_tokens.insert(tab_index, token);
+        }
+        self.symbol_table.reenter_block(block_index);
 
         // process parameters
         let params = if let Some(param_idx) = param_node_idx {
@@ -427,16 +780,24 @@ impl SemanticAnalyzer {
         self.current_proc = Some(name.clone());
 
         // Process declarations
+        self.label_scopes.push(LabelScope::default());
         let declarations = self.visit_declaration_part(&node.children[idx]);
         idx += 1;
 
         // Process body
         let body = self.visit_compound_statement(&node.children[idx]);
+        self.close_label_scope();
 
         self.current_proc = None;
 
         // Exit block
         self.symbol_table.exit_block();
+        debug_assert_eq!(
+            self.symbol_table.current_level(),
+            outer_level,
+            "'{}' did not close the blocks it opened",
+            name
+        );
 
         AstNode::FuncDecl {
             name,
@@ -455,15 +816,18 @@ impl SemanticAnalyzer {
         let mut i = 1; // Skip '('
 
         while i < node.children.len() - 1 { // Skip ')'
-            // Skip optional 'variabel' keyword
-            if let NodeType::Terminal(token) = &node.children[i].node_type {
-                if token.value == "variabel" {
-                    i += 1; // Skip the 'variabel' keyword
-                }
+            // 'variabel' makes the group pass by reference
+            let is_var = matches!(
+                &node.children[i].node_type,
+                NodeType::Terminal(token) if token.keyword == Some(Keyword::Var)
+            );
+            if is_var {
+                i += 1; // Skip the 'variabel' keyword
             }
             
             // Get identifier list
-            let id_list = self.get_identifier_list(&node.children[i]);
+            let id_tokens = self.get_identifier_tokens(&node.children[i]);
+            let id_list: Vec<String> = id_tokens.iter().map(|token| token.value.clone()).collect();
             i += 1;
 
             // Skip colon
@@ -475,24 +839,29 @@ impl SemanticAnalyzer {
 
             // Insert parameters into symbol table
             let mut tab_indices = Vec::new();
-            for name in &id_list {
+            for (name, token) in id_list.iter().zip(&id_tokens) {
+                if self.symbol_table.lookup_current_scope(name).is_some() {
+                    self.errors.push(SemanticError::redeclared(name.clone(), Some(token.clone())));
+                }
+                self.check_shadowing(token);
                 let tab_index = self.symbol_table.insert(TabEntry {
                     name: name.clone(),
                     link: None,
                     obj: ObjectKind::Parameter,
                     data_type: data_type.clone(),
                     ref_index: None,
-                    normal: true,
+                    normal: !is_var,
                     level: self.symbol_table.current_level(),
                     address: 0,
                 });
+                self.reference(ReferenceKind::Decl, tab_index, token);
                 tab_indices.push(tab_index);
             }
 
             params.push(AstNode::ParamDecl {
                 names: id_list,
                 data_type,
-                is_var: false,
+                is_var,
                 tab_indices,
             });
 
@@ -511,7 +880,7 @@ impl SemanticAnalyzer {
     fn visit_compound_statement(&mut self, node: &ParseNode) -> AstNode {
         // mulai statement-list selesai
         if node.children.len() >= 2 {
-            let statements = self.visit_statement_list(&node.children[1]);
+            let statements = self.visit_statement_list(&node.children[1].children);
             let block_index = self.symbol_table.current_block();
             let level = self.symbol_table.current_level();
             return AstNode::Block { 
@@ -523,19 +892,33 @@ impl SemanticAnalyzer {
         AstNode::Empty
     }
 
-    /// Visit statement list
-    fn visit_statement_list(&mut self, node: &ParseNode) -> Vec<AstNode> {
+    /// Visit statement list, warning at the first statement no path reaches after
+    /// an unconditional jump. A labeled statement can be reached again by pergi_ke
+    fn visit_statement_list(&mut self, children: &[ParseNode]) -> Vec<AstNode> {
         let mut statements = Vec::new();
+        let mut reachable = true;
+        let mut reported = false;
 
-        for child in &node.children {
+        for child in children {
             if let NodeType::Terminal(_) = child.node_type {
                 continue; // Skip semicolons
             }
 
             let stmt = self.visit_statement(child);
-            if !matches!(stmt, AstNode::Empty) {
-                statements.push(stmt);
+            if matches!(stmt, AstNode::Empty) {
+                continue;
+            }
+            if matches!(stmt, AstNode::Labeled { .. }) {
+                reachable = true;
+            } else if !reachable && !reported {
+                self.warnings.push(SemanticWarning::unreachable(child.first_token().cloned()));
+                reported = true;
             }
+            if reachable && always_transfers(&stmt) {
+                reachable = false;
+                reported = false;
+            }
+            statements.push(stmt);
         }
 
         statements
@@ -543,63 +926,266 @@ impl SemanticAnalyzer {
 
     /// Visit statement
     fn visit_statement(&mut self, node: &ParseNode) -> AstNode {
-        match &node.node_type {
+        // Entering a structured statement extends the path that label definitions
+        // and jumps are compared on
+        let structured = matches!(
+            node.node_type,
+            NodeType::IfStatement
+                | NodeType::WhileStatement
+                | NodeType::RepeatStatement
+                | NodeType::ForStatement
+                | NodeType::CaseStatement
+                | NodeType::CompoundStatement
+        );
+        if structured {
+            self.structure_count += 1;
+            self.structure_path.push(self.structure_count);
+        }
+
+        let statement = match &node.node_type {
             NodeType::AssignmentStatement => self.visit_assignment_statement(node),
             NodeType::IfStatement => self.visit_if_statement(node),
             NodeType::WhileStatement => self.visit_while_statement(node),
+            NodeType::RepeatStatement => self.visit_repeat_statement(node),
+            NodeType::BreakStatement | NodeType::ContinueStatement => self.visit_loop_control(node),
             NodeType::ForStatement => self.visit_for_statement(node),
-            NodeType::ProcedureOrFunctionCall => self.visit_procedure_call(node),
+            NodeType::CaseStatement => self.visit_case_statement(node),
+            NodeType::ProcedureOrFunctionCall => {
+                let call = self.visit_procedure_call(node);
+                if self.warn_ignored_result
+                    && let AstNode::ProcCall { name, tab_index, .. } = &call
+                    && self.symbol_table.tab[*tab_index].obj == ObjectKind::Function
+                {
+                    self.warnings.push(SemanticWarning::result_ignored(
+                        name.clone(),
+                        node.first_token().cloned(),
+                    ));
+                }
+                call
+            }
             NodeType::CompoundStatement => self.visit_compound_statement(node),
+            NodeType::LabeledStatement => self.visit_labeled_statement(node),
+            NodeType::GotoStatement => self.visit_goto_statement(node),
             _ => AstNode::Empty,
+        };
+
+        if structured {
+            self.structure_path.pop();
         }
+        statement
     }
 
-    /// Visit assignment statement
-    fn visit_assignment_statement(&mut self, node: &ParseNode) -> AstNode {
-        // IDENTIFIER := expression
-        let var_name = if let NodeType::Terminal(token) = &node.children[0].node_type {
-            token.value.clone()
-        } else {
+    /// Visit a labeled statement, the label must be declared by the enclosing routine
+    fn visit_labeled_statement(&mut self, node: &ParseNode) -> AstNode {
+        // NUMBER : statement
+        let (Some(token), Some(statement)) = (node.first_token(), node.children.get(2)) else {
             return AstNode::Empty;
         };
+        let Some(label) = self.label_name(token) else {
+            return self.visit_statement(statement);
+        };
 
-        // Lookup variable
-        let tab_index = match self.symbol_table.lookup(&var_name) {
-            Some(idx) => idx,
-            None => {
-                self.errors
-                    .push(SemanticError::undeclared(var_name.clone(), None));
-                return AstNode::Empty;
+        match self.label_scopes.last_mut() {
+            Some(scope) if scope.declared.contains(&label) => {
+                if scope.defined.contains_key(&label) {
+                    self.errors.push(SemanticError::new(
+                        SemanticErrorKind::LabelRedefined(label.clone()),
+                        Some(token.clone()),
+                    ));
+                } else {
+                    scope.defined.insert(label.clone(), self.structure_path.clone());
+                }
+                if let Some(idx) = self.symbol_table.lookup(&label) {
+                    self.reference(ReferenceKind::Use, idx, token);
+                }
             }
+            _ => self.errors.push(SemanticError::new(
+                SemanticErrorKind::UndeclaredLabel(label.clone()),
+                Some(token.clone()),
+            )),
+        }
+
+        AstNode::Labeled {
+            label,
+            statement: Box::new(self.visit_statement(statement)),
+        }
+    }
+
+    /// Visit pergi_ke, which may also leave the routine for a label of an enclosing one
+    fn visit_goto_statement(&mut self, node: &ParseNode) -> AstNode {
+        // pergi_ke NUMBER
+        let Some(NodeType::Terminal(token)) = node.children.get(1).map(|child| &child.node_type)
+        else {
+            return AstNode::Empty;
+        };
+        let Some(label) = self.label_name(token) else {
+            return AstNode::Empty;
         };
 
-        let var_type = self.symbol_table.tab[tab_index].data_type.clone();
-        let var_level = self.symbol_table.tab[tab_index].level;
+        let innermost = self.label_scopes.len().checked_sub(1);
+        let target = self
+            .label_scopes
+            .iter()
+            .rposition(|scope| scope.declared.contains(&label));
+        match target {
+            Some(scope_index) => {
+                // A jump out of the routine is compared from the routine's top level
+                let path = if Some(scope_index) == innermost {
+                    self.structure_path.clone()
+                } else {
+                    Vec::new()
+                };
+                self.label_scopes[scope_index].gotos.push((label.clone(), token.clone(), path));
+                if let Some(idx) = self.symbol_table.lookup(&label) {
+                    self.reference(ReferenceKind::Use, idx, token);
+                }
+            }
+            None => self.errors.push(SemanticError::new(
+                SemanticErrorKind::UndeclaredLabel(label.clone()),
+                Some(token.clone()),
+            )),
+        }
 
-        let target = AstNode::Var {
-            name: var_name.clone(),
-            data_type: var_type.clone(),
-            tab_index,
-            level: var_level,
+        AstNode::Goto { label }
+    }
+
+    /// Check the jumps to the labels of the routine just visited
+    fn close_label_scope(&mut self) {
+        let Some(scope) = self.label_scopes.pop() else {
+            return;
+        };
+
+        let mut undefined = HashSet::new();
+        for (label, token, path) in scope.gotos {
+            match scope.defined.get(&label) {
+                // The label's statement encloses or sits beside the jump
+                Some(label_path) if path.starts_with(label_path) => {}
+                Some(_) => {
+                    self.warnings.push(SemanticWarning::jump_into(label, Some(token)));
+                }
+                None => {
+                    if undefined.insert(label.clone()) {
+                        self.errors.push(SemanticError::new(
+                            SemanticErrorKind::LabelNotDefined {
+                                label,
+                                goto: self.keywords.spelling(Keyword::Goto).to_string(),
+                            },
+                            Some(token),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Normalized label number, reporting numbers that are not valid labels
+    fn label_name(&mut self, token: &Token) -> Option<String> {
+        match token.value.parse::<u32>() {
+            Ok(value) if value <= 9999 => Some(value.to_string()),
+            _ => {
+                self.errors.push(SemanticError::new(
+                    SemanticErrorKind::InvalidLabel(token.value.clone()),
+                    Some(token.clone()),
+                ));
+                None
+            }
+        }
+    }
+
+    /// Visit assignment statement
+    fn visit_assignment_statement(&mut self, node: &ParseNode) -> AstNode {
+        // (IDENTIFIER | array-access | dereference | field-access) := expression
+        let (target, var_token) = match &node.children[0].node_type {
+            NodeType::Terminal(token) => {
+                let var_name = token.value.clone();
+
+                // Lookup variable
+                let tab_index = match self.symbol_table.lookup(&var_name) {
+                    Some(idx) => idx,
+                    None => {
+                        self.errors
+                            .push(SemanticError::undeclared(var_name, Some(token.clone())));
+                        return AstNode::Empty;
+                    }
+                };
+                self.reference(ReferenceKind::Use, tab_index, token);
+
+                let target = AstNode::Var {
+                    name: var_name,
+                    data_type: self.symbol_table.tab[tab_index].data_type.clone(),
+                    tab_index,
+                    level: self.symbol_table.tab[tab_index].level,
+                };
+                (target, token.clone())
+            }
+            NodeType::ArrayAccess | NodeType::Dereference | NodeType::FieldAccess => {
+                let target = self.visit_selector(&node.children[0]);
+                match node.children[0].first_token() {
+                    Some(token) => (target, token.clone()),
+                    None => return AstNode::Empty,
+                }
+            }
+            _ => return AstNode::Empty,
         };
+        let var_type = self.get_expr_type(&target);
 
         // Visit value expression
         let value = self.visit_expression(&node.children[2]);
         let value_type = self.get_expr_type(&value);
 
-        // Type check
-        if !DataType::can_assign(&var_type, &value_type) {
+        // An unknown value had its error reported already, an empty one (left by a
+        // malformed tree) did not
+        let value_reported = value_type == DataType::Unknown && !matches!(value, AstNode::Empty);
+
+        // Type check, a target already reported as invalid is skipped
+        if var_type == DataType::Integer && value_type == DataType::Real {
+            self.errors.push(SemanticError::new(
+                SemanticErrorKind::RealToInteger,
+                Some(var_token),
+            ));
+        } else if var_type == DataType::Real && value_type == DataType::Integer {
+            if self.warn_widening {
+                self.warnings.push(SemanticWarning::widening(
+                    var_token.value.clone(),
+                    Some(var_token),
+                ));
+            }
+        } else if (var_type.is_scalar() && value_type.is_composite())
+            || (var_type.is_composite() && value_type.is_scalar())
+        {
+            // The atab/btab index in array[0] means nothing to the reader, spell the types out
+            self.errors.push(SemanticError::new(
+                SemanticErrorKind::CompositeScalarAssignment {
+                    target: self.symbol_table.describe_type(&var_type),
+                    value: self.symbol_table.describe_type(&value_type),
+                    composite_value: value_type.is_composite(),
+                },
+                Some(var_token),
+            ));
+        } else if var_type != DataType::Unknown
+            && !value_reported
+            && !self.can_assign(&var_type, &value_type)
+        {
             self.errors.push(SemanticError::type_mismatch(
-                format!("{}", var_type),
-                format!("{}", value_type),
-                None,
+                self.symbol_table.describe_type(&var_type),
+                self.symbol_table.describe_type(&value_type),
+                Some(var_token),
             ));
         }
 
+        // A var parameter names the caller's storage, the store goes through the reference
+        let by_reference = matches!(
+            &target,
+            AstNode::Var { tab_index, .. }
+                if self.symbol_table.tab[*tab_index].obj == ObjectKind::Parameter
+                    && !self.symbol_table.tab[*tab_index].normal
+        );
+
         AstNode::Assign {
             target: Box::new(target),
             value: Box::new(value),
             data_type: var_type,
+            by_reference,
         }
     }
 
@@ -610,21 +1196,37 @@ impl SemanticAnalyzer {
         let cond_type = self.get_expr_type(&condition);
 
         // Check condition is boolean
-        if cond_type != DataType::Boolean {
+        if cond_type != DataType::Boolean && cond_type != DataType::Unknown {
             self.errors.push(SemanticError::new(
                 SemanticErrorKind::ConditionNotBoolean,
-                None,
+                node.children[1].first_token().cloned(),
             ));
         }
 
         let then_stmt = self.visit_statement(&node.children[3]);
 
         let else_stmt = if node.children.len() > 5 {
-            Some(Box::new(self.visit_statement(&node.children[5])))
+            Some(self.visit_statement(&node.children[5]))
         } else {
             None
         };
 
+        // Collapse an else-if into a single multi-branch chain
+        let else_stmt = match else_stmt {
+            Some(AstNode::If { condition: else_condition, then_stmt: else_then, else_stmt }) => {
+                return AstNode::IfChain {
+                    branches: vec![(condition, then_stmt), (*else_condition, *else_then)],
+                    else_stmt,
+                };
+            }
+            Some(AstNode::IfChain { branches: rest, else_stmt }) => {
+                let mut branches = vec![(condition, then_stmt)];
+                branches.extend(rest);
+                return AstNode::IfChain { branches, else_stmt };
+            }
+            other => other.map(Box::new),
+        };
+
         AstNode::If {
             condition: Box::new(condition),
             then_stmt: Box::new(then_stmt),
@@ -632,6 +1234,106 @@ impl SemanticAnalyzer {
         }
     }
 
+    /// Visit case statement
+    fn visit_case_statement(&mut self, node: &ParseNode) -> AstNode {
+        // kasus expression dari case-element (; case-element)* (; selain_itu statement)? selesai
+        let selector = self.visit_expression(&node.children[1]);
+        let mut selector_type = self.get_expr_type(&selector);
+
+        if !selector_type.is_ordinal() && selector_type != DataType::Unknown {
+            self.errors.push(SemanticError::new(
+                SemanticErrorKind::NotOrdinal(format!("{}", selector_type)),
+                node.children[1].first_token().cloned(),
+            ));
+            selector_type = DataType::Unknown; // Labels are not checked against an invalid selector
+        }
+
+        let mut branches = Vec::new();
+        let mut else_stmt = None;
+        let mut seen = HashSet::new();
+
+        let mut i = 3; // Skip "kasus", selector and "dari"
+        while i < node.children.len() {
+            match &node.children[i].node_type {
+                NodeType::CaseElement => {
+                    branches.push(self.visit_case_element(&node.children[i], &selector_type, &mut seen));
+                }
+                NodeType::Terminal(token) if token.keyword == Some(Keyword::Else) => {
+                    else_stmt = Some(Box::new(self.visit_statement(&node.children[i + 1])));
+                    i += 1;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+
+        // Without a default, a boolean case must cover both values
+        if self.warn_non_exhaustive && selector_type == DataType::Boolean && else_stmt.is_none() {
+            for (value, keyword) in [(true, Keyword::True), (false, Keyword::False)] {
+                if !seen.contains(&LiteralValue::Boolean(value).to_string()) {
+                    self.warnings.push(SemanticWarning::non_exhaustive(
+                        self.keywords.spelling(keyword).to_string(),
+                        self.keywords.spelling(Keyword::Else),
+                        node.children[0].first_token().cloned(),
+                    ));
+                }
+            }
+        }
+
+        AstNode::Case {
+            selector: Box::new(selector),
+            branches,
+            else_stmt,
+        }
+    }
+
+    /// Visit case element, labels must be distinct constants of the selector type
+    fn visit_case_element(
+        &mut self,
+        node: &ParseNode,
+        selector_type: &DataType,
+        seen: &mut HashSet<String>,
+    ) -> (Vec<AstNode>, AstNode) {
+        // label (, label)* : statement
+        let mut labels = Vec::new();
+
+        for child in &node.children[..node.children.len() - 1] {
+            if !matches!(child.node_type, NodeType::Expression) {
+                continue; // Skip commas and colon
+            }
+
+            let label = self.visit_expression(child);
+            let label_type = self.get_expr_type(&label);
+            let token = child.first_token().cloned();
+
+            match self.evaluator().evaluate(&label) {
+                Err(reason) => {
+                    self.report_not_constant("Case label".to_string(), reason, token);
+                }
+                Ok(_) if !self.types_equal(&label_type, selector_type) && *selector_type != DataType::Unknown => {
+                    self.errors.push(SemanticError::type_mismatch(
+                        format!("{}", selector_type),
+                        format!("{}", label_type),
+                        token,
+                    ));
+                }
+                Ok(value) => {
+                    if !seen.insert(value.to_string()) {
+                        self.errors.push(SemanticError::new(
+                            SemanticErrorKind::DuplicateCaseLabel(value.to_string()),
+                            token,
+                        ));
+                    }
+                }
+            }
+
+            labels.push(label);
+        }
+
+        let stmt = self.visit_statement(&node.children[node.children.len() - 1]);
+        (labels, stmt)
+    }
+
     /// Visit while statement
     fn visit_while_statement(&mut self, node: &ParseNode) -> AstNode {
         // selama expression lakukan statement
@@ -639,14 +1341,14 @@ impl SemanticAnalyzer {
         let cond_type = self.get_expr_type(&condition);
 
         // Check condition is boolean
-        if cond_type != DataType::Boolean {
+        if cond_type != DataType::Boolean && cond_type != DataType::Unknown {
             self.errors.push(SemanticError::new(
                 SemanticErrorKind::ConditionNotBoolean,
-                None,
+                node.children[1].first_token().cloned(),
             ));
         }
 
-        let body = self.visit_statement(&node.children[3]);
+        let body = self.visit_loop_body(&node.children[3]);
 
         AstNode::While {
             condition: Box::new(condition),
@@ -654,45 +1356,110 @@ impl SemanticAnalyzer {
         }
     }
 
+    /// Visit repeat statement
+    fn visit_repeat_statement(&mut self, node: &ParseNode) -> AstNode {
+        // ulangi statement (; statement)* sampai expression
+        let Some((condition_node, rest)) = node.children.split_last() else {
+            return AstNode::Empty;
+        };
+
+        self.loop_depth += 1;
+        let body = self.visit_statement_list(rest);
+        self.loop_depth -= 1;
+
+        let condition = self.visit_expression(condition_node);
+        let cond_type = self.get_expr_type(&condition);
+        if cond_type != DataType::Boolean && cond_type != DataType::Unknown {
+            self.errors.push(SemanticError::new(
+                SemanticErrorKind::ConditionNotBoolean,
+                condition_node.first_token().cloned(),
+            ));
+        }
+
+        AstNode::Repeat {
+            body,
+            condition: Box::new(condition),
+        }
+    }
+
+    /// Visit the body of a loop, where keluar/lanjut are allowed
+    fn visit_loop_body(&mut self, node: &ParseNode) -> AstNode {
+        self.loop_depth += 1;
+        let body = self.visit_statement(node);
+        self.loop_depth -= 1;
+        body
+    }
+
+    /// Visit keluar/lanjut, which must be inside a loop
+    fn visit_loop_control(&mut self, node: &ParseNode) -> AstNode {
+        if self.loop_depth == 0 {
+            let token = node.first_token().cloned();
+            let keyword = token.as_ref().map_or(String::new(), |token| token.value.clone());
+            self.errors.push(SemanticError::new(
+                SemanticErrorKind::LoopControlOutsideLoop(keyword),
+                token,
+            ));
+        }
+
+        match node.node_type {
+            NodeType::BreakStatement => AstNode::Break,
+            _ => AstNode::Continue,
+        }
+    }
+
     /// Visit for statement
     fn visit_for_statement(&mut self, node: &ParseNode) -> AstNode {
         // untuk IDENTIFIER := expression (ke|turun_ke) expression lakukan statement
-        let var_name = if let NodeType::Terminal(token) = &node.children[1].node_type {
-            token.value.clone()
+        let var_token = if let NodeType::Terminal(token) = &node.children[1].node_type {
+            token.clone()
         } else {
             return AstNode::Empty;
         };
+        let var_name = var_token.value.clone();
 
         // Lookup variable
         let tab_index = match self.symbol_table.lookup(&var_name) {
             Some(idx) => idx,
             None => {
                 self.errors
-                    .push(SemanticError::undeclared(var_name.clone(), None));
+                    .push(SemanticError::undeclared(var_name.clone(), Some(var_token)));
                 return AstNode::Empty;
             }
         };
+        self.reference(ReferenceKind::Use, tab_index, &var_token);
 
         let var_type = self.symbol_table.tab[tab_index].data_type.clone();
 
         // Check variable is integer
-        if var_type != DataType::Integer {
+        if var_type != DataType::Integer && var_type != DataType::Unknown {
             self.errors.push(SemanticError::new(
                 SemanticErrorKind::InvalidLoopVariable,
-                None,
+                Some(var_token),
             ));
         }
 
         let start = self.visit_expression(&node.children[3]);
         
-        let is_downto = if let NodeType::Terminal(token) = &node.children[4].node_type {
-            token.value == "turun_ke"
-        } else {
-            false
-        };
+        let direction = node.children[4].first_token();
+        let is_downto = direction.is_some_and(|token| token.keyword == Some(Keyword::Downto));
 
         let end = self.visit_expression(&node.children[5]);
-        let body = self.visit_statement(&node.children[7]);
+
+        // Constant bounds that run the wrong way make a loop that never runs
+        let evaluator = self.evaluator();
+        if let (Ok(first), Ok(last)) = (evaluator.evaluate_ordinal(&start), evaluator.evaluate_ordinal(&end))
+            && (if is_downto { first < last } else { first > last })
+        {
+            let keyword = if is_downto { Keyword::Downto } else { Keyword::To };
+            self.warnings.push(SemanticWarning::empty_loop(
+                self.keywords.spelling(keyword),
+                first,
+                last,
+                direction.cloned(),
+            ));
+        }
+
+        let body = self.visit_loop_body(&node.children[7]);
 
         AstNode::For {
             var_name,
@@ -707,41 +1474,208 @@ impl SemanticAnalyzer {
     /// Visit procedure call
     fn visit_procedure_call(&mut self, node: &ParseNode) -> AstNode {
         // IDENTIFIER (parameter-list)?
-        let name = if let NodeType::Terminal(token) = &node.children[0].node_type {
-            token.value.clone()
+        let name_token = if let NodeType::Terminal(token) = &node.children[0].node_type {
+            token.clone()
         } else {
             return AstNode::Empty;
         };
+        let name = name_token.value.clone();
 
         // Lookup procedure/function (predefined procedures in reserved words)
         let tab_index = match self.symbol_table.lookup(&name) {
             Some(idx) => idx,
             None => {
-                self.errors.push(SemanticError::undeclared(name.clone(), None));
+                self.errors.push(SemanticError::undeclared(name.clone(), Some(name_token)));
                 return AstNode::Empty;
             }
         };
+        self.reference(ReferenceKind::Use, tab_index, &name_token);
 
-        // Get arguments if present
-        let args = if node.children.len() > 2 {
-            // Has parameters
-            let param_list_idx = node
-                .children
-                .iter()
-                .position(|c| matches!(c.node_type, NodeType::ParameterList));
-            if let Some(idx) = param_list_idx {
-                self.visit_parameter_list(&node.children[idx])
-            } else {
-                Vec::new()
-            }
-        } else {
-            Vec::new()
+        if tab_index < FIRST_USER_INDEX && name == "ukuran" {
+            return self.visit_size_of(&name_token, node);
+        }
+
+        // Arguments come only from a parameter list, so `f()` has none
+        let args = match node
+            .children
+            .iter()
+            .find(|c| matches!(c.node_type, NodeType::ParameterList))
+        {
+            Some(param_list) => self.visit_parameter_list(param_list),
+            None => Vec::new(),
         };
 
-        AstNode::ProcCall {
-            name,
-            args,
+        self.visit_call(&name_token, tab_index, args)
+    }
+
+    /// Fold ukuran(T) or ukuran(v) to the storage size of the type, so it can be used as a constant
+    fn visit_size_of(&mut self, name_token: &Token, node: &ParseNode) -> AstNode {
+        let erroneous = AstNode::Literal { value: LiteralValue::Integer(0), data_type: DataType::Unknown };
+        let args: Vec<&ParseNode> = node
+            .children
+            .iter()
+            .filter(|c| matches!(c.node_type, NodeType::ParameterList))
+            .flat_map(|param_list| &param_list.children)
+            .filter(|c| matches!(c.node_type, NodeType::Expression | NodeType::Type))
+            .collect();
+        let [arg] = args.as_slice() else {
+            self.errors.push(SemanticError::new(
+                SemanticErrorKind::WrongParameterCount { min: 1, max: Some(1), found: args.len() },
+                Some(name_token.clone()),
+            ));
+            return erroneous;
+        };
+
+        let data_type = if matches!(arg.node_type, NodeType::Type) {
+            self.get_type(arg)
+        } else {
+            let value = self.visit_expression(arg);
+            let found = match &value {
+                // Undeclared or otherwise erroneous, already reported
+                AstNode::Var { data_type: DataType::Unknown, .. }
+                | AstNode::Literal { data_type: DataType::Unknown, .. } => return erroneous,
+                AstNode::Var { tab_index, .. } => match &self.symbol_table.tab[*tab_index].obj {
+                    ObjectKind::Type | ObjectKind::Variable | ObjectKind::Parameter => None,
+                    obj => Some(format!("{} '{}'", obj, self.symbol_table.tab[*tab_index].name)),
+                },
+                AstNode::ArrayAccess { .. } | AstNode::FieldAccess { .. } | AstNode::Deref { .. } => None,
+                _ => Some("an expression".to_string()),
+            };
+            if let Some(found) = found {
+                self.errors.push(SemanticError::new(
+                    SemanticErrorKind::NotSizeable(found),
+                    arg.first_token().cloned(),
+                ));
+                return erroneous;
+            }
+            // The operand is gone once folded, but it was still used
+            let mut used = HashSet::new();
+            self.collect_usages(&value, &mut used);
+            self.folded_uses.extend(used);
+            self.get_expr_type(&value)
+        };
+
+        match self.type_size(&data_type, &mut Vec::new()) {
+            Ok(size) if data_type != DataType::Unknown => AstNode::Literal {
+                value: LiteralValue::Integer(size as i64),
+                data_type: DataType::Integer,
+            },
+            _ => erroneous,
+        }
+    }
+
+    /// Build a call, checking built-ins against their signature
+    fn visit_call(&mut self, name_token: &Token, tab_index: usize, args: Vec<AstNode>) -> AstNode {
+        let name = name_token.value.clone();
+        let mut data_type = self.symbol_table.tab[tab_index].data_type.clone();
+
+        if !matches!(self.symbol_table.tab[tab_index].obj, ObjectKind::Procedure | ObjectKind::Function) {
+            self.errors.push(SemanticError::new(
+                SemanticErrorKind::NotCallable(name.clone()),
+                Some(name_token.clone()),
+            ));
+            data_type = DataType::Unknown;
+        } else if tab_index < FIRST_USER_INDEX
+            && let Some(builtin) = builtin::find(&name)
+        {
+            data_type = self.check_builtin_call(builtin, name_token, &args);
+        }
+
+        let is_write = tab_index < FIRST_USER_INDEX && matches!(name.as_str(), "write" | "writeln");
+        if !is_write && args.iter().any(|arg| matches!(arg, AstNode::WriteArg { .. })) {
+            self.errors.push(SemanticError::new(
+                SemanticErrorKind::FormatOutsideWrite(name.clone()),
+                Some(name_token.clone()),
+            ));
+        }
+
+        AstNode::ProcCall {
+            name,
+            args,
             tab_index,
+            data_type,
+        }
+    }
+
+    /// Check arity and argument types of a built-in call, returning its result type
+    fn check_builtin_call(&mut self, builtin: &Builtin, name_token: &Token, args: &[AstNode]) -> DataType {
+        if let Some((min, max)) = builtin.arity_mismatch(args.len()) {
+            self.errors.push(SemanticError::new(
+                SemanticErrorKind::WrongParameterCount {
+                    min,
+                    max,
+                    found: args.len(),
+                },
+                Some(name_token.clone()),
+            ));
+            return builtin.data_type();
+        }
+
+        let arg_types: Vec<DataType> = args.iter().map(|arg| self.get_expr_type(arg)).collect();
+        for arg_type in &arg_types {
+            if !builtin.arg_kind.accepts(arg_type) {
+                self.errors.push(SemanticError::type_mismatch(
+                    builtin.arg_kind.describe().to_string(),
+                    format!("{}", arg_type),
+                    Some(name_token.clone()),
+                ));
+                return builtin.data_type();
+            }
+        }
+
+        // succ of the last value of its type or pred of the first has no result,
+        // which can only be told at compile time for a constant argument
+        if matches!(builtin.name, "succ" | "pred") {
+            let evaluator = self.evaluator();
+            let out_of_range = evaluator.evaluate(&args[0]).is_ok_and(|value| {
+                matches!(evaluator.apply_builtin(builtin.name, value), Err(NotConstant::OutOfRange))
+            });
+            if out_of_range {
+                self.warnings.push(SemanticWarning::ordinal_out_of_range(
+                    builtin.name,
+                    format!("{}", arg_types[0]),
+                    Some(name_token.clone()),
+                ));
+            }
+        }
+
+        // new and dispose change the pointer itself, so it must be a variable
+        if matches!(builtin.arg_kind, ArgKind::Pointer) {
+            for arg in args {
+                let not_variable = match arg {
+                    AstNode::Var { name, tab_index, .. } => {
+                        let obj = &self.symbol_table.tab[*tab_index].obj;
+                        (!matches!(obj, ObjectKind::Variable | ObjectKind::Parameter)).then_some(name)
+                    }
+                    AstNode::ProcCall { name, .. } => Some(name),
+                    _ => None,
+                };
+                if let Some(name) = not_variable {
+                    self.errors.push(SemanticError::new(
+                        SemanticErrorKind::NotAssignable(name.clone()),
+                        Some(name_token.clone()),
+                    ));
+                }
+            }
+        }
+
+        // write/writeln print simple values, strings and char arrays,
+        // optionally to the file given first
+        if matches!(builtin.name, "write" | "writeln") {
+            let to_file = arg_types.first() == Some(&DataType::Text);
+            for arg_type in &arg_types[usize::from(to_file)..] {
+                if !self.is_printable(arg_type) {
+                    self.errors.push(SemanticError::new(
+                        SemanticErrorKind::NotPrintable(format!("{}", arg_type)),
+                        Some(name_token.clone()),
+                    ));
+                }
+            }
+        }
+
+        match builtin.result {
+            ResultType::SameAsArgument => arg_types[0].clone(),
+            ResultType::Fixed(_) => builtin.data_type(),
         }
     }
 
@@ -754,139 +1688,179 @@ impl SemanticAnalyzer {
                 continue; // Skip commas
             }
 
-            if let NodeType::Expression = child.node_type {
-                args.push(self.visit_expression(child));
+            match child.node_type {
+                NodeType::Expression => args.push(self.visit_expression(child)),
+                NodeType::FormattedArgument => args.push(self.visit_formatted_argument(child)),
+                _ => {}
             }
         }
 
         args
     }
 
+    /// Visit `expr:width` or `expr:width:prec`, both integers and a precision only for reals
+    fn visit_formatted_argument(&mut self, node: &ParseNode) -> AstNode {
+        let Some(first) = node.children.first() else {
+            return AstNode::Empty;
+        };
+        let expr = self.visit_expression(first);
+
+        let mut formats = Vec::new();
+        for pair in node.children[1..].chunks(2) {
+            let [colon, format] = pair else {
+                break;
+            };
+            let NodeType::Terminal(colon) = &colon.node_type else {
+                break;
+            };
+            let format = self.visit_expression(format);
+            let found = self.get_expr_type(&format);
+            if found != DataType::Integer && found != DataType::Unknown {
+                self.errors.push(SemanticError::type_mismatch(
+                    "integer".to_string(),
+                    format!("{}", found),
+                    Some(colon.clone()),
+                ));
+            }
+            formats.push((colon.clone(), Box::new(format)));
+        }
+
+        let mut formats = formats.into_iter();
+        let width = formats.next().map(|(_, width)| width);
+        let prec = formats.next().map(|(colon, prec)| {
+            let found = self.get_expr_type(&expr);
+            if found != DataType::Real && found != DataType::Unknown {
+                self.errors.push(SemanticError::type_mismatch(
+                    "real".to_string(),
+                    format!("{}", found),
+                    Some(colon),
+                ));
+            }
+            prec
+        });
+
+        AstNode::WriteArg { expr: Box::new(expr), width, prec }
+    }
+
     /// Visit expression
     fn visit_expression(&mut self, node: &ParseNode) -> AstNode {
         // expression -> simple-expression (relational-op simple-expression)?
-        if node.children.len() == 1 {
-            return self.visit_simple_expression(&node.children[0]);
-        } else if node.children.len() == 3 {
-            let left = self.visit_simple_expression(&node.children[0]);
-            let op = if let NodeType::Terminal(token) = &node.children[1].node_type {
-                token.value.clone()
-            } else {
-                "=".to_string()
-            };
-            let right = self.visit_simple_expression(&node.children[2]);
-
-            let left_type = self.get_expr_type(&left);
-            let right_type = self.get_expr_type(&right);
-
-            let result_type = match DataType::get_relational_result_type(&left_type, &right_type) {
-                Ok(t) => t,
-                Err(_) => {
-                    self.errors.push(SemanticError::invalid_operation(
-                        op.clone(),
-                        format!("{} and {}", left_type, right_type),
-                        None,
-                    ));
-                    DataType::Unknown
-                }
-            };
+        // Dispatch on the operator rather than the child count, so a malformed
+        // node (e.g. after error recovery) yields Empty instead of panicking
+        let is_simple = |child: &ParseNode| matches!(child.node_type, NodeType::SimpleExpression);
+        let operator = node.children.iter().position(|child| {
+            matches!(&child.node_type, NodeType::Terminal(token)
+                if token.token_type == TokenType::RelationalOperator)
+        });
 
-            return AstNode::BinOp {
-                op,
-                left: Box::new(left),
-                right: Box::new(right),
-                data_type: result_type,
-            };
+        match operator {
+            None => match node.children.as_slice() {
+                [operand] if is_simple(operand) => self.visit_simple_expression(operand),
+                _ => AstNode::Empty,
+            },
+            Some(i) => {
+                let left = i.checked_sub(1).and_then(|j| node.children.get(j));
+                let right = node.children.get(i + 1);
+
+                match (left, &node.children[i].node_type, right) {
+                    (Some(left), NodeType::Terminal(token), Some(right))
+                        if is_simple(left) && is_simple(right) =>
+                    {
+                        let left = self.visit_simple_expression(left);
+                        let right = self.visit_simple_expression(right);
+                        self.visit_binary_op(token, left, right)
+                    }
+                    _ => AstNode::Empty,
+                }
+            }
         }
+    }
 
-        AstNode::Empty
+    /// Build a binary operation, reporting operand types the operator does not accept
+    fn visit_binary_op(&mut self, op_token: &Token, left: AstNode, right: AstNode) -> AstNode {
+        let Some(op) = Operator::from_token(op_token) else {
+            return AstNode::Empty;
+        };
+
+        let left_type = self.get_expr_type(&left);
+        let right_type = self.get_expr_type(&right);
+
+        let compares_composites = op.is_relational()
+            && (left_type.is_composite() || right_type.is_composite())
+            && left_type != DataType::Unknown
+            && right_type != DataType::Unknown;
+        let compares_char_with_integer = matches!(
+            (&left_type, &right_type),
+            (DataType::Char, DataType::Integer) | (DataType::Integer, DataType::Char)
+        );
+        let result = if compares_composites {
+            self.compare_composites(op, &left_type, &right_type)
+        } else if self.char_ordinals && op.is_relational() && compares_char_with_integer {
+            Ok(DataType::Boolean)
+        } else {
+            op.result_type(&left_type, &right_type).map_err(|_| {
+                SemanticErrorKind::InvalidOperation {
+                    op: op.spelling(&self.keywords).to_string(),
+                    types: format!("{} and {}", left_type, right_type),
+                }
+            })
+        };
+
+        let result_type = match result {
+            Ok(t) => t,
+            Err(kind) => {
+                self.errors.push(SemanticError::new(kind, Some(op_token.clone())));
+                // A comparison is boolean even when its operands cannot be compared
+                if compares_composites { DataType::Boolean } else { DataType::Unknown }
+            }
+        };
+
+        AstNode::BinOp {
+            op,
+            left: Box::new(left),
+            right: Box::new(right),
+            data_type: result_type,
+        }
     }
 
     /// Visit simple expression
     fn visit_simple_expression(&mut self, node: &ParseNode) -> AstNode {
         // simple-expression -> (sign)? term (additive-op term)*
         let mut i = 0;
-        let mut result: Option<AstNode> = None;
 
         // Check for unary sign
-        if let NodeType::Terminal(token) = &node.children[i].node_type {
-            if token.value == "+" || token.value == "-" {
-                i += 1;
-                let operand = self.visit_term(&node.children[i]);
-                i += 1;
+        let mut result = match &node.children[i].node_type {
+            NodeType::Terminal(token) if token.value == "+" || token.value == "-" => {
+                let operand = self.visit_term(&node.children[i + 1]);
+                i += 2;
 
                 if token.value == "-" {
                     let op_type = self.get_expr_type(&operand);
-                    result = Some(AstNode::UnaryOp {
-                        op: "-".to_string(),
+                    AstNode::UnaryOp {
+                        op: Operator::Sub,
                         operand: Box::new(operand),
                         data_type: op_type,
-                    });
+                    }
                 } else {
-                    result = Some(operand);
+                    operand
                 }
             }
-        }
-
-        // If no unary sign, start with first term
-        if result.is_none() {
-            result = Some(self.visit_term(&node.children[i]));
-            i += 1;
-        }
+            _ => {
+                i += 1;
+                self.visit_term(&node.children[0])
+            }
+        };
 
         // Process remaining terms with operators
-        while i < node.children.len() {
+        while i + 1 < node.children.len() {
             if let NodeType::Terminal(token) = &node.children[i].node_type {
-                let op = token.value.clone();
-                i += 1;
-
-                if i < node.children.len() {
-                    let right = self.visit_term(&node.children[i]);
-                    i += 1;
-
-                    let left = result.unwrap();
-                    let left_type = self.get_expr_type(&left);
-                    let right_type = self.get_expr_type(&right);
-
-                    let result_type = if op == "atau" {
-                        match DataType::get_logical_result_type(&left_type, &right_type) {
-                            Ok(t) => t,
-                            Err(_) => {
-                                self.errors.push(SemanticError::invalid_operation(
-                                    op.clone(),
-                                    format!("{} and {}", left_type, right_type),
-                                    None,
-                                ));
-                                DataType::Unknown
-                            }
-                        }
-                    } else {
-                        match DataType::get_arithmetic_result_type(&left_type, &right_type) {
-                            Ok(t) => t,
-                            Err(_) => {
-                                self.errors.push(SemanticError::invalid_operation(
-                                    op.clone(),
-                                    format!("{} and {}", left_type, right_type),
-                                    None,
-                                ));
-                                DataType::Unknown
-                            }
-                        }
-                    };
-
-                    result = Some(AstNode::BinOp {
-                        op,
-                        left: Box::new(left),
-                        right: Box::new(right),
-                        data_type: result_type,
-                    });
-                }
-            } else {
-                i += 1;
+                let right = self.visit_term(&node.children[i + 1]);
+                result = self.visit_binary_op(token, result, right);
             }
+            i += 2;
         }
 
-        result.unwrap_or(AstNode::Empty)
+        result
     }
 
     /// Visit term
@@ -895,55 +1869,12 @@ impl SemanticAnalyzer {
         let mut result = self.visit_factor(&node.children[0]);
         let mut i = 1;
 
-        while i < node.children.len() {
+        while i + 1 < node.children.len() {
             if let NodeType::Terminal(token) = &node.children[i].node_type {
-                let op = token.value.clone();
-                i += 1;
-
-                if i < node.children.len() {
-                    let right = self.visit_factor(&node.children[i]);
-                    i += 1;
-
-                    let left = result;
-                    let left_type = self.get_expr_type(&left);
-                    let right_type = self.get_expr_type(&right);
-
-                    let result_type = if op == "dan" {
-                        match DataType::get_logical_result_type(&left_type, &right_type) {
-                            Ok(t) => t,
-                            Err(_) => {
-                                self.errors.push(SemanticError::invalid_operation(
-                                    op.clone(),
-                                    format!("{} and {}", left_type, right_type),
-                                    None,
-                                ));
-                                DataType::Unknown
-                            }
-                        }
-                    } else {
-                        match DataType::get_arithmetic_result_type(&left_type, &right_type) {
-                            Ok(t) => t,
-                            Err(_) => {
-                                self.errors.push(SemanticError::invalid_operation(
-                                    op.clone(),
-                                    format!("{} and {}", left_type, right_type),
-                                    None,
-                                ));
-                                DataType::Unknown
-                            }
-                        }
-                    };
-
-                    result = AstNode::BinOp {
-                        op,
-                        left: Box::new(left),
-                        right: Box::new(right),
-                        data_type: result_type,
-                    };
-                }
-            } else {
-                i += 1;
+                let right = self.visit_factor(&node.children[i + 1]);
+                result = self.visit_binary_op(token, result, right);
             }
+            i += 2;
         }
 
         result
@@ -969,54 +1900,53 @@ impl SemanticAnalyzer {
                             };
                         }
                     } else {
-                        if let Ok(val) = token.value.parse::<i64>() {
-                            return AstNode::Literal {
-                                value: LiteralValue::Integer(val),
-                                data_type: DataType::Integer,
-                            };
+                        match Self::integer_value(&token.value) {
+                            Some(val) if self.int_width.contains(val) => {
+                                return AstNode::Literal {
+                                    value: LiteralValue::Integer(val),
+                                    data_type: DataType::Integer,
+                                };
+                            }
+                            _ => {
+                                self.errors.push(SemanticError::new(
+                                    SemanticErrorKind::IntegerOutOfRange {
+                                        literal: token.value.clone(),
+                                        bits: self.int_width.bits(),
+                                        max: self.int_width.bounds().1,
+                                    },
+                                    Some(token.clone()),
+                                ));
+                                return AstNode::Literal {
+                                    value: LiteralValue::Integer(0),
+                                    data_type: DataType::Unknown,
+                                };
+                            }
                         }
                     }
                     AstNode::Empty
                 }
                 TokenType::CharLiteral => AstNode::Literal {
-                    value: LiteralValue::Char(token.value.chars().nth(0).unwrap_or(' ')),
+                    value: LiteralValue::Char(
+                        Self::literal_text(token).chars().next().unwrap_or(' '),
+                    ),
                     data_type: DataType::Char,
                 },
                 TokenType::StringLiteral => AstNode::Literal {
-                    value: LiteralValue::String(token.value.clone()),
+                    value: LiteralValue::String(Self::literal_text(token)),
                     data_type: DataType::String,
                 },
                 TokenType::Identifier => {
-                    let name = token.value.clone();
-
-                    // Lookup identifier
-                    match self.symbol_table.lookup(&name) {
-                        Some(idx) => {
-                            let entry = &self.symbol_table.tab[idx];
-                            AstNode::Var {
-                                name: name.clone(),
-                                data_type: entry.data_type.clone(),
-                                tab_index: idx,
-                                level: entry.level,
-                            }
-                        }
-                        None => {
-                            self.errors.push(SemanticError::undeclared(name.clone(), None));
-                            AstNode::Literal {
-                                value: LiteralValue::Integer(0),
-                                data_type: DataType::Unknown,
-                            }
-                        }
-                    }
+                    let value = self.visit_identifier(token);
+                    self.check_has_value(value, token)
                 }
                 TokenType::Keyword => {
-                    // Handle true/false
-                    if token.value == "true" {
+                    // Handle true/false (benar/salah)
+                    if token.keyword == Some(Keyword::True) {
                         return AstNode::Literal {
                             value: LiteralValue::Boolean(true),
                             data_type: DataType::Boolean,
                         };
-                    } else if token.value == "false" {
+                    } else if token.keyword == Some(Keyword::False) {
                         return AstNode::Literal {
                             value: LiteralValue::Boolean(false),
                             data_type: DataType::Boolean,
@@ -1024,39 +1954,494 @@ impl SemanticAnalyzer {
                     }
                     AstNode::Empty
                 }
-                TokenType::LogicalOperator if token.value == "tidak" => {
-                    // Unary not
-                    let operand = self.visit_factor(&node.children[1]);
+                TokenType::LogicalOperator if token.keyword == Some(Keyword::Not) => {
+                    // Unary not, a malformed tree may have lost the operand
+                    let Some(operand_node) = node.children.get(1) else {
+                        self.errors.push(SemanticError::new(
+                            SemanticErrorKind::MissingOperand(token.value.clone()),
+                            Some(token.clone()),
+                        ));
+                        return AstNode::Empty;
+                    };
+                    let operand = self.visit_factor(operand_node);
                     let op_type = self.get_expr_type(&operand);
 
-                    if op_type != DataType::Boolean {
+                    if op_type != DataType::Boolean && op_type != DataType::Unknown {
                         self.errors.push(SemanticError::invalid_operation(
-                            "tidak".to_string(),
+                            token.value.clone(),
                             format!("{}", op_type),
-                            None,
+                            Some(token.clone()),
                         ));
                     }
 
                     AstNode::UnaryOp {
-                        op: "tidak".to_string(),
+                        op: Operator::Not,
                         operand: Box::new(operand),
-                        data_type: DataType::Boolean,
+                        data_type: if op_type == DataType::Unknown { op_type } else { DataType::Boolean },
                     }
                 }
                 TokenType::LParenthesis => {
                     // Parenthesized expression
-                    self.visit_expression(&node.children[1])
+                    match node.children.get(1) {
+                        Some(inner) => self.visit_expression(inner),
+                        None => AstNode::Empty,
+                    }
                 }
                 _ => AstNode::Empty,
             },
             NodeType::ProcedureOrFunctionCall => {
                 // Function call
-                self.visit_procedure_call(child)
+                let call = self.visit_procedure_call(child);
+                match child.first_token() {
+                    Some(token) => self.check_has_value(call, token),
+                    None => call,
+                }
+            }
+            NodeType::ArrayAccess | NodeType::Dereference | NodeType::FieldAccess => {
+                self.visit_selector(child)
             }
+            NodeType::SetLiteral => self.visit_set_literal(child),
             _ => AstNode::Empty,
         }
     }
 
+    /// Report a procedure named where a value is needed, its type becomes unknown
+    fn check_has_value(&mut self, node: AstNode, token: &Token) -> AstNode {
+        match node {
+            AstNode::ProcCall { name, args, tab_index, .. }
+                if self.symbol_table.tab[tab_index].obj == ObjectKind::Procedure =>
+            {
+                self.errors.push(SemanticError::new(
+                    SemanticErrorKind::ProcedureHasNoValue(name.clone()),
+                    Some(token.clone()),
+                ));
+                AstNode::ProcCall { name, args, tab_index, data_type: DataType::Unknown }
+            }
+            AstNode::Var { name, tab_index, level, .. }
+                if self.symbol_table.tab[tab_index].obj == ObjectKind::Procedure =>
+            {
+                self.errors.push(SemanticError::new(
+                    SemanticErrorKind::ProcedureHasNoValue(name.clone()),
+                    Some(token.clone()),
+                ));
+                AstNode::Var { name, data_type: DataType::Unknown, tab_index, level }
+            }
+            node => node,
+        }
+    }
+
+    /// Visit identifier used as a value
+    fn visit_identifier(&mut self, token: &Token) -> AstNode {
+        let name = &token.value;
+
+        if let Some(position) = self.pending_constants.iter().position(|pending| pending == name) {
+            let kind = if position == 0 {
+                SemanticErrorKind::SelfReferentialConstant(name.clone())
+            } else {
+                SemanticErrorKind::ConstantNotDefinedYet(name.clone())
+            };
+            self.errors.push(SemanticError::new(kind, Some(token.clone())));
+            return AstNode::Literal {
+                value: LiteralValue::Integer(0),
+                data_type: DataType::Unknown,
+            };
+        }
+
+        let tab_index = self.symbol_table.lookup(name);
+        if let Some(idx) = tab_index {
+            self.reference(ReferenceKind::Use, idx, token);
+        }
+
+        match tab_index {
+            // eof/eoln are called without parentheses, as in `selama tidak eof lakukan`
+            Some(idx) if idx < FIRST_USER_INDEX
+                && self.symbol_table.tab[idx].obj == ObjectKind::Function =>
+            {
+                self.visit_call(token, idx, Vec::new())
+            }
+            Some(idx) => {
+                let entry = &self.symbol_table.tab[idx];
+                AstNode::Var {
+                    name: name.clone(),
+                    data_type: entry.data_type.clone(),
+                    tab_index: idx,
+                    level: entry.level,
+                }
+            }
+            None => {
+                self.errors.push(SemanticError::undeclared(name.clone(), Some(token.clone())));
+                AstNode::Literal {
+                    value: LiteralValue::Integer(0),
+                    data_type: DataType::Unknown,
+                }
+            }
+        }
+    }
+
+    /// Record that `token` declares or uses the symbol at `tab_index`
+    fn reference(&mut self, kind: ReferenceKind, tab_index: usize, token: &Token) {
+        self.references.push(Reference::new(kind, tab_index, token));
+    }
+
+    /// Warn when a new declaration hides a user identifier of an enclosing scope
+    fn check_shadowing(&mut self, token: &Token) {
+        if !self.warn_shadow || self.symbol_table.lookup_current_scope(&token.value).is_some() {
+            return;
+        }
+
+        if let Some(outer) = self.symbol_table.lookup(&token.value)
+            && outer >= FIRST_USER_INDEX
+        {
+            self.warnings.push(SemanticWarning::shadows(
+                token.value.clone(),
+                self.symbol_table.tab[outer].level,
+                Some(token.clone()),
+            ));
+        }
+    }
+
+    /// Warn about variables and subprograms that are declared but never referenced
+    fn check_unused(&mut self, ast: &AstNode) {
+        let mut used = self.folded_uses.clone();
+        self.collect_usages(ast, &mut used);
+
+        let mut unused: Vec<(&usize, &Token)> = self
+            .decl_tokens
+            .iter()
+            .filter(|(tab_index, _)| !used.contains(*tab_index))
+            .collect();
+        unused.sort_by_key(|(tab_index, _)| **tab_index);
+
+        for (&tab_index, token) in unused {
+            let entry = &self.symbol_table.tab[tab_index];
+            self.warnings.push(SemanticWarning::unused(
+                entry.obj.clone(),
+                entry.name.clone(),
+                Some(token.clone()),
+            ));
+        }
+    }
+
+    /// Collect tab indices referenced by calls and variable uses
+    fn collect_usages(&self, node: &AstNode, used: &mut HashSet<usize>) {
+        match node {
+            AstNode::Program { declarations, body, .. } => {
+                for decl in declarations {
+                    self.collect_usages(decl, used);
+                }
+                self.collect_usages(body, used);
+            }
+            AstNode::ConstDecl { value, .. } => self.collect_usages(value, used),
+            AstNode::ProcDecl { declarations, body, .. }
+            | AstNode::FuncDecl { declarations, body, .. } => {
+                for decl in declarations {
+                    self.collect_usages(decl, used);
+                }
+                self.collect_usages(body, used);
+            }
+            AstNode::Block { statements, .. } => {
+                for stmt in statements {
+                    self.collect_usages(stmt, used);
+                }
+            }
+            AstNode::Assign { target, value, .. } => {
+                // Assigning a function's result is not a call of that function
+                let is_result = matches!(
+                    target.as_ref(),
+                    AstNode::Var { tab_index, .. }
+                        if self.symbol_table.tab[*tab_index].obj == ObjectKind::Function
+                );
+                if !is_result {
+                    self.collect_usages(target, used);
+                }
+                self.collect_usages(value, used);
+            }
+            AstNode::If { condition, then_stmt, else_stmt } => {
+                self.collect_usages(condition, used);
+                self.collect_usages(then_stmt, used);
+                if let Some(else_part) = else_stmt {
+                    self.collect_usages(else_part, used);
+                }
+            }
+            AstNode::IfChain { branches, else_stmt } => {
+                for (condition, stmt) in branches {
+                    self.collect_usages(condition, used);
+                    self.collect_usages(stmt, used);
+                }
+                if let Some(else_part) = else_stmt {
+                    self.collect_usages(else_part, used);
+                }
+            }
+            AstNode::Case { selector, branches, else_stmt } => {
+                self.collect_usages(selector, used);
+                for (labels, stmt) in branches {
+                    for label in labels {
+                        self.collect_usages(label, used);
+                    }
+                    self.collect_usages(stmt, used);
+                }
+                if let Some(else_part) = else_stmt {
+                    self.collect_usages(else_part, used);
+                }
+            }
+            AstNode::While { condition, body } => {
+                self.collect_usages(condition, used);
+                self.collect_usages(body, used);
+            }
+            AstNode::Repeat { body, condition } => {
+                for stmt in body {
+                    self.collect_usages(stmt, used);
+                }
+                self.collect_usages(condition, used);
+            }
+            AstNode::For { start, end, body, tab_index, .. } => {
+                used.insert(*tab_index);
+                self.collect_usages(start, used);
+                self.collect_usages(end, used);
+                self.collect_usages(body, used);
+            }
+            AstNode::ProcCall { args, tab_index, .. } => {
+                used.insert(*tab_index);
+                for arg in args {
+                    self.collect_usages(arg, used);
+                }
+            }
+            AstNode::WriteArg { .. } => {
+                for part in node.children() {
+                    self.collect_usages(part, used);
+                }
+            }
+            AstNode::BinOp { left, right, .. } => {
+                self.collect_usages(left, used);
+                self.collect_usages(right, used);
+            }
+            AstNode::UnaryOp { operand, .. } => self.collect_usages(operand, used),
+            AstNode::Var { tab_index, .. } => {
+                used.insert(*tab_index);
+            }
+            AstNode::ArrayAccess { array, index, .. } => {
+                self.collect_usages(array, used);
+                self.collect_usages(index, used);
+            }
+            AstNode::Deref { pointer, .. } => self.collect_usages(pointer, used),
+            AstNode::FieldAccess { record, .. } => self.collect_usages(record, used),
+            AstNode::SetLiteral { elements, .. } => {
+                for (value, upper) in elements {
+                    self.collect_usages(value, used);
+                    if let Some(upper) = upper {
+                        self.collect_usages(upper, used);
+                    }
+                }
+            }
+            AstNode::Labeled { statement, .. } => self.collect_usages(statement, used),
+            AstNode::LabelDecl { .. }
+            | AstNode::Goto { .. }
+            | AstNode::VarDecl { .. }
+            | AstNode::TypeDecl { .. }
+            | AstNode::ParamDecl { .. }
+            | AstNode::Literal { .. }
+            | AstNode::Break
+            | AstNode::Continue
+            | AstNode::Empty => {}
+        }
+    }
+
+    /// Visit set literal, all elements share one ordinal base type
+    fn visit_set_literal(&mut self, node: &ParseNode) -> AstNode {
+        // [ (element (, element)*)? ] where element is expression or range
+        let mut elements = Vec::new();
+        let mut base = DataType::Unknown;
+
+        for child in &node.children {
+            let (value, upper) = match child.node_type {
+                NodeType::Expression => (self.visit_expression(child), None),
+                NodeType::Range => (
+                    self.visit_expression(&child.children[0]),
+                    Some(self.visit_expression(&child.children[2])),
+                ),
+                _ => continue,
+            };
+
+            for element in std::iter::once(&value).chain(upper.as_ref()) {
+                let element_type = self.get_expr_type(element);
+                let expected = if element_type == DataType::Unknown {
+                    continue;
+                } else if !element_type.is_ordinal() {
+                    "ordinal".to_string()
+                } else if base == DataType::Unknown {
+                    base = element_type;
+                    continue;
+                } else if !self.types_equal(&element_type, &base) {
+                    base.to_string()
+                } else {
+                    continue;
+                };
+
+                self.errors.push(SemanticError::type_mismatch(
+                    expected,
+                    element_type.to_string(),
+                    child.first_token().cloned(),
+                ));
+            }
+
+            elements.push((value, upper));
+        }
+
+        AstNode::SetLiteral {
+            elements,
+            data_type: DataType::Set(Box::new(base)),
+        }
+    }
+
+    /// Visit array access
+    fn visit_array_access(&mut self, node: &ParseNode) -> AstNode {
+        // (IDENTIFIER | array-access) LBRACKET expression (COMMA expression)* RBRACKET
+        let mut result = self.visit_selector(&node.children[0]);
+
+        // Each index selects one dimension: a[i, j] is a[i][j]
+        for child in &node.children[1..] {
+            if let NodeType::Expression = child.node_type {
+                let index = self.visit_expression(child);
+                result = self.index_array(result, index, child.first_token().cloned());
+            }
+        }
+
+        result
+    }
+
+    /// Visit a variable reference with any indexes and dereferences applied
+    fn visit_selector(&mut self, node: &ParseNode) -> AstNode {
+        match &node.node_type {
+            NodeType::Terminal(token) => self.visit_identifier(token),
+            NodeType::ArrayAccess => self.visit_array_access(node),
+            NodeType::Dereference => self.visit_dereference(node),
+            NodeType::FieldAccess => self.visit_field_access(node),
+            _ => AstNode::Empty,
+        }
+    }
+
+    /// Visit r.f, which has the type of field f in r's record block
+    fn visit_field_access(&mut self, node: &ParseNode) -> AstNode {
+        // (IDENTIFIER | array-access | dereference | field-access) DOT IDENTIFIER
+        let record = self.visit_selector(&node.children[0]);
+        let Some(NodeType::Terminal(field_token)) = node.children.get(2).map(|c| &c.node_type)
+        else {
+            return AstNode::Empty;
+        };
+        let field = field_token.value.clone();
+
+        let (tab_index, data_type) = match self.get_expr_type(&record) {
+            DataType::Record(block_index) => {
+                match self.symbol_table.lookup_in_block(block_index, &field) {
+                    Some(idx) => {
+                        self.reference(ReferenceKind::Use, idx, field_token);
+                        (idx, self.symbol_table.tab[idx].data_type.clone())
+                    }
+                    None => {
+                        self.errors.push(SemanticError::new(
+                            SemanticErrorKind::UnknownField(field.clone()),
+                            Some(field_token.clone()),
+                        ));
+                        (0, DataType::Unknown)
+                    }
+                }
+            }
+            DataType::Unknown => (0, DataType::Unknown),
+            other => {
+                self.errors.push(SemanticError::new(
+                    SemanticErrorKind::NotARecord(format!("{}", other)),
+                    Some(field_token.clone()),
+                ));
+                (0, DataType::Unknown)
+            }
+        };
+
+        AstNode::FieldAccess {
+            record: Box::new(record),
+            field,
+            tab_index,
+            data_type,
+        }
+    }
+
+    /// Visit p^, which has the type p points to
+    fn visit_dereference(&mut self, node: &ParseNode) -> AstNode {
+        // (IDENTIFIER | array-access | dereference) CARET
+        let pointer = self.visit_selector(&node.children[0]);
+        let token = node.children.get(1).and_then(|caret| caret.first_token()).cloned();
+
+        let data_type = match self.get_expr_type(&pointer) {
+            // The target may have been a forward reference when the pointer type was declared
+            DataType::Pointer(target) => self
+                .resolve_type(&target, &mut Vec::new())
+                .unwrap_or(DataType::Unknown),
+            DataType::Unknown => DataType::Unknown,
+            other => {
+                self.errors.push(SemanticError::new(
+                    SemanticErrorKind::NotAPointer(format!("{}", other)),
+                    token,
+                ));
+                DataType::Unknown
+            }
+        };
+
+        AstNode::Deref {
+            pointer: Box::new(pointer),
+            data_type,
+        }
+    }
+
+    /// Type-check one index into an array value
+    fn index_array(&mut self, array: AstNode, index: AstNode, token: Option<Token>) -> AstNode {
+        let array_type = self.get_expr_type(&array);
+        let index_type = self.get_expr_type(&index);
+
+        let data_type = match array_type {
+            DataType::Array(atab_index) => {
+                let entry = self.symbol_table.atab[atab_index].clone();
+
+                if index_type != DataType::Unknown
+                    && entry.index_type != DataType::Unknown
+                    && !self.types_equal(&index_type, &entry.index_type)
+                {
+                    self.errors.push(SemanticError::type_mismatch(
+                        format!("{}", entry.index_type),
+                        format!("{}", index_type),
+                        token,
+                    ));
+                } else if let Ok(value) = self.evaluator().evaluate_ordinal(&index)
+                    && (value < i64::from(entry.low_bound) || value > i64::from(entry.high_bound))
+                {
+                    // Only statically known indices are checked, others are left to runtime
+                    self.errors.push(SemanticError::new(
+                        SemanticErrorKind::IndexOutOfBounds {
+                            index: value,
+                            low: entry.low_bound,
+                            high: entry.high_bound,
+                        },
+                        token,
+                    ));
+                }
+
+                entry.element_type
+            }
+            DataType::Unknown => DataType::Unknown,
+            other => {
+                self.errors.push(SemanticError::new(
+                    SemanticErrorKind::NotAnArray(format!("{}", other)),
+                    token,
+                ));
+                DataType::Unknown
+            }
+        };
+
+        AstNode::ArrayAccess {
+            array: Box::new(array),
+            index: Box::new(index),
+            data_type,
+        }
+    }
+
     /// Get type from parse tree type node
     fn get_type(&mut self, node: &ParseNode) -> DataType {
         if node.children.is_empty() {
@@ -1066,126 +2451,493 @@ impl SemanticAnalyzer {
         let child = &node.children[0];
 
         match &child.node_type {
-            NodeType::Terminal(token) => match token.value.as_str() {
-                "integer" => DataType::Integer,
-                "real" => DataType::Real,
-                "boolean" => DataType::Boolean,
-                "char" => DataType::Char,
+            NodeType::Terminal(token) if token.token_type == TokenType::Caret => {
+                // ^T, a named target may be declared later so it is resolved on use
+                let target = match node.children.get(1).and_then(|t| t.children.first()) {
+                    Some(ParseNode { node_type: NodeType::Terminal(name), .. })
+                        if name.token_type == TokenType::Identifier =>
+                    {
+                        DataType::UserDefined(name.value.clone())
+                    }
+                    _ => self.get_type(&node.children[1]),
+                };
+                DataType::Pointer(Box::new(target))
+            }
+            NodeType::Terminal(token) => match token.keyword {
+                Some(Keyword::Integer) => DataType::Integer,
+                Some(Keyword::Real) => DataType::Real,
+                Some(Keyword::Boolean) => DataType::Boolean,
+                Some(Keyword::Char) => DataType::Char,
                 _ => {
                     // User-defined type or identifier
-                    if let Some(idx) = self.symbol_table.lookup(&token.value) {
+                    let type_index = self
+                        .symbol_table
+                        .lookup(&token.value)
+                        .filter(|idx| self.symbol_table.tab[*idx].obj == ObjectKind::Type);
+                    if let Some(idx) = type_index {
+                        self.reference(ReferenceKind::Use, idx, token);
                         self.symbol_table.tab[idx].data_type.clone()
-                    } else {
+                    } else if let Some(pending) = &mut self.pending_types {
+                        pending.push(token.clone());
                         DataType::UserDefined(token.value.clone())
+                    } else {
+                        self.unknown_type(token);
+                        DataType::Unknown
                     }
                 }
             },
             NodeType::ArrayType => {
-                // larik[range] dari type
-                let range_node = &child.children[2];
-                let (low, high) = self.get_range(range_node);
-
-                let elem_type = self.get_type(&child.children[5]);
-
-                let elem_size = 1; // Simplified
-                let total_size = ((high - low + 1) as usize) * elem_size;
-
-                let atab_index = self.symbol_table.insert_array(ATabEntry {
-                    index_type: DataType::Integer,
-                    element_type: elem_type.clone(),
-                    element_ref: None,
-                    low_bound: low,
-                    high_bound: high,
-                    element_size: elem_size,
-                    total_size,
-                });
+                // larik[range (, range)*] dari type
+                let ranges: Vec<(DataType, i32, i32)> = child
+                    .children
+                    .iter()
+                    .filter(|c| matches!(c.node_type, NodeType::Range))
+                    .map(|range_node| self.get_range(range_node))
+                    .collect();
+
+                let mut elem_type = self.get_type(&child.children[child.children.len() - 1]);
+
+                // larik[a..b, c..d] dari T is larik[a..b] dari larik[c..d] dari T
+                for (index_type, low, high) in ranges.into_iter().rev() {
+                    let elem_size = self.type_size(&elem_type, &mut Vec::new()).unwrap_or(0);
+                    let count = (i64::from(high) - i64::from(low) + 1).max(0) as usize;
+                    let total_size = match count
+                        .checked_mul(elem_size)
+                        .filter(|size| *size <= MAX_ARRAY_SIZE)
+                    {
+                        Some(size) => size,
+                        None => {
+                            self.errors.push(SemanticError::new(
+                                SemanticErrorKind::ArrayTooLarge(MAX_ARRAY_SIZE),
+                                child.first_token().cloned(),
+                            ));
+                            0
+                        }
+                    };
+
+                    let atab_index = self.symbol_table.insert_array(ATabEntry {
+                        index_type,
+                        element_type: elem_type.clone(),
+                        element_ref: match elem_type {
+                            DataType::Array(index) | DataType::Record(index) => Some(index),
+                            _ => None,
+                        },
+                        low_bound: low,
+                        high_bound: high,
+                        element_size: elem_size,
+                        total_size,
+                    });
+
+                    elem_type = DataType::Array(atab_index);
+                }
 
-                DataType::Array(atab_index)
+                elem_type
             }
+            NodeType::RecordType => self.get_record_type(child),
+            NodeType::SetType => self.get_set_type(child),
             _ => DataType::Unknown,
         }
     }
 
+    /// Report an undeclared type name, suggesting the built-in type it resembles
+    fn unknown_type(&mut self, token: &Token) {
+        let name = token.value.to_lowercase();
+        let suggestion = BUILTIN_TYPES
+            .into_iter()
+            .map(|builtin| self.keywords.spelling(builtin))
+            .map(|builtin| (edit_distance(&name, builtin), builtin))
+            .filter(|(distance, _)| *distance <= 2 && *distance < name.chars().count())
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, builtin)| builtin.to_string());
+
+        self.errors.push(SemanticError::new(
+            SemanticErrorKind::UnknownType { name: token.value.clone(), suggestion },
+            Some(token.clone()),
+        ));
+    }
+
+    /// Build a record type, its fields live in their own btab block
+    fn get_record_type(&mut self, node: &ParseNode) -> DataType {
+        // rekaman (identifier-list COLON type SEMICOLON?)* selesai
+        let block_index = self.symbol_table.enter_block();
+        let level = self.symbol_table.current_level();
+        let mut offset = 0;
+        let mut i = 1; // Skip "rekaman" keyword
+
+        while i < node.children.len() {
+            if !matches!(node.children[i].node_type, NodeType::IdentifierList) {
+                i += 1; // Skip semicolons and "selesai"
+                continue;
+            }
+
+            let id_tokens = self.get_identifier_tokens(&node.children[i]);
+            let field_type = self.get_type(&node.children[i + 2]);
+            let field_size = self.type_size(&field_type, &mut Vec::new()).unwrap_or(0);
+            i += 3;
+
+            for token in &id_tokens {
+                let name = &token.value;
+
+                if self.symbol_table.lookup_current_scope(name).is_some() {
+                    self.errors.push(SemanticError::redeclared(name.clone(), Some(token.clone())));
+                    continue;
+                }
+
+                let tab_index = self.symbol_table.insert(TabEntry {
+                    name: name.clone(),
+                    link: None,
+                    obj: ObjectKind::Variable,
+                    data_type: field_type.clone(),
+                    ref_index: None,
+                    normal: true,
+                    level,
+                    address: offset,
+                });
+                self.reference(ReferenceKind::Decl, tab_index, token);
+                offset += field_size;
+            }
+        }
+
+        self.symbol_table.btab[block_index].var_size = offset;
+        self.symbol_table.exit_block();
+
+        DataType::Record(block_index)
+    }
+
+    /// Build himpunan dari T, T must be ordinal
+    fn get_set_type(&mut self, node: &ParseNode) -> DataType {
+        // himpunan dari (type | range), the bounds of a range base are not kept
+        let base_node = &node.children[2];
+        let base = match base_node.node_type {
+            NodeType::Range => self.get_range(base_node).0,
+            _ => self.get_type(base_node),
+        };
+
+        if base != DataType::Unknown && !base.is_ordinal() {
+            self.errors.push(SemanticError::type_mismatch(
+                "ordinal".to_string(),
+                base.to_string(),
+                base_node.first_token().cloned(),
+            ));
+            return DataType::Set(Box::new(DataType::Unknown));
+        }
+
+        DataType::Set(Box::new(base))
+    }
+
+    /// Follow user-defined type names to the type they stand for.
+    /// Every name visited is pushed onto `path`; fails with the name that closes a cycle.
+    fn resolve_type(&self, data_type: &DataType, path: &mut Vec<String>) -> Result<DataType, String> {
+        let mut current = data_type.clone();
+
+        while let DataType::UserDefined(name) = &current {
+            if path.contains(name) {
+                return Err(name.clone());
+            }
+            path.push(name.clone());
+
+            match self.symbol_table.lookup(name) {
+                Some(idx) if self.symbol_table.tab[idx].obj == ObjectKind::Type => {
+                    current = self.symbol_table.tab[idx].data_type.clone();
+                }
+                _ => break, // Undeclared, reported elsewhere
+            }
+        }
+
+        Ok(current)
+    }
+
+    /// Storage size of a type, looking through arrays and records by value.
+    /// `path` holds the type names currently being expanded, so a type that
+    /// contains itself yields `Err` instead of recursing forever.
+    fn type_size(&self, data_type: &DataType, path: &mut Vec<String>) -> Result<usize, String> {
+        let depth = path.len();
+
+        let size = match self.resolve_type(data_type, path)? {
+            DataType::Array(idx) => {
+                let entry = &self.symbol_table.atab[idx];
+                let count =
+                    (i64::from(entry.high_bound) - i64::from(entry.low_bound) + 1).max(0) as usize;
+                count.saturating_mul(self.type_size(&entry.element_type, path)?)
+            }
+            DataType::Record(block_index) => {
+                let mut size: usize = 0;
+                for field in self.symbol_table.block_entries(block_index) {
+                    let field_size = self.type_size(&self.symbol_table.tab[field].data_type, path)?;
+                    size = size.saturating_add(field_size);
+                }
+                size
+            }
+            DataType::UserDefined(_) | DataType::Void | DataType::Unknown => 0,
+            _ => 1,
+        };
+
+        path.truncate(depth);
+        Ok(size)
+    }
+
     /// Get range bounds
-    fn get_range(&mut self, node: &ParseNode) -> (i32, i32) {
-        // expression .. expression
+    fn get_range(&mut self, node: &ParseNode) -> (DataType, i32, i32) {
+        // expression .. expression, bounds are ordinal values ('a'..'z' is 97..122)
         let low_expr = self.visit_expression(&node.children[0]);
         let high_expr = self.visit_expression(&node.children[2]);
+        let index_type = self.get_expr_type(&low_expr);
+        let high_type = self.get_expr_type(&high_expr);
 
-        let low = self.get_literal_int(&low_expr).unwrap_or(0);
-        let high = self.get_literal_int(&high_expr).unwrap_or(0);
+        if index_type == DataType::Unknown || high_type == DataType::Unknown {
+            return (DataType::Unknown, 0, 0);
+        } else if !index_type.is_ordinal() {
+            self.errors.push(SemanticError::type_mismatch(
+                "ordinal".to_string(),
+                format!("{}", index_type),
+                node.first_token().cloned(),
+            ));
+            return (DataType::Unknown, 0, 0);
+        } else if !self.types_equal(&high_type, &index_type) {
+            self.errors.push(SemanticError::type_mismatch(
+                format!("{}", index_type),
+                format!("{}", high_type),
+                node.first_token().cloned(),
+            ));
+            return (DataType::Unknown, 0, 0);
+        }
+
+        let evaluator = self.evaluator();
+        let (low, high) = match (evaluator.evaluate_ordinal(&low_expr), evaluator.evaluate_ordinal(&high_expr)) {
+            (Ok(low), Ok(high)) => (low, high),
+            (Err(reason), _) => {
+                let token = node.children[0].first_token().cloned();
+                self.report_not_constant("Range bound".to_string(), reason, token);
+                return (DataType::Unknown, 0, 0);
+            }
+            (_, Err(reason)) => {
+                let token = node.children[2].first_token().cloned();
+                self.report_not_constant("Range bound".to_string(), reason, token);
+                return (DataType::Unknown, 0, 0);
+            }
+        };
+
+        // Bounds are stored as i32 in atab
+        let (Ok(low), Ok(high)) = (i32::try_from(low), i32::try_from(high)) else {
+            self.errors.push(SemanticError::new(
+                SemanticErrorKind::ArrayTooLarge(MAX_ARRAY_SIZE),
+                node.first_token().cloned(),
+            ));
+            return (DataType::Unknown, 0, 0);
+        };
 
         if low > high {
             self.errors.push(SemanticError::new(
                 SemanticErrorKind::InvalidArrayBounds,
-                None,
+                node.first_token().cloned(),
             ));
         }
 
-        (low, high)
+        (index_type, low, high)
     }
 
-    /// Get integer value from literal node or unary expression
-    fn get_literal_int(&self, node: &AstNode) -> Option<i32> {
-        match node {
-            AstNode::Literal { value, .. } => {
-                if let LiteralValue::Integer(v) = value {
-                    return Some(*v as i32);
-                }
-                None
-            }
-            AstNode::UnaryOp { op, operand, .. } => {
-                // Handle unary + and -
-                if let Some(val) = self.get_literal_int(operand) {
-                    match op.as_str() {
-                        "-" => Some(-val),
-                        "+" => Some(val),
-                        _ => None,
-                    }
-                } else {
-                    None
-                }
-            }
-            AstNode::Var { tab_index, .. } => {
-                // Handle constant references
-                let entry = &self.symbol_table.tab[*tab_index];
-                if entry.obj == ObjectKind::Constant {
-                    // TODO: Store constant values in symbol table
-                    None
-                } else {
-                    None
-                }
-            }
-            _ => None,
+    /// Compile-time evaluator over the constants declared so far
+    fn evaluator(&self) -> ConstEvaluator<'_> {
+        ConstEvaluator::new(&self.symbol_table, &self.const_values, self.int_width, self.short_circuit)
+    }
+
+    /// Report an expression that must be constant, errors inside it are already reported
+    fn report_not_constant(&mut self, context: String, reason: NotConstant, token: Option<Token>) {
+        if reason != NotConstant::Erroneous {
+            self.errors.push(SemanticError::not_constant(context, reason, token));
         }
     }
 
-    /// Get identifier list from parse tree
-    fn get_identifier_list(&self, node: &ParseNode) -> Vec<String> {
+    /// Value of an integer literal, decimal or prefixed with &O (octal) or &B (binary)
+    fn integer_value(text: &str) -> Option<i64> {
+        let (digits, radix) = match text.get(..2) {
+            Some("&O" | "&o") => (&text[2..], 8),
+            Some("&B" | "&b") => (&text[2..], 2),
+            _ => (text, 10),
+        };
+        i64::from_str_radix(digits, radix).ok()
+    }
+
+    /// Text of a char or string literal token without its quotes, '' stands for one quote
+    fn literal_text(token: &Token) -> String {
+        let value = &token.value;
+        value[1..value.len() - 1].replace("''", "'")
+    }
+
+    /// Get identifier tokens from an identifier list
+    fn get_identifier_tokens(&self, node: &ParseNode) -> Vec<Token> {
         let mut ids = Vec::new();
 
         for child in &node.children {
-            if let NodeType::Terminal(token) = &child.node_type {
-                if token.token_type == TokenType::Identifier {
-                    ids.push(token.value.clone());
-                }
+            if let NodeType::Terminal(token) = &child.node_type
+                && token.token_type == TokenType::Identifier
+            {
+                ids.push(token.clone());
             }
         }
 
         ids
     }
 
+    /// Whether a type is an array of char, which doubles as a string
+    fn is_char_array(&self, data_type: &DataType) -> bool {
+        matches!(
+            data_type,
+            DataType::Array(atab_index)
+                if self.symbol_table.atab[*atab_index].element_type == DataType::Char
+        )
+    }
+
+    /// Relational operator with an array or record operand. Char arrays compare like
+    /// strings, other composites only for (in)equality with a value of the same structure
+    fn compare_composites(&self, op: Operator, left: &DataType, right: &DataType) -> Result<DataType, SemanticErrorKind> {
+        let is_text = |t: &DataType| *t == DataType::String || self.is_char_array(t);
+        if is_text(left) && is_text(right) {
+            return Ok(DataType::Boolean);
+        }
+
+        if op.is_ordering() {
+            let composite = match left {
+                DataType::Array(_) | DataType::Record(_) => left,
+                _ => right,
+            };
+            return Err(SemanticErrorKind::CannotOrder(self.symbol_table.describe_type(composite)));
+        }
+
+        if self.types_equal(left, right) {
+            Ok(DataType::Boolean)
+        } else {
+            Err(SemanticErrorKind::InvalidOperation {
+                op: op.spelling(&self.keywords).to_string(),
+                types: format!(
+                    "{} and {}",
+                    self.symbol_table.describe_type(left),
+                    self.symbol_table.describe_type(right)
+                ),
+            })
+        }
+    }
+
+    /// Structural type equality: arrays by index type, bounds and element type,
+    /// records by field names and types in order, pointers and sets by their target
+    /// or base, scalars by variant. Type names are looked through, so two separately
+    /// declared types of the same shape are equal
+    pub fn types_equal(&self, a: &DataType, b: &DataType) -> bool {
+        self.types_equal_assuming(a, b, &mut Vec::new())
+    }
+
+    /// `types_equal`, with the record pairs being compared further up assumed equal,
+    /// so records that reach themselves through a pointer do not recurse forever
+    fn types_equal_assuming(&self, a: &DataType, b: &DataType, assumed: &mut Vec<(usize, usize)>) -> bool {
+        if a == b {
+            return true;
+        }
+        let (Ok(a), Ok(b)) = (self.resolve_type(a, &mut Vec::new()), self.resolve_type(b, &mut Vec::new())) else {
+            return false;
+        };
+
+        match (&a, &b) {
+            (DataType::Array(a), DataType::Array(b)) => {
+                let (a, b) = (&self.symbol_table.atab[*a], &self.symbol_table.atab[*b]);
+                self.types_equal_assuming(&a.index_type, &b.index_type, assumed)
+                    && (a.low_bound, a.high_bound) == (b.low_bound, b.high_bound)
+                    && self.types_equal_assuming(&a.element_type, &b.element_type, assumed)
+            }
+            (DataType::Record(a), DataType::Record(b)) => {
+                if a == b || assumed.contains(&(*a, *b)) {
+                    return true;
+                }
+                assumed.push((*a, *b));
+                let a_fields = self.symbol_table.block_entries(*a);
+                let b_fields = self.symbol_table.block_entries(*b);
+                let equal = a_fields.len() == b_fields.len()
+                    && a_fields.iter().zip(&b_fields).all(|(&a, &b)| {
+                        let (a, b) = (&self.symbol_table.tab[a], &self.symbol_table.tab[b]);
+                        a.name == b.name && self.types_equal_assuming(&a.data_type, &b.data_type, assumed)
+                    });
+                assumed.pop();
+                equal
+            }
+            (DataType::Pointer(a), DataType::Pointer(b)) | (DataType::Set(a), DataType::Set(b)) => {
+                self.types_equal_assuming(a, b, assumed)
+            }
+            _ => a == b,
+        }
+    }
+
+    /// Assignment compatibility, treating char arrays and strings as interchangeable
+    /// and composites of the same structure as the same type
+    fn can_assign(&self, to: &DataType, from: &DataType) -> bool {
+        let is_text = |data_type: &DataType| {
+            *data_type == DataType::String || self.is_char_array(data_type)
+        };
+        DataType::can_assign(to, from) || self.types_equal(to, from) || (is_text(to) && is_text(from))
+    }
+
+    /// Whether a value of this type can be passed to write/writeln
+    fn is_printable(&self, data_type: &DataType) -> bool {
+        match data_type {
+            DataType::Integer
+            | DataType::Real
+            | DataType::Boolean
+            | DataType::Char
+            | DataType::String
+            | DataType::Unknown => true,
+            _ => self.is_char_array(data_type),
+        }
+    }
+
     /// Get type of an expression AST node
-    fn get_expr_type(&self, node: &AstNode) -> DataType {
+    pub fn get_expr_type(&self, node: &AstNode) -> DataType {
         match node {
             AstNode::Literal { data_type, .. } => data_type.clone(),
             AstNode::Var { data_type, .. } => data_type.clone(),
             AstNode::BinOp { data_type, .. } => data_type.clone(),
             AstNode::UnaryOp { data_type, .. } => data_type.clone(),
-            AstNode::ProcCall { tab_index, .. } => {
-                self.symbol_table.tab[*tab_index].data_type.clone()
-            }
+            AstNode::ArrayAccess { data_type, .. } => data_type.clone(),
+            AstNode::Deref { data_type, .. } => data_type.clone(),
+            AstNode::FieldAccess { data_type, .. } => data_type.clone(),
+            AstNode::SetLiteral { data_type, .. } => data_type.clone(),
+            AstNode::ProcCall { data_type, .. } => data_type.clone(),
+            AstNode::WriteArg { expr, .. } => self.get_expr_type(expr),
             _ => DataType::Unknown,
         }
     }
 }
+
+/// Whether control never continues to the statement after this one
+fn always_transfers(stmt: &AstNode) -> bool {
+    match stmt {
+        AstNode::Break | AstNode::Continue | AstNode::Goto { .. } => true,
+        AstNode::ProcCall { name, tab_index, .. } => {
+            *tab_index < FIRST_USER_INDEX && builtin::find(name).is_some_and(Builtin::ends_program)
+        }
+        AstNode::Labeled { statement, .. } => always_transfers(statement),
+        // A block jumps away when its last run of statements after a label does
+        AstNode::Block { statements, .. } => statements
+            .iter()
+            .rev()
+            .take_while(|stmt| !matches!(stmt, AstNode::Labeled { .. }))
+            .chain(statements.iter().rev().find(|stmt| matches!(stmt, AstNode::Labeled { .. })))
+            .any(always_transfers),
+        _ => false,
+    }
+}
+
+/// Levenshtein distance: single-character insertions, deletions and substitutions
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
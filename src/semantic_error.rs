@@ -1,4 +1,6 @@
+use crate::const_eval::NotConstant;
 use crate::token::Token;
+use crate::types::ObjectKind;
 use std::fmt;
 
 /// Semantic error types
@@ -8,22 +10,46 @@ pub enum SemanticErrorKind {
     RedeclaredIdentifier(String),
     TypeMismatch { expected: String, found: String },
     InvalidOperation { op: String, types: String },
-    #[allow(dead_code)]
-    WrongParameterCount { expected: usize, found: usize },
-    #[allow(dead_code)]
+    MissingOperand(String),
+    CannotOrder(String),
+    WrongParameterCount { min: usize, max: Option<usize>, found: usize }, // No max when variadic
     NotCallable(String),
-    #[allow(dead_code)]
     NotAssignable(String),
     InvalidArrayBounds,
+    ArrayTooLarge(usize),
+    NotAnArray(String),
+    NotAPointer(String),
+    NotARecord(String),
+    UnknownField(String),
+    IndexOutOfBounds { index: i64, low: i32, high: i32 },
     InvalidLoopVariable,
     ConditionNotBoolean,
+    RecursiveType(String),
+    NotPrintable(String),
+    FormatOutsideWrite(String),
+    NotOrdinal(String),
+    NotConstant { context: String, reason: NotConstant },
+    SelfReferentialConstant(String),
+    ConstantNotDefinedYet(String),
+    DuplicateCaseLabel(String),
+    RealToInteger,
+    LoopControlOutsideLoop(String),
+    InvalidLabel(String),
+    UndeclaredLabel(String),
+    LabelRedeclared(String),
+    LabelRedefined(String),
+    LabelNotDefined { label: String, goto: String }, // goto is the spelling of pergi_ke
+    ProcedureHasNoValue(String),
+    UnknownType { name: String, suggestion: Option<String> },
+    NotSizeable(String),
+    IntegerOutOfRange { literal: String, bits: u32, max: i64 },
+    CompositeScalarAssignment { target: String, value: String, composite_value: bool },
 }
 
 /// Semantic error with location information
 #[derive(Debug, Clone)]
 pub struct SemanticError {
-    #[allow(dead_code)]
-    pub kind: SemanticErrorKind,
+    kind: SemanticErrorKind,
     pub message: String,
     pub token: Option<Token>,
 }
@@ -43,7 +69,19 @@ impl SemanticError {
             SemanticErrorKind::InvalidOperation { op, types } => {
                 format!("Invalid operation '{}' for types {}", op, types)
             }
-            SemanticErrorKind::WrongParameterCount { expected, found } => {
+            SemanticErrorKind::MissingOperand(op) => {
+                format!("Operator '{}' is missing its operand", op)
+            }
+            SemanticErrorKind::CannotOrder(found) => {
+                format!("Cannot order values of type {}", found)
+            }
+            SemanticErrorKind::WrongParameterCount { min, max, found } => {
+                let expected = match max {
+                    Some(max) if max == min => min.to_string(),
+                    Some(max) if *max == min + 1 => format!("{} or {}", min, max),
+                    Some(max) => format!("{} to {}", min, max),
+                    None => format!("at least {}", min),
+                };
                 format!(
                     "Wrong number of parameters: expected {}, found {}",
                     expected, found
@@ -59,12 +97,96 @@ impl SemanticError {
                 "Invalid array bounds: lower bound must be less than or equal to upper bound"
                     .to_string()
             }
+            SemanticErrorKind::ArrayTooLarge(limit) => {
+                format!("Array too large: size exceeds the limit of {} storage units", limit)
+            }
+            SemanticErrorKind::NotAPointer(found) => {
+                format!("Cannot dereference a value of type {}", found)
+            }
+            SemanticErrorKind::NotARecord(found) => {
+                format!("Cannot select a field of a value of type {}", found)
+            }
+            SemanticErrorKind::UnknownField(field) => {
+                format!("Record has no field '{}'", field)
+            }
+            SemanticErrorKind::NotAnArray(found) => {
+                format!("Cannot index a value of type {}", found)
+            }
+            SemanticErrorKind::IndexOutOfBounds { index, low, high } => {
+                format!("Array index {} is out of bounds [{}..{}]", index, low, high)
+            }
             SemanticErrorKind::InvalidLoopVariable => {
                 "Loop variable must be of integer type".to_string()
             }
             SemanticErrorKind::ConditionNotBoolean => {
                 "Condition must be of boolean type".to_string()
             }
+            SemanticErrorKind::RecursiveType(name) => {
+                format!("Recursive type definition '{}'", name)
+            }
+            SemanticErrorKind::NotPrintable(found) => {
+                format!("Cannot write a value of type {}", found)
+            }
+            SemanticErrorKind::FormatOutsideWrite(name) => {
+                format!("Field width is only allowed in write and writeln, not in a call to '{}'", name)
+            }
+            SemanticErrorKind::NotOrdinal(found) => {
+                format!("Case selector must be of ordinal type, found {}", found)
+            }
+            SemanticErrorKind::NotConstant { context, reason } => {
+                format!("{} must be a constant: {}", context, reason)
+            }
+            SemanticErrorKind::SelfReferentialConstant(name) => {
+                format!("Constant '{}' cannot be defined in terms of itself", name)
+            }
+            SemanticErrorKind::ConstantNotDefinedYet(name) => {
+                format!("Constant '{}' is not defined yet, it is declared further down", name)
+            }
+            SemanticErrorKind::DuplicateCaseLabel(label) => {
+                format!("Duplicate case label {}", label)
+            }
+            SemanticErrorKind::RealToInteger => {
+                "Cannot assign a real value to an integer, use round or trunc".to_string()
+            }
+            SemanticErrorKind::LoopControlOutsideLoop(keyword) => {
+                format!("'{}' can only be used inside a loop", keyword)
+            }
+            SemanticErrorKind::InvalidLabel(text) => {
+                format!("Invalid label {}: labels are integers from 0 to 9999", text)
+            }
+            SemanticErrorKind::UndeclaredLabel(label) => {
+                format!("Undeclared label {}", label)
+            }
+            SemanticErrorKind::LabelRedeclared(label) => {
+                format!("Label {} is already declared in this scope", label)
+            }
+            SemanticErrorKind::LabelRedefined(label) => {
+                format!("Label {} is defined more than once", label)
+            }
+            SemanticErrorKind::LabelNotDefined { label, goto } => {
+                format!("Label {} is the target of '{}' but never defined", label, goto)
+            }
+            SemanticErrorKind::ProcedureHasNoValue(name) => {
+                format!("Procedure '{}' has no return value and cannot be used in an expression", name)
+            }
+            SemanticErrorKind::UnknownType { name, suggestion: Some(suggestion) } => {
+                format!("Unknown type '{}'; did you mean '{}'?", name, suggestion)
+            }
+            SemanticErrorKind::UnknownType { name, suggestion: None } => {
+                format!("Unknown type '{}'", name)
+            }
+            SemanticErrorKind::NotSizeable(found) => {
+                format!("'ukuran' expects a type or a variable, found {}", found)
+            }
+            SemanticErrorKind::CompositeScalarAssignment { target, value, composite_value: true } => {
+                format!("Cannot assign composite type {} to scalar type {}", value, target)
+            }
+            SemanticErrorKind::CompositeScalarAssignment { target, value, composite_value: false } => {
+                format!("Cannot assign scalar type {} to composite type {}", value, target)
+            }
+            SemanticErrorKind::IntegerOutOfRange { literal, bits, max } => {
+                format!("Integer literal {} is out of range for {}-bit integers (at most {})", literal, bits, max)
+            }
         };
 
         SemanticError {
@@ -74,6 +196,11 @@ impl SemanticError {
         }
     }
 
+    /// Category of the error, for callers that act on it rather than show the message
+    pub fn kind(&self) -> &SemanticErrorKind {
+        &self.kind
+    }
+
     pub fn undeclared(name: String, token: Option<Token>) -> Self {
         Self::new(SemanticErrorKind::UndeclaredIdentifier(name), token)
     }
@@ -86,6 +213,10 @@ impl SemanticError {
         Self::new(SemanticErrorKind::TypeMismatch { expected, found }, token)
     }
 
+    pub fn not_constant(context: String, reason: NotConstant, token: Option<Token>) -> Self {
+        Self::new(SemanticErrorKind::NotConstant { context, reason }, token)
+    }
+
     pub fn invalid_operation(op: String, types: String, token: Option<Token>) -> Self {
         Self::new(SemanticErrorKind::InvalidOperation { op, types }, token)
     }
@@ -102,3 +233,88 @@ impl fmt::Display for SemanticError {
 }
 
 impl std::error::Error for SemanticError {}
+
+/// Non-fatal finding reported alongside the analysis result
+#[derive(Debug, Clone)]
+pub struct SemanticWarning {
+    pub message: String,
+    pub token: Option<Token>,
+}
+
+impl SemanticWarning {
+    pub fn unused(obj: ObjectKind, name: String, token: Option<Token>) -> Self {
+        SemanticWarning {
+            message: format!("Unused {} '{}'", obj, name),
+            token,
+        }
+    }
+
+    pub fn shadows(name: String, outer_level: usize, token: Option<Token>) -> Self {
+        SemanticWarning {
+            message: format!(
+                "Declaration of '{}' shadows an outer binding at level {}",
+                name, outer_level
+            ),
+            token,
+        }
+    }
+
+    pub fn widening(name: String, token: Option<Token>) -> Self {
+        SemanticWarning {
+            message: format!("Implicit widening of integer to real in assignment to '{}'", name),
+            token,
+        }
+    }
+
+    pub fn empty_loop(keyword: &str, start: i64, end: i64, token: Option<Token>) -> Self {
+        SemanticWarning {
+            message: format!("Loop never runs: {} {} {}", start, keyword, end),
+            token,
+        }
+    }
+
+    pub fn jump_into(label: String, token: Option<Token>) -> Self {
+        SemanticWarning {
+            message: format!("Jump into a structured statement at label {}", label),
+            token,
+        }
+    }
+
+    pub fn result_ignored(name: String, token: Option<Token>) -> Self {
+        SemanticWarning {
+            message: format!("Result of function '{}' is ignored", name),
+            token,
+        }
+    }
+
+    pub fn non_exhaustive(missing: String, else_keyword: &str, token: Option<Token>) -> Self {
+        SemanticWarning {
+            message: format!("Non-exhaustive case: no label for {} and no '{}'", missing, else_keyword),
+            token,
+        }
+    }
+
+    pub fn unreachable(token: Option<Token>) -> Self {
+        SemanticWarning {
+            message: "Unreachable statement".to_string(),
+            token,
+        }
+    }
+
+    pub fn ordinal_out_of_range(name: &str, type_name: String, token: Option<Token>) -> Self {
+        SemanticWarning {
+            message: format!("{} out of range for type {}", name, type_name),
+            token,
+        }
+    }
+}
+
+impl fmt::Display for SemanticWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(token) = &self.token {
+            write!(f, "Semantic warning at {}: {}", token, self.message)
+        } else {
+            write!(f, "Semantic warning: {}", self.message)
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::ast::{AstNode, LiteralValue};
+use crate::const_eval::ConstEvaluator;
+
+/// Rewrite a decorated AST without changing what it does: operator expressions over
+/// constants become literals, and branches and loops behind a constant condition that
+/// can never run are dropped. A dropped statement must not hold a label, pergi_ke
+/// could still jump into it
+pub fn simplify(node: &mut AstNode, evaluator: &ConstEvaluator) {
+    for child in node.children_mut() {
+        simplify(child, evaluator);
+    }
+
+    let replacement = match node {
+        AstNode::BinOp { .. } | AstNode::UnaryOp { .. } => fold(node, evaluator),
+        AstNode::If { condition, then_stmt, else_stmt } => match constant_condition(condition, evaluator) {
+            Some(true) if else_stmt.as_deref().is_none_or(|stmt| !has_label(stmt)) => {
+                Some(std::mem::replace(then_stmt.as_mut(), AstNode::Empty))
+            }
+            Some(false) if !has_label(then_stmt) => {
+                Some(else_stmt.take().map_or(AstNode::Empty, |stmt| *stmt))
+            }
+            _ => None,
+        },
+        AstNode::IfChain { branches, else_stmt } => simplify_chain(branches, else_stmt, evaluator),
+        AstNode::While { condition, body } => {
+            (constant_condition(condition, evaluator) == Some(false) && !has_label(body))
+                .then_some(AstNode::Empty)
+        }
+        // Empty statements are left out of statement lists, as the analyzer does
+        AstNode::Block { statements, .. } | AstNode::Repeat { body: statements, .. } => {
+            statements.retain(|stmt| !matches!(stmt, AstNode::Empty));
+            None
+        }
+        _ => None,
+    };
+
+    if let Some(replacement) = replacement {
+        *node = replacement;
+    }
+}
+
+/// Drop the else-if branches whose condition is always false, and end the chain at the
+/// first one that always holds. Returns the statement left when no branch remains
+fn simplify_chain(
+    branches: &mut Vec<(AstNode, AstNode)>,
+    else_stmt: &mut Option<Box<AstNode>>,
+    evaluator: &ConstEvaluator,
+) -> Option<AstNode> {
+    let mut kept = Vec::new();
+    let mut remaining = std::mem::take(branches).into_iter();
+
+    while let Some((condition, stmt)) = remaining.next() {
+        match constant_condition(&condition, evaluator) {
+            Some(false) if !has_label(&stmt) => {}
+            // Every later branch and the else part are unreachable
+            Some(true)
+                if remaining.as_slice().iter().all(|(_, stmt)| !has_label(stmt))
+                    && else_stmt.as_deref().is_none_or(|stmt| !has_label(stmt)) =>
+            {
+                *else_stmt = Some(Box::new(stmt));
+                remaining = Vec::new().into_iter();
+            }
+            _ => kept.push((condition, stmt)),
+        }
+    }
+
+    *branches = kept;
+    match branches.as_slice() {
+        [] => Some(else_stmt.take().map_or(AstNode::Empty, |stmt| *stmt)),
+        _ => None,
+    }
+}
+
+/// Literal with the value and type of a constant operator expression
+fn fold(node: &AstNode, evaluator: &ConstEvaluator) -> Option<AstNode> {
+    let (AstNode::BinOp { data_type, .. } | AstNode::UnaryOp { data_type, .. }) = node else {
+        return None;
+    };
+    let value = evaluator.evaluate(node).ok()?;
+    Some(AstNode::Literal { value, data_type: data_type.clone() })
+}
+
+fn constant_condition(condition: &AstNode, evaluator: &ConstEvaluator) -> Option<bool> {
+    match evaluator.evaluate(condition) {
+        Ok(LiteralValue::Boolean(value)) => Some(value),
+        _ => None,
+    }
+}
+
+fn has_label(node: &AstNode) -> bool {
+    matches!(node, AstNode::Labeled { .. }) || node.children().into_iter().any(has_label)
+}
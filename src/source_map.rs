@@ -0,0 +1,46 @@
+use serde::Serialize;
+
+use crate::token::Token;
+
+/// Whether a reference introduces its symbol or refers back to it
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReferenceKind {
+    Decl,
+    Use,
+}
+
+/// An identifier in the source and the tab entry it resolves to
+#[derive(Debug, Clone, Serialize)]
+pub struct Reference {
+    pub kind: ReferenceKind,
+    pub name: String,
+    pub tab_index: usize,
+    pub line: usize,
+    pub col: usize,
+    pub offset: usize, // Byte offset of the identifier's first character
+}
+
+impl Reference {
+    pub fn new(kind: ReferenceKind, tab_index: usize, token: &Token) -> Self {
+        Reference {
+            kind,
+            name: token.value.clone(),
+            tab_index,
+            line: token.line,
+            col: token.column,
+            offset: token.offset,
+        }
+    }
+}
+
+/// One JSON object per reference, in source order
+pub fn to_json_lines(references: &[Reference]) -> String {
+    let mut sorted: Vec<&Reference> = references.iter().collect();
+    sorted.sort_by_key(|reference| reference.offset);
+
+    sorted
+        .iter()
+        .map(|reference| serde_json::to_string(reference).unwrap() + "\n")
+        .collect()
+}
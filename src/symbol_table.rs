@@ -1,8 +1,53 @@
+use crate::builtin::BUILTINS;
+use crate::keyword::{Keyword, KeywordSet};
 use crate::types::{DataType, ObjectKind};
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 
 // This uses Backward chaining
 
+/// Reserved words in tab order, spelled by the keyword set the table is built with
+const RESERVED_WORDS: [Keyword; 29] = [
+    Keyword::And,
+    Keyword::Array,
+    Keyword::Begin,
+    Keyword::Case,
+    Keyword::Const,
+    Keyword::Div,
+    Keyword::Downto,
+    Keyword::Do,
+    Keyword::Else,
+    Keyword::End,
+    Keyword::For,
+    Keyword::Function,
+    Keyword::If,
+    Keyword::Mod,
+    Keyword::Not,
+    Keyword::Of,
+    Keyword::Or,
+    Keyword::Procedure,
+    Keyword::Program,
+    Keyword::Record,
+    Keyword::Repeat,
+    Keyword::String,
+    Keyword::Then,
+    Keyword::To,
+    Keyword::Type,
+    Keyword::Until,
+    Keyword::Var,
+    Keyword::While,
+    Keyword::Packed,
+];
+
+/// Index of the first predefined procedure or function, after the reserved words
+pub const FIRST_BUILTIN_INDEX: usize = RESERVED_WORDS.len();
+
+/// Predefined file variables, entered after the built-ins
+pub const STANDARD_FILES: [&str; 2] = ["input", "output"];
+
+/// Index of the first user identifier, after reserved words and predefined identifiers
+pub const FIRST_USER_INDEX: usize = FIRST_BUILTIN_INDEX + BUILTINS.len() + STANDARD_FILES.len();
+
 /// Entry in the identifier table (tab)
 #[derive(Debug, Clone)]
 pub struct TabEntry {
@@ -37,426 +82,99 @@ pub struct ATabEntry {
     pub total_size: usize,         // Total size of array
 }
 
+impl ATabEntry {
+    /// Offset of element `index` from the start of the array, None when out of bounds
+    pub fn offset(&self, index: i32) -> Option<usize> {
+        (self.low_bound..=self.high_bound)
+            .contains(&index)
+            .then(|| (index - self.low_bound) as usize * self.element_size)
+    }
+}
+
 /// Symbol table with three tables: tab, btab, atab
 pub struct SymbolTable {
     pub tab: Vec<TabEntry>,
     pub btab: Vec<BTabEntry>,
     pub atab: Vec<ATabEntry>,
     pub display: Vec<usize>, // Display stack for scope management
+    reserved: HashMap<String, usize>, // Reserved words and predefined procedures by name
+    block_names: Vec<HashMap<String, usize>>, // Identifiers of each block by name, parallel to btab
+}
+
+impl Default for SymbolTable {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SymbolTable {
     /// Create a new symbol table initialized with reserved words and predefined identifiers
     pub fn new() -> Self {
+        SymbolTable::with_keywords(&KeywordSet::default())
+    }
+
+    /// Symbol table whose reserved words are spelled as in `keywords`
+    pub fn with_keywords(keywords: &KeywordSet) -> Self {
         let mut tab = Vec::new();
         
         // ============================================================
         // RESERVED WORDS (indices 0-28) - 29 entries
         // ============================================================
         
-        // 0: AND (dan)
-        tab.push(TabEntry {
-            name: "dan".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 0,
-        });
-        
-        // 1: ARRAY (larik)
-        tab.push(TabEntry {
-            name: "larik".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 1,
-        });
-        
-        // 2: BEGIN (mulai)
-        tab.push(TabEntry {
-            name: "mulai".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 2,
-        });
-        
-        // 3: CASE (kasus)
-        tab.push(TabEntry {
-            name: "kasus".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 3,
-        });
-        
-        // 4: CONST (konstanta)
-        tab.push(TabEntry {
-            name: "konstanta".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 4,
-        });
-        
-        // 5: DIV (bagi)
-        tab.push(TabEntry {
-            name: "bagi".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 5,
-        });
-        
-        // 6: DOWNTO (turun_ke)
-        tab.push(TabEntry {
-            name: "turun_ke".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 6,
-        });
-        
-        // 7: DO (lakukan)
-        tab.push(TabEntry {
-            name: "lakukan".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 7,
-        });
-        
-        // 8: ELSE (selain_itu)
-        tab.push(TabEntry {
-            name: "selain_itu".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 8,
-        });
-        
-        // 9: END (selesai)
-        tab.push(TabEntry {
-            name: "selesai".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 9,
-        });
-        
-        // 10: FOR (untuk)
-        tab.push(TabEntry {
-            name: "untuk".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 10,
-        });
-        
-        // 11: FUNCTION (fungsi)
-        tab.push(TabEntry {
-            name: "fungsi".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 11,
-        });
-        
-        // 12: IF (jika)
-        tab.push(TabEntry {
-            name: "jika".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 12,
-        });
-        
-        // 13: MOD (mod)
-        tab.push(TabEntry {
-            name: "mod".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 13,
-        });
-        
-        // 14: NOT (tidak)
-        tab.push(TabEntry {
-            name: "tidak".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 14,
-        });
-        
-        // 15: OF (dari)
-        tab.push(TabEntry {
-            name: "dari".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 15,
-        });
-        
-        // 16: OR (atau)
-        tab.push(TabEntry {
-            name: "atau".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 16,
-        });
-        
-        // 17: PROCEDURE (prosedur)
-        tab.push(TabEntry {
-            name: "prosedur".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 17,
-        });
-        
-        // 18: PROGRAM (program)
-        tab.push(TabEntry {
-            name: "program".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 18,
-        });
-        
-        // 19: RECORD (rekaman)
-        tab.push(TabEntry {
-            name: "rekaman".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 19,
-        });
-        
-        // 20: REPEAT (ulangi)
-        tab.push(TabEntry {
-            name: "ulangi".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 20,
-        });
-        
-        // 21: STRING (string) - Note: not in dfa_rules.json keywords
-        tab.push(TabEntry {
-            name: "string".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::String,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 21,
-        });
-        
-        // 22: THEN (maka)
-        tab.push(TabEntry {
-            name: "maka".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 22,
-        });
-        
-        // 23: TO (ke)
-        tab.push(TabEntry {
-            name: "ke".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 23,
-        });
-        
-        // 24: TYPE (tipe)
-        tab.push(TabEntry {
-            name: "tipe".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 24,
-        });
-        
-        // 25: UNTIL (sampai)
-        tab.push(TabEntry {
-            name: "sampai".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 25,
-        });
-        
-        // 26: VAR (variabel)
-        tab.push(TabEntry {
-            name: "variabel".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 26,
-        });
-        
-        // 27: WHILE (selama)
-        tab.push(TabEntry {
-            name: "selama".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 27,
-        });
-        
-        // 28: PACKED (padat)
-        tab.push(TabEntry {
-            name: "padat".to_string(),
-            link: None,
-            obj: ObjectKind::Type,
-            data_type: DataType::Unknown,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 28,
-        });
+        for (address, keyword) in RESERVED_WORDS.into_iter().enumerate() {
+            // `string` is usable as a type name, the other reserved words have no type
+            let data_type = if keyword == Keyword::String { DataType::String } else { DataType::Unknown };
+            tab.push(TabEntry {
+                name: keywords.spelling(keyword).to_string(),
+                link: None,
+                obj: ObjectKind::Type,
+                data_type,
+                ref_index: None,
+                normal: true,
+                level: 0,
+                address,
+            });
+        }
         
         // ============================================================
-        // PREDEFINED PROCEDURES (indices 29-32)
-        // These are always available and cannot be redeclared
+        // PREDEFINED PROCEDURES AND FUNCTIONS (from index 29)
+        // These are always available, their signatures live in builtin.rs
         // ============================================================
         
-        // 29: writeln
-        tab.push(TabEntry {
-            name: "writeln".to_string(),
-            link: None,
-            obj: ObjectKind::Procedure,
-            data_type: DataType::Void,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 29,
-        });
-        
-        // 30: write
-        tab.push(TabEntry {
-            name: "write".to_string(),
-            link: None,
-            obj: ObjectKind::Procedure,
-            data_type: DataType::Void,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 30,
-        });
+        for builtin in BUILTINS {
+            let address = tab.len();
+            tab.push(TabEntry {
+                name: builtin.name.to_string(),
+                link: None,
+                obj: if builtin.is_function() { ObjectKind::Function } else { ObjectKind::Procedure },
+                data_type: builtin.data_type(),
+                ref_index: None,
+                normal: true,
+                level: 0,
+                address,
+            });
+        }
         
-        // 31: readln
-        tab.push(TabEntry {
-            name: "readln".to_string(),
-            link: None,
-            obj: ObjectKind::Procedure,
-            data_type: DataType::Void,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 31,
-        });
+        // ============================================================
+        // STANDARD FILES (after the built-ins)
+        // ============================================================
         
-        // 32: read
-        tab.push(TabEntry {
-            name: "read".to_string(),
-            link: None,
-            obj: ObjectKind::Procedure,
-            data_type: DataType::Void,
-            ref_index: None,
-            normal: true,
-            level: 0,
-            address: 32,
-        });
+        for name in STANDARD_FILES {
+            let address = tab.len();
+            tab.push(TabEntry {
+                name: name.to_string(),
+                link: None,
+                obj: ObjectKind::Variable,
+                data_type: DataType::Text,
+                ref_index: None,
+                normal: true,
+                level: 0,
+                address,
+            });
+        }
         
         // ============================================================
-        // USER IDENTIFIERS START FROM INDEX 33
+        // USER IDENTIFIERS START FROM FIRST_USER_INDEX
         // ============================================================
         
         // Initialize btab with global block (index 0)
@@ -469,12 +187,19 @@ impl SymbolTable {
         
         let atab = Vec::new();
         let display = vec![0]; // Display[0] points to global block
+        let reserved = tab
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| (entry.name.clone(), index))
+            .collect();
         
         SymbolTable {
             tab,
             btab,
             atab,
             display,
+            reserved,
+            block_names: vec![HashMap::new()],
         }
     }
     
@@ -487,10 +212,23 @@ impl SymbolTable {
             param_size: 0,
             var_size: 0,
         });
+        self.block_names.push(HashMap::new());
         self.display.push(block_index);
         block_index
     }
     
+    /// Make an existing block current again, so a routine's parameters and locals
+    /// land in the block recorded as its ref_index
+    pub fn reenter_block(&mut self, block_index: usize) {
+        debug_assert!(
+            block_index < self.btab.len(),
+            "cannot re-enter block {}, btab has {} blocks",
+            block_index,
+            self.btab.len()
+        );
+        self.display.push(block_index);
+    }
+
     /// Exit current block
     pub fn exit_block(&mut self) {
         if self.display.len() > 1 {
@@ -508,6 +246,13 @@ impl SymbolTable {
         let index = self.tab.len();
         let level = self.current_level();
         let block_index = self.display[level];
+        debug_assert!(
+            block_index < self.btab.len(),
+            "display level {} refers to block {}, btab has {} blocks",
+            level,
+            block_index,
+            self.btab.len()
+        );
         
         let mut entry = entry;
         
@@ -525,90 +270,133 @@ impl SymbolTable {
         
         entry.link = prev_same_type;  // Link to previous entry of same type (or None)
         
+        self.block_names[block_index].insert(entry.name.clone(), index);
         self.tab.push(entry);
         
         // Update btab.last to point to the most recently inserted identifier
         self.btab[block_index].last = index;
+        debug_assert!(
+            self.chain_terminates(block_index),
+            "link chain of block {} does not run back to 0",
+            block_index
+        );
         
         index
     }
+
+    /// Every link in the chain from a block's last entry points to an earlier entry,
+    /// so following it always ends
+    fn chain_terminates(&self, block_index: usize) -> bool {
+        let mut current = self.btab[block_index].last;
+        while let Some(previous) = self.tab.get(current).and_then(|entry| entry.link) {
+            if previous >= current {
+                return false;
+            }
+            current = previous;
+        }
+        current < self.tab.len()
+    }
     
     /// Lookup an identifier in current and outer scopes
     pub fn lookup(&self, name: &str) -> Option<usize> {
         // Search from current level down to global level
         for level in (0..=self.current_level()).rev() {
             let block_index = self.display[level];
-            let mut current = self.btab[block_index].last;  // Points to last (most recent) identifier
-            
-            // Follow the backward linked list in this block
-            while current > 0 {
-                if self.tab[current].name == name {
-                    return Some(current);
-                }
-                current = self.tab[current].link.unwrap_or(0);
+            if let Some(&index) = self.block_names[block_index].get(name) {
+                return Some(index);
             }
         }
         
-        // Check reserved words and predefined procedures (indices 0-32)
-        for i in 0..33 {
-            if self.tab[i].name == name {
-                return Some(i);
-            }
-        }
-        
-        // Check for dynamically inserted identifiers after index 32
-        for i in 33..self.tab.len() {
-            if self.tab[i].name == name && self.tab[i].level == 0 {
-                return Some(i);
+        // Check reserved words and predefined identifiers (below FIRST_USER_INDEX)
+        self.reserved.get(name).copied()
+    }
+    
+    /// Every identifier reachable from the current scope, innermost first, each block in
+    /// declaration order, then reserved words and predefined identifiers. A name hidden
+    /// by an inner declaration only appears once, as its innermost entry.
+    pub fn visible_identifiers(&self) -> Vec<&TabEntry> {
+        let mut seen = HashSet::new();
+        let mut visible = Vec::new();
+
+        let blocks = (0..=self.current_level())
+            .rev()
+            .map(|level| &self.block_names[self.display[level]]);
+        for names in blocks.chain(std::iter::once(&self.reserved)) {
+            let mut indices: Vec<usize> = names.values().copied().collect();
+            indices.sort_unstable();
+            for index in indices {
+                if seen.insert(self.tab[index].name.as_str()) {
+                    visible.push(&self.tab[index]);
+                }
             }
         }
-        
-        None
+
+        visible
     }
     
-    /// Insert new identifier at global level after user declarations have completed
-    pub fn insert_at_global(&mut self, mut entry: TabEntry) -> usize {
-        let index = self.tab.len();
-        let block_index = 0;  // Always use global block
-        
-        let mut prev_same_type: Option<usize> = None;
+    /// Lookup identifier only in current scope (for redeclaration checking)
+    pub fn lookup_current_scope(&self, name: &str) -> Option<usize> {
+        self.block_names[self.current_block()].get(name).copied()
+    }
+    
+    /// Lookup an identifier declared directly in the given block, e.g. a record field
+    pub fn lookup_in_block(&self, block_index: usize, name: &str) -> Option<usize> {
+        self.block_names[block_index].get(name).copied()
+    }
+    
+    /// Identifiers of a block, most recent first, following the link chain.
+    /// Only complete for blocks holding a single kind of object (e.g. record fields).
+    pub fn block_entries(&self, block_index: usize) -> Vec<usize> {
+        let mut entries = Vec::new();
         let mut current = self.btab[block_index].last;
-        
+
         while current > 0 {
-            if self.tab[current].obj == entry.obj {
-                prev_same_type = Some(current);
-                break;
-            }
+            entries.push(current);
             current = self.tab[current].link.unwrap_or(0);
         }
-        
-        entry.link = prev_same_type;  // Previous entry of same type (or None)
-        entry.level = 0;  // Force global level
-        
-        self.tab.push(entry);
-        index
-    }
-    
-    /// Check if an identifier is a built-in procedure or constant
-    /// recognized by lexer but not stored in symbol table
-    pub fn is_builtin(&self, name: &str) -> bool {
-        matches!(name, "writeln" | "write" | "readln" | "read" | "true" | "false")
+
+        entries
     }
     
-    /// Lookup identifier only in current scope (for redeclaration checking)
-    pub fn lookup_current_scope(&self, name: &str) -> Option<usize> {
-        let level = self.current_level();
-        let block_index = self.display[level];
-        let mut current = self.btab[block_index].last;  // Points to most recent identifier
-        
-        while current > 0 {
-            if self.tab[current].name == name {
-                return Some(current);
+    /// Spell out arrays and records from atab/btab, e.g. `array[1..3] of record x: integer end`
+    pub fn describe_type(&self, data_type: &DataType) -> String {
+        match data_type {
+            DataType::Array(atab_index) => {
+                let entry = &self.atab[*atab_index];
+                let bound = |value: i32| match entry.index_type {
+                    DataType::Char => match u32::try_from(value).ok().and_then(char::from_u32) {
+                        Some(c) => format!("'{}'", c),
+                        None => value.to_string(),
+                    },
+                    DataType::Boolean => (value != 0).to_string(),
+                    _ => value.to_string(),
+                };
+                format!(
+                    "array[{}..{}] of {}",
+                    bound(entry.low_bound),
+                    bound(entry.high_bound),
+                    self.describe_type(&entry.element_type)
+                )
             }
-            current = self.tab[current].link.unwrap_or(0);
+            DataType::Record(block_index) => {
+                let fields: Vec<String> = self
+                    .block_entries(*block_index)
+                    .iter()
+                    .rev()
+                    .map(|&field| {
+                        let entry = &self.tab[field];
+                        format!("{}: {}", entry.name, self.describe_type(&entry.data_type))
+                    })
+                    .collect();
+                if fields.is_empty() {
+                    "record end".to_string()
+                } else {
+                    format!("record {} end", fields.join("; "))
+                }
+            }
+            DataType::Set(base) => format!("set of {}", self.describe_type(base)),
+            _ => format!("{}", data_type),
         }
-        
-        None
     }
     
     /// Add an array type to atab
@@ -665,6 +453,20 @@ impl fmt::Display for SymbolTable {
             )?;
         }
         
+        // Identifiers reachable from each btab[i].last by following link
+        writeln!(f, "\nBlock Chains (last -> link):")?;
+        writeln!(f, "{}", "-".repeat(30))?;
+        
+        for i in 0..self.btab.len() {
+            let chain: Vec<String> = self
+                .block_entries(i)
+                .iter()
+                .map(|&index| format!("{}({})", self.tab[index].name, index))
+                .collect();
+            let chain = if chain.is_empty() { "-".to_string() } else { chain.join(" -> ") };
+            writeln!(f, "{:<4} {}", i, chain)?;
+        }
+        
         if !self.atab.is_empty() {
             writeln!(f, "\nArray Table (atab):")?;
             writeln!(f, "{:<4} {:<10} {:<10} {:<5} {:<6} {:<6} {:<6} {:<6}", 
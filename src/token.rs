@@ -1,3 +1,4 @@
+use crate::keyword::Keyword;
 use std::fmt;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -20,18 +21,40 @@ pub enum TokenType {
     LBracket,
     RBracket,
     RangeOperator,
+    Caret,
+    At,
+    Eof, // End of input, only produced by the parser
 }
 
-#[derive(Debug, Clone)]
-pub struct Token {
-    pub token_type: TokenType,
-    pub value: String,
-}
+impl TokenType {
+    /// Token type named by a final state in the DFA rules
+    pub fn from_dfa_name(name: &str) -> Option<TokenType> {
+        let token_type = match name {
+            "IDENTIFIER" => TokenType::Identifier,
+            "NUMBER" => TokenType::Number,
+            "STRING_LITERAL" => TokenType::StringLiteral,
+            "ASSIGN_OPERATOR" => TokenType::AssignOperator,
+            "RELATIONAL_OPERATOR" => TokenType::RelationalOperator,
+            "ARITHMETIC_OPERATOR" => TokenType::ArithmeticOperator,
+            "COLON" => TokenType::Colon,
+            "DOT" => TokenType::Dot,
+            "RANGE_OPERATOR" => TokenType::RangeOperator,
+            "SEMICOLON" => TokenType::Semicolon,
+            "COMMA" => TokenType::Comma,
+            "LPARENTHESIS" => TokenType::LParenthesis,
+            "RPARENTHESIS" => TokenType::RParenthesis,
+            "LBRACKET" => TokenType::LBracket,
+            "RBRACKET" => TokenType::RBracket,
+            "CARET" => TokenType::Caret,
+            "AT" => TokenType::At,
+            _ => return None,
+        };
+        Some(token_type)
+    }
 
-impl fmt::Display for Token {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // We convert the enum variant to a string for the output
-        let type_str = match self.token_type {
+    /// Name used in token listings and diagnostics
+    pub fn name(&self) -> &'static str {
+        match self {
             TokenType::Keyword => "KEYWORD",
             TokenType::Identifier => "IDENTIFIER",
             TokenType::ArithmeticOperator => "ARITHMETIC_OPERATOR",
@@ -50,7 +73,49 @@ impl fmt::Display for Token {
             TokenType::LBracket => "LBRACKET",
             TokenType::RBracket => "RBRACKET",
             TokenType::RangeOperator => "RANGE_OPERATOR",
-        };
-        write!(f, "{}({})", type_str, self.value)
+            TokenType::Caret => "CARET",
+            TokenType::At => "AT",
+            TokenType::Eof => "EOF",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub token_type: TokenType,
+    pub value: String,
+    pub line: usize,   // 1-based line of the first character
+    pub column: usize, // 1-based column of the first character
+    pub offset: usize, // Byte offset of the first character in the source
+    pub keyword: Option<Keyword>, // Role of a reserved word or word operator
+}
+
+impl Token {
+    /// Column headings of the token listing, aligned with `fmt_table_row`
+    pub fn table_header() -> String {
+        format!("{:<5} {:<5} {:<4} {:<20} {}", "idx", "line", "col", "type", "value")
+    }
+
+    /// One row of the token listing: index, line, column, type and value
+    pub fn fmt_table_row(&self, index: usize) -> String {
+        format!(
+            "{:<5} {:<5} {:<4} {:<20} {}",
+            index,
+            self.line,
+            self.column,
+            self.token_type.name(),
+            self.value
+        )
+        .trim_end()
+        .to_string()
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.token_type {
+            TokenType::Eof => write!(f, "EOF"),
+            _ => write!(f, "{}({})", self.token_type.name(), self.value),
+        }
     }
 }
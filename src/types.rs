@@ -1,3 +1,4 @@
+use serde::ser::{Serialize, SerializeStruct, Serializer};
 use std::fmt;
 
 /// Represents the data types in Pascal-S
@@ -9,6 +10,10 @@ pub enum DataType {
     Char,
     String,
     Array(usize), // Index to atab
+    Record(usize), // Index to btab
+    Pointer(Box<DataType>), // ^T
+    Set(Box<DataType>), // Set of an ordinal base type, unknown base for []
+    Text,    // Standard files input/output
     UserDefined(String),
     Void,    // For procedures
     Unknown, // For error recovery
@@ -23,6 +28,10 @@ impl fmt::Display for DataType {
             DataType::Char => write!(f, "char"),
             DataType::String => write!(f, "string"),
             DataType::Array(idx) => write!(f, "array[{}]", idx),
+            DataType::Record(idx) => write!(f, "record[{}]", idx),
+            DataType::Pointer(target) => write!(f, "^{}", target),
+            DataType::Set(base) => write!(f, "set of {}", base),
+            DataType::Text => write!(f, "text"),
             DataType::UserDefined(name) => write!(f, "{}", name),
             DataType::Void => write!(f, "void"),
             DataType::Unknown => write!(f, "unknown"),
@@ -30,6 +39,49 @@ impl fmt::Display for DataType {
     }
 }
 
+/// Size of the integer type, which decides the range of integer values
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum IntWidth {
+    Bits16,
+    #[default]
+    Bits32,
+    Bits64,
+}
+
+impl IntWidth {
+    /// Width for a `--int-width` value
+    pub fn from_bits(bits: &str) -> Option<IntWidth> {
+        match bits {
+            "16" => Some(IntWidth::Bits16),
+            "32" => Some(IntWidth::Bits32),
+            "64" => Some(IntWidth::Bits64),
+            _ => None,
+        }
+    }
+
+    pub fn bits(&self) -> u32 {
+        match self {
+            IntWidth::Bits16 => 16,
+            IntWidth::Bits32 => 32,
+            IntWidth::Bits64 => 64,
+        }
+    }
+
+    /// Smallest and largest integer, the ordinal bounds of the integer type
+    pub fn bounds(&self) -> (i64, i64) {
+        match self {
+            IntWidth::Bits16 => (i16::MIN.into(), i16::MAX.into()),
+            IntWidth::Bits32 => (i32::MIN.into(), i32::MAX.into()),
+            IntWidth::Bits64 => (i64::MIN, i64::MAX),
+        }
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let (min, max) = self.bounds();
+        (min..=max).contains(&value)
+    }
+}
+
 /// Represents the kind of object an identifier refers to
 #[derive(Debug, Clone, PartialEq)]
 pub enum ObjectKind {
@@ -40,6 +92,7 @@ pub enum ObjectKind {
     Function,
     Parameter,
     Program,
+    Label,
 }
 
 impl fmt::Display for ObjectKind {
@@ -52,6 +105,7 @@ impl fmt::Display for ObjectKind {
             ObjectKind::Function => write!(f, "function"),
             ObjectKind::Parameter => write!(f, "parameter"),
             ObjectKind::Program => write!(f, "program"),
+            ObjectKind::Label => write!(f, "label"),
         }
     }
 }
@@ -65,6 +119,8 @@ impl DataType {
             (DataType::Boolean, DataType::Boolean) => true,
             (DataType::Char, DataType::Char) => true,
             (DataType::String, DataType::String) => true,
+            (DataType::Pointer(a), DataType::Pointer(b)) => a == b,
+            (DataType::Set(a), DataType::Set(b)) => Self::same_set_base(a, b),
             // Integer can be promoted to Real
             (DataType::Integer, DataType::Real) | (DataType::Real, DataType::Integer) => true,
             _ => false,
@@ -81,10 +137,17 @@ impl DataType {
             (DataType::Char, DataType::Char) => true,
             (DataType::String, DataType::String) => true,
             (DataType::UserDefined(a), DataType::UserDefined(b)) => a == b,
+            (DataType::Pointer(a), DataType::Pointer(b)) => a == b,
+            (DataType::Set(a), DataType::Set(b)) => Self::same_set_base(a, b),
             _ => false,
         }
     }
 
+    /// Set bases match, the empty set `[]` matches any base
+    fn same_set_base(a: &DataType, b: &DataType) -> bool {
+        a == b || *a == DataType::Unknown || *b == DataType::Unknown
+    }
+
     /// Get the result type of a binary arithmetic operation
     pub fn get_arithmetic_result_type(left: &DataType, right: &DataType) -> Result<DataType, String> {
         match (left, right) {
@@ -100,6 +163,29 @@ impl DataType {
         }
     }
 
+    /// Get the result type of real division (always real)
+    pub fn get_division_result_type(left: &DataType, right: &DataType) -> Result<DataType, String> {
+        if left.is_numeric() && right.is_numeric() {
+            Ok(DataType::Real)
+        } else {
+            Err(format!(
+                "Division requires numeric operands, got {} and {}",
+                left, right
+            ))
+        }
+    }
+
+    /// Get the result type of integer division or modulo (integer operands only)
+    pub fn get_integer_result_type(left: &DataType, right: &DataType) -> Result<DataType, String> {
+        match (left, right) {
+            (DataType::Integer, DataType::Integer) => Ok(DataType::Integer),
+            _ => Err(format!(
+                "Integer operation requires integer operands, got {} and {}",
+                left, right
+            )),
+        }
+    }
+
     /// Get the result type of a relational operation (always boolean)
     pub fn get_relational_result_type(left: &DataType, right: &DataType) -> Result<DataType, String> {
         if left.is_compatible(right) {
@@ -112,6 +198,14 @@ impl DataType {
         }
     }
 
+    /// Get the result type of `element dalam set`
+    pub fn get_membership_result_type(left: &DataType, right: &DataType) -> Result<DataType, String> {
+        match right {
+            DataType::Set(base) if Self::same_set_base(base, left) => Ok(DataType::Boolean),
+            _ => Err(format!("Cannot test membership of {} in {}", left, right)),
+        }
+    }
+
     /// Get the result type of a logical operation (must be boolean)
     pub fn get_logical_result_type(left: &DataType, right: &DataType) -> Result<DataType, String> {
         match (left, right) {
@@ -124,21 +218,29 @@ impl DataType {
     }
 
     /// Check if this is a numeric type
-    #[allow(dead_code)]
     pub fn is_numeric(&self) -> bool {
         matches!(self, DataType::Integer | DataType::Real)
     }
 
     /// Check if this is an ordinal type (can be used in for loops, array indices)
-    #[allow(dead_code)]
     pub fn is_ordinal(&self) -> bool {
         matches!(self, DataType::Integer | DataType::Char | DataType::Boolean)
     }
+
+    /// Check if this is a single value, numeric or ordinal
+    pub fn is_scalar(&self) -> bool {
+        self.is_numeric() || self.is_ordinal()
+    }
+
+    /// Check if this is made of elements or fields
+    pub fn is_composite(&self) -> bool {
+        matches!(self, DataType::Array(_) | DataType::Record(_))
+    }
     
     /// Convert DataType to numeric code (for Pascal-S compatibility)
     /// Following standard Pascal-S type codes:
     /// 0 = Void, 1 = Integer, 2 = Real, 3 = Boolean, 4 = String, 5 = Char
-    /// 6+ = Array/Record (ref to atab/btab)
+    /// 6+ = Array/Record (ref to atab/btab), 7 = Pointer, 8 = Text, 9 = Set
     pub fn to_numeric(&self) -> String {
         match self {
             DataType::Void => "0".to_string(),
@@ -148,8 +250,22 @@ impl DataType {
             DataType::String => "4".to_string(),
             DataType::Char => "5".to_string(),
             DataType::Array(idx) => format!("{}", idx),
+            DataType::Record(idx) => format!("{}", idx),
             DataType::UserDefined(_) => "6".to_string(),
+            DataType::Pointer(_) => "7".to_string(),
+            DataType::Text => "8".to_string(),
+            DataType::Set(_) => "9".to_string(),
             DataType::Unknown => "-".to_string(),
         }
     }
 }
+
+/// Serialized with both spellings, e.g. `{"name": "array[2]", "code": "2"}`
+impl Serialize for DataType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("DataType", 2)?;
+        state.serialize_field("name", &self.to_string())?;
+        state.serialize_field("code", &self.to_numeric())?;
+        state.end()
+    }
+}
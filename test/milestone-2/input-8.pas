@@ -0,0 +1,10 @@
+program SalahKataKunci;
+
+variabel
+    i: integer;
+
+mulai
+    i := 0;
+    selama i < 10 maka
+        i := i + 1;
+selesai.
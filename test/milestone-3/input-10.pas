@@ -0,0 +1,21 @@
+program RecursiveTypes;
+
+tipe
+  A = B;
+  B = A;
+  Node = rekaman
+    value : integer;
+    next : Node;
+  selesai;
+  Point = rekaman
+    x, y : integer;
+  selesai;
+  Line = rekaman
+    p, q : Point;
+  selesai;
+
+variabel
+  l : Line;
+
+mulai
+selesai.
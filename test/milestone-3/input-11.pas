@@ -0,0 +1,20 @@
+program IndeksLarik;
+
+konstanta
+  N = 10;
+  K = 3;
+  Besar = 11;
+
+variabel
+  a : larik[1..N] dari integer;
+  m : larik[1..3, 1..4] dari integer;
+  i, x : integer;
+
+mulai
+  i := 2;
+  x := a[K];
+  x := a[i];
+  x := m[2, 4];
+  x := a[Besar];
+  x := m[1][5];
+selesai.
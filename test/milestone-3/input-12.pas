@@ -0,0 +1,15 @@
+program KonstantaBertipe;
+
+konstanta
+  Maks : integer = 100;
+  Pi : real = 3;
+  Salah : integer = 3.5;
+  Nama = 'pascal';
+
+variabel
+  x : real;
+
+mulai
+  x := Pi * Maks;
+  writeln(Nama)
+selesai.
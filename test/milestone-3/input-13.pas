@@ -0,0 +1,29 @@
+program TidakTerpakai;
+
+variabel
+  a, b, sisa : integer;
+
+prosedur cetak(x : integer);
+mulai
+  writeln(x)
+selesai;
+
+prosedur lupa;
+mulai
+  writeln('tidak pernah dipanggil')
+selesai;
+
+fungsi kuadrat(n : integer) : integer;
+mulai
+  kuadrat := n * n
+selesai;
+
+fungsi tanpaPanggil(n : integer) : integer;
+mulai
+  tanpaPanggil := n
+selesai;
+
+mulai
+  a := kuadrat(3);
+  cetak(a + b)
+selesai.
@@ -0,0 +1,18 @@
+program RantaiJika;
+
+variabel
+  nilai : integer;
+  huruf : char;
+
+mulai
+  nilai := 75;
+  jika nilai >= 80 maka
+    huruf := 'A'
+  selain_itu jika nilai >= 70 maka
+    huruf := 'B'
+  selain_itu jika nilai >= 60 maka
+    huruf := 'C'
+  selain_itu
+    huruf := 'E';
+  writeln(huruf)
+selesai.
@@ -0,0 +1,26 @@
+program LingkupParameter;
+
+variabel
+  total : integer;
+
+prosedur tambah(a, b : integer);
+variabel
+  hasil : integer;
+mulai
+  hasil := a + b;
+  total := total + hasil
+selesai;
+
+fungsi ganda(n : integer) : integer;
+variabel
+  i : integer;
+mulai
+  i := n;
+  ganda := i * 2
+selesai;
+
+mulai
+  total := 0;
+  tambah(1, 2);
+  total := ganda(total)
+selesai.
@@ -0,0 +1,16 @@
+program LarikKarakter;
+
+variabel
+  nama : larik[1..5] dari char;
+  salinan : larik[1..5] dari char;
+  angka : larik[1..5] dari integer;
+  n : integer;
+
+mulai
+  nama := 'halo!';
+  salinan := nama;
+  writeln('Nama: ', nama);
+  write(salinan);
+  n := nama;
+  writeln(angka)
+selesai.
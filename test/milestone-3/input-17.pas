@@ -0,0 +1,31 @@
+program KasusBoolean;
+
+konstanta
+  Batas = 10;
+
+variabel
+  lulus : boolean;
+  nilai : integer;
+
+mulai
+  nilai := 75;
+  lulus := nilai >= 60;
+  jika lulus = benar maka
+    writeln('lulus');
+
+  kasus lulus dari
+    benar: writeln('selamat');
+    salah: writeln('coba lagi')
+  selesai;
+
+  kasus lulus dari
+    benar: writeln('hanya benar')
+  selesai;
+
+  kasus nilai dari
+    1, 2, 3: writeln('kecil');
+    Batas: writeln('batas');
+  selain_itu
+    writeln('lainnya')
+  selesai
+selesai.
@@ -0,0 +1,24 @@
+program KasusSalah;
+
+variabel
+  x : integer;
+  r : real;
+  b : boolean;
+
+mulai
+  kasus r dari
+    1: writeln('satu')
+  selesai;
+
+  kasus x dari
+    1: writeln('satu');
+    1: writeln('lagi');
+    x: writeln('variabel');
+    'a': writeln('karakter')
+  selesai;
+
+  kasus b dari
+    benar, salah: writeln('semua');
+    benar: writeln('dobel')
+  selesai
+selesai.
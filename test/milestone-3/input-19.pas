@@ -0,0 +1,20 @@
+program JumlahInput;
+variabel
+  x, total, baris : integer;
+  selesaiBaris : boolean;
+mulai
+  total := 0;
+  baris := 0;
+  selama tidak eof lakukan
+  mulai
+    selama tidak eoln() lakukan
+    mulai
+      read(x);
+      total := total + x
+    selesai;
+    selesaiBaris := eoln(input);
+    readln;
+    baris := baris + 1
+  selesai;
+  writeln(output, 'total: ', total, ' dari ', baris, ' baris')
+selesai.
@@ -0,0 +1,130 @@
+//! Parsing and analysis in one call, with syntax and semantic errors kept apart.
+
+use cgk_tubes_if2224::{
+    ast::AstNode,
+    dfa::Dfa,
+    lexer::Lexer,
+    semantic_analyzer::{AnalysisError, SemanticAnalyzer},
+    semantic_error::SemanticErrorKind,
+    token::Token,
+    types::DataType,
+};
+
+fn tokens(source: &str) -> Vec<Token> {
+    let dfa = Dfa::from_file("dfa_rules.json").expect("dfa_rules.json should load");
+    let mut lexer = Lexer::new(source.to_string(), dfa);
+    std::iter::from_fn(|| lexer.get_next_token()).collect()
+}
+
+#[test]
+fn valid_program_yields_the_decorated_ast() {
+    let mut analyzer = SemanticAnalyzer::new();
+    let ast = analyzer
+        .analyze_source(tokens("program P; variabel x : integer; mulai x := 1 selesai."))
+        .expect("program should analyze");
+
+    assert!(matches!(ast, AstNode::Program { ref name, .. } if name == "P"));
+    assert!(analyzer.symbol_table.lookup("x").is_some());
+}
+
+#[test]
+fn syntax_error_stops_before_analysis() {
+    let mut analyzer = SemanticAnalyzer::new();
+    let result = analyzer.analyze_source(tokens("program P; mulai x := selesai."));
+
+    let Err(AnalysisError::Syntax(error)) = result else {
+        panic!("expected a syntax error, got {:?}", result);
+    };
+    assert_eq!(error.token.value, "selesai");
+    assert!(analyzer.errors.is_empty());
+}
+
+#[test]
+fn semantic_errors_are_returned_together() {
+    let mut analyzer = SemanticAnalyzer::new();
+    let result = analyzer.analyze_source(tokens("program P; mulai x := 1; y := 2 selesai."));
+
+    let Err(AnalysisError::Semantic(errors)) = result else {
+        panic!("expected semantic errors, got {:?}", result);
+    };
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].message.contains("'x'"));
+}
+
+#[test]
+fn unknown_variable_type_is_reported_once_at_the_declaration() {
+    let mut analyzer = SemanticAnalyzer::new();
+    let result = analyzer.analyze_source(tokens(
+        "program P; variabel x : NoSuchType; y : integer; mulai x := 1; y := x + 1 selesai.",
+    ));
+
+    let Err(AnalysisError::Semantic(errors)) = result else {
+        panic!("expected semantic errors, got {:?}", result);
+    };
+    // Uses of x are not reported again, its type is already known to be wrong
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].token.as_ref().map(|token| token.value.as_str()), Some("NoSuchType"));
+    assert!(errors[0].message.contains("Unknown type 'NoSuchType'"));
+}
+
+#[test]
+fn errors_can_be_matched_by_kind() {
+    let mut analyzer = SemanticAnalyzer::new();
+    let result = analyzer.analyze_source(tokens(
+        "program P; variabel x : integer; mulai x(1); x := 'a'; y := 1 selesai.",
+    ));
+
+    let Err(AnalysisError::Semantic(errors)) = result else {
+        panic!("expected semantic errors, got {:?}", result);
+    };
+    assert!(matches!(errors[0].kind(), SemanticErrorKind::NotCallable(name) if name == "x"));
+    assert!(matches!(errors[1].kind(), SemanticErrorKind::TypeMismatch { expected, .. } if expected == "integer"));
+    assert!(matches!(errors[2].kind(), SemanticErrorKind::UndeclaredIdentifier(name) if name == "y"));
+}
+
+#[test]
+fn parameter_counts_are_matched_by_bounds() {
+    let mut analyzer = SemanticAnalyzer::new();
+    let result = analyzer.analyze_source(tokens("program P; mulai halt(1, 2); writeln(abs(1, 2)) selesai."));
+
+    let Err(AnalysisError::Semantic(errors)) = result else {
+        panic!("expected semantic errors, got {:?}", result);
+    };
+    assert!(matches!(
+        errors[0].kind(),
+        SemanticErrorKind::WrongParameterCount { min: 0, max: Some(1), found: 2 }
+    ));
+    assert!(matches!(
+        errors[1].kind(),
+        SemanticErrorKind::WrongParameterCount { min: 1, max: Some(1), found: 2 }
+    ));
+    assert_eq!(errors[0].message, "Wrong number of parameters: expected 0 or 1, found 2");
+    assert_eq!(errors[1].message, "Wrong number of parameters: expected 1, found 2");
+}
+
+#[test]
+fn expression_type_is_inferred_against_the_declarations() {
+    let dfa = Dfa::from_file("dfa_rules.json").expect("dfa_rules.json should load");
+    let mut analyzer = SemanticAnalyzer::new();
+    analyzer
+        .analyze_source(tokens("program P; variabel a, b : integer; mulai a := b selesai."))
+        .expect("program should analyze");
+
+    assert_eq!(analyzer.infer_expression_type("1 + 2.0", &dfa).unwrap(), DataType::Real);
+    assert_eq!(analyzer.infer_expression_type("a > b", &dfa).unwrap(), DataType::Boolean);
+    assert_eq!(analyzer.infer_expression_type("a bagi 2", &dfa).unwrap(), DataType::Integer);
+}
+
+#[test]
+fn expression_type_reports_each_phase_apart() {
+    let dfa = Dfa::from_file("dfa_rules.json").expect("dfa_rules.json should load");
+    let mut analyzer = SemanticAnalyzer::new();
+
+    assert!(matches!(analyzer.infer_expression_type("1 ? 2", &dfa), Err(AnalysisError::Lexical(_))));
+    assert!(matches!(analyzer.infer_expression_type("1 +", &dfa), Err(AnalysisError::Syntax(_))));
+    let result = analyzer.infer_expression_type("c + 1", &dfa);
+    let Err(AnalysisError::Semantic(errors)) = result else {
+        panic!("expected semantic errors, got {:?}", result);
+    };
+    assert!(matches!(errors[0].kind(), SemanticErrorKind::UndeclaredIdentifier(name) if name == "c"));
+}
@@ -0,0 +1,90 @@
+//! Runs the semantic analyzer on parse trees that the parser would not produce,
+//! checking that malformed nodes are tolerated instead of panicking.
+
+mod common;
+
+use cgk_tubes_if2224::{
+    node::{NodeType, ParseNode},
+    semantic_analyzer::SemanticAnalyzer,
+};
+use common::parse;
+
+const SOURCE: &str = "program P;
+variabel
+  b : boolean;
+mulai
+  b := 1 < 2
+selesai.
+";
+
+/// First expression node in the tree, depth first
+fn find_expression(node: &mut ParseNode) -> Option<&mut ParseNode> {
+    if let NodeType::Expression = node.node_type {
+        return Some(node);
+    }
+    node.children.iter_mut().find_map(find_expression)
+}
+
+/// Edit applied to the children of the expression node
+type Reshape = fn(&mut Vec<ParseNode>);
+
+/// Analyze SOURCE after reshaping the expression of `b := 1 < 2`
+fn analyze_with(reshape: impl FnOnce(&mut Vec<ParseNode>)) -> Result<(), usize> {
+    let mut tree = parse(SOURCE);
+    let expression = find_expression(&mut tree).expect("source has an expression");
+    reshape(&mut expression.children);
+
+    SemanticAnalyzer::new().analyze(&tree).map(|_| ()).map_err(|errors| errors.len())
+}
+
+#[test]
+fn well_formed_expression_is_accepted() {
+    assert_eq!(analyze_with(|_| {}), Ok(()));
+}
+
+#[test]
+fn malformed_expression_nodes_do_not_panic() {
+    let reshapes: [(&str, Reshape); 6] = [
+        ("no children", |children| children.clear()),
+        ("missing right operand", |children| children.truncate(2)),
+        ("missing left operand", |children| {
+            children.remove(0);
+        }),
+        ("operator only", |children| {
+            children.remove(2);
+            children.remove(0);
+        }),
+        ("two operands, no operator", |children| {
+            children.remove(1);
+        }),
+        ("operand is not a simple expression", |children| {
+            children[0] = ParseNode::new(NodeType::Term);
+        }),
+    ];
+
+    for (name, reshape) in reshapes {
+        // The expression becomes Empty, so only the assignment is reported
+        assert_eq!(analyze_with(reshape), Err(1), "{}", name);
+    }
+}
+
+/// First factor node that starts with `tidak`, depth first
+fn find_not_factor(node: &mut ParseNode) -> Option<&mut ParseNode> {
+    let is_not = matches!(node.node_type, NodeType::Factor)
+        && matches!(node.children.first().map(|child| &child.node_type),
+            Some(NodeType::Terminal(token)) if token.value == "tidak");
+    if is_not {
+        return Some(node);
+    }
+    node.children.iter_mut().find_map(find_not_factor)
+}
+
+#[test]
+fn not_without_operand_is_reported() {
+    let mut tree = parse("program P; variabel b : boolean; mulai b := tidak benar selesai.");
+    let factor = find_not_factor(&mut tree).expect("source has a 'tidak' factor");
+    factor.children.truncate(1);
+
+    let errors = SemanticAnalyzer::new().analyze(&tree).map(|_| ()).unwrap_err();
+    assert_eq!(errors[0].message, "Operator 'tidak' is missing its operand");
+}
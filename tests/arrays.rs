@@ -0,0 +1,118 @@
+//! Layout of array types in atab: element sizes, bounds and element offsets, and
+//! structural equality of array types.
+
+mod common;
+
+use cgk_tubes_if2224::{semantic_analyzer::SemanticAnalyzer, symbol_table::ATabEntry, types::DataType};
+use common::parse;
+
+/// atab after analyzing `source`, which must be free of semantic errors
+fn arrays_of(source: &str) -> Vec<ATabEntry> {
+    let mut analyzer = SemanticAnalyzer::new();
+    if let Err(errors) = analyzer.analyze(&parse(source)) {
+        panic!("unexpected semantic errors: {:?}", errors);
+    }
+    analyzer.symbol_table.atab
+}
+
+#[test]
+fn negative_lower_bound_offsets_start_at_zero() {
+    let atab = arrays_of("program P; variabel a : larik[-5..5] dari integer; mulai writeln(a[0]) selesai.");
+    let array = &atab[0];
+
+    assert_eq!((array.low_bound, array.high_bound), (-5, 5));
+    assert_eq!(array.total_size, 11);
+    assert_eq!(array.offset(-5), Some(0));
+    assert_eq!(array.offset(0), Some(5));
+    assert_eq!(array.offset(5), Some(10));
+    assert_eq!(array.offset(-6), None);
+    assert_eq!(array.offset(6), None);
+}
+
+#[test]
+fn char_bounds_use_character_codes() {
+    let atab = arrays_of("program P; variabel a : larik['a'..'z'] dari integer; mulai writeln(a['c']) selesai.");
+    let array = &atab[0];
+
+    assert_eq!(array.index_type, DataType::Char);
+    assert_eq!((array.low_bound, array.high_bound), ('a' as i32, 'z' as i32));
+    assert_eq!(array.total_size, 26);
+    assert_eq!(array.offset('c' as i32), Some(2));
+}
+
+#[test]
+fn offsets_scale_with_element_size() {
+    let atab = arrays_of(
+        "program P;
+        tipe Titik = rekaman x, y : integer; selesai;
+        variabel a : larik[-2..2] dari Titik;
+        mulai selesai.",
+    );
+    let array = &atab[0];
+
+    assert_eq!(array.element_size, 2);
+    assert_eq!(array.total_size, 10);
+    assert_eq!(array.offset(-2), Some(0));
+    assert_eq!(array.offset(1), Some(6));
+}
+
+#[test]
+fn trunc_and_round_fold_in_constant_bounds() {
+    // trunc(-2.7) = -2 rounds toward zero, round(-2.5) = -3 rounds the half away from zero
+    let atab = arrays_of(
+        "program P;
+         konstanta Bawah = round(-2.5); Atas = trunc(-2.7);
+         variabel a : larik[Bawah..Atas] dari integer; b : larik[trunc(2.7)..round(2.5)] dari integer;
+         mulai writeln(a[-3], b[2]) selesai.",
+    );
+
+    assert_eq!((atab[0].low_bound, atab[0].high_bound), (-3, -2));
+    assert_eq!((atab[1].low_bound, atab[1].high_bound), (2, 3));
+}
+
+#[test]
+fn octal_and_binary_literals_as_bounds() {
+    let atab = arrays_of(
+        "program P;
+        konstanta atas = &O17;
+        variabel a : larik[&B0..&b1010] dari integer; b : larik[&o7..atas] dari char;
+        mulai selesai.",
+    );
+
+    assert_eq!((atab[0].low_bound, atab[0].high_bound), (0, 10));
+    assert_eq!((atab[1].low_bound, atab[1].high_bound), (7, 15));
+}
+
+#[test]
+fn separately_declared_identical_arrays_are_compatible() {
+    let mut analyzer = SemanticAnalyzer::new();
+    let result = analyzer.analyze(&parse(
+        "program P;
+        tipe Baris = larik[1..3] dari integer;
+        variabel a : larik[1..3] dari integer; b : Baris; c : larik[1..3] dari integer;
+        mulai a := b; c := a selesai.",
+    ));
+
+    assert!(result.is_ok(), "unexpected semantic errors: {:?}", result.err());
+    let atab = &analyzer.symbol_table.atab;
+    assert_ne!(atab.len(), 1, "each declaration should get its own atab entry");
+    assert!(analyzer.types_equal(&DataType::Array(0), &DataType::Array(atab.len() - 1)));
+}
+
+#[test]
+fn arrays_differing_in_bounds_or_elements_are_not_equal() {
+    let mut analyzer = SemanticAnalyzer::new();
+    let result = analyzer.analyze(&parse(
+        "program P;
+        variabel a : larik[1..3] dari integer; b : larik[0..2] dari integer; c : larik[1..3] dari char;
+        mulai a := b selesai.",
+    ));
+
+    let messages: Vec<String> = result.err().unwrap_or_default().into_iter().map(|error| error.message).collect();
+    assert_eq!(
+        messages,
+        ["Type mismatch: expected array[1..3] of integer, found array[0..2] of integer"]
+    );
+    assert!(!analyzer.types_equal(&DataType::Array(0), &DataType::Array(1)));
+    assert!(!analyzer.types_equal(&DataType::Array(0), &DataType::Array(2)));
+}
@@ -0,0 +1,41 @@
+//! The --check flag: silent with exit code 0 on success, diagnostics only on failure.
+
+use std::process::{Command, Output};
+
+fn check(fixture: &str, extra: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_CGK-Tubes-IF2224"))
+        .arg(format!("tests/fixtures/{}.pas", fixture))
+        .arg("--check")
+        .args(extra)
+        .output()
+        .expect("compiler binary should run")
+}
+
+#[test]
+fn clean_program_prints_nothing() {
+    let output = check("clean", &[]);
+
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn errors_are_the_only_output() {
+    let output = check("semantic_error", &[]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert!(stderr.contains("---SEMANTIC ERRORS---"));
+    assert!(!stderr.contains("---TOKENS---"));
+}
+
+#[test]
+fn errors_follow_the_chosen_format() {
+    let output = check("syntax_error", &["--error-format", "json"]);
+    let diagnostics: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(diagnostics[0]["phase"], "parser");
+}
@@ -0,0 +1,12 @@
+use cgk_tubes_if2224::{dfa::Dfa, lexer::Lexer, node::ParseNode, parser::Parser};
+
+/// Lex and parse `source`, panicking on a syntax error
+pub fn parse(source: &str) -> ParseNode {
+    let dfa = Dfa::from_file("dfa_rules.json").expect("dfa_rules.json should load");
+    let mut lexer = Lexer::new(source.to_string(), dfa);
+    let mut tokens = Vec::new();
+    while let Some(token) = lexer.get_next_token() {
+        tokens.push(token);
+    }
+    Parser::new(tokens).parse().expect("source should parse")
+}
@@ -0,0 +1,80 @@
+//! Looking up declarations of the decorated AST by name.
+
+use cgk_tubes_if2224::{
+    ast::AstNode, dfa::Dfa, lexer::Lexer, semantic_analyzer::SemanticAnalyzer, token::Token,
+};
+
+fn analyze(source: &str) -> AstNode {
+    let dfa = Dfa::from_file("dfa_rules.json").expect("dfa_rules.json should load");
+    let mut lexer = Lexer::new(source.to_string(), dfa);
+    let tokens: Vec<Token> = std::iter::from_fn(|| lexer.get_next_token()).collect();
+    SemanticAnalyzer::new().analyze_source(tokens).expect("program should analyze")
+}
+
+const SOURCE: &str = "
+program Indeks;
+konstanta
+  n = 3;
+tipe
+  Vektor = larik[1..n] dari integer;
+variabel
+  a, b : integer;
+
+prosedur p(x : integer);
+variabel
+  b : char;
+
+  fungsi q : integer;
+  variabel
+    c : boolean;
+  mulai
+    c := benar;
+    q := x
+  selesai;
+
+mulai
+  b := 'z';
+  a := q
+selesai;
+
+mulai
+  p(a);
+  writeln(b)
+selesai.
+";
+
+#[test]
+fn global_declarations_are_keyed_by_name() {
+    let ast = analyze(SOURCE);
+    let index = ast.declarations_index();
+
+    assert!(matches!(index["n"], AstNode::ConstDecl { .. }));
+    assert!(matches!(index["Vektor"], AstNode::TypeDecl { .. }));
+    assert!(matches!(index["p"], AstNode::ProcDecl { .. }));
+    assert!(matches!(index["a"], AstNode::VarDecl { names, .. } if names == &["a"]));
+    assert!(matches!(index["b"], AstNode::VarDecl { names, .. } if names == &["b"]));
+}
+
+#[test]
+fn nested_declarations_are_qualified_by_scope() {
+    let ast = analyze(SOURCE);
+    let index = ast.declarations_index();
+
+    assert!(matches!(index["p::x"], AstNode::ParamDecl { .. }));
+    assert!(matches!(index["p::b"], AstNode::VarDecl { data_type, .. } if data_type.to_string() == "char"));
+    assert!(matches!(index["p::q"], AstNode::FuncDecl { .. }));
+    assert!(matches!(index["p::q::c"], AstNode::VarDecl { .. }));
+    assert!(!index.contains_key("c"));
+    assert!(!std::ptr::eq(index["b"], index["p::b"]));
+}
+
+#[test]
+fn only_programs_and_subprograms_have_declarations() {
+    let ast = analyze(SOURCE);
+    let AstNode::Program { body, .. } = &ast else {
+        panic!("expected a program");
+    };
+
+    assert!(body.declarations_index().is_empty());
+    assert_eq!(ast.declarations_index().len(), 9);
+}
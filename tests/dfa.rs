@@ -0,0 +1,195 @@
+//! Loading and validation of the DFA rules, from files, strings and streams, and the
+//! priority among transition keys that match the same character.
+
+use cgk_tubes_if2224::{
+    dfa::{Dfa, DfaTable},
+    lexer::Lexer,
+};
+use serde_json::Value;
+
+fn rules_with_final_states(extra: &[(&str, &str)]) -> String {
+    let rules = std::fs::read_to_string("dfa_rules.json").expect("dfa_rules.json should exist");
+    let mut rules: Value = serde_json::from_str(&rules).expect("dfa_rules.json should be JSON");
+    for (state, token_type) in extra {
+        rules["final_states"][*state] = Value::from(*token_type);
+    }
+    rules.to_string()
+}
+
+#[test]
+fn shipped_rules_load() {
+    assert!(Dfa::from_json(&rules_with_final_states(&[])).is_ok());
+}
+
+#[test]
+fn unknown_token_types_are_listed() {
+    let rules = rules_with_final_states(&[("S_Bogus", "BOGUS"), ("S_Arrow", "ARROW")]);
+    let error = Dfa::from_json(&rules).expect_err("unknown token types should be rejected");
+
+    assert_eq!(
+        error.to_string(),
+        "Unknown token type in final_states: S_Arrow -> ARROW, S_Bogus -> BOGUS"
+    );
+}
+
+#[test]
+fn reversed_ranges_are_rejected() {
+    let mut rules: Value = serde_json::from_str(&rules_with_final_states(&[])).unwrap();
+    let start = rules["start_state"].as_str().unwrap().to_string();
+    rules["transitions"][&start]["z-a"] = Value::from(start.clone());
+    let error = Dfa::from_json(&rules.to_string()).expect_err("reversed ranges should be rejected");
+
+    assert_eq!(error.to_string(), format!("Reversed range in transitions: {} on z-a", start));
+}
+
+#[test]
+fn reversed_ranges_never_match() {
+    // Rules built in code skip the validation of from_json
+    let shipped = Dfa::from_json(&rules_with_final_states(&[])).unwrap();
+    let mut reversed = shipped.clone();
+    let start = reversed.start_state.clone();
+    reversed.transitions.get_mut(&start).unwrap().insert("z-a".to_string(), start);
+
+    let (shipped, reversed) = (DfaTable::new(&shipped), DfaTable::new(&reversed));
+
+    for ch in ['a', 'm', 'z', '-', '0'] {
+        assert_eq!(reversed.next_state(reversed.start, ch), shipped.next_state(shipped.start, ch), "{:?}", ch);
+    }
+}
+
+fn rules_with_examples(examples: &[(&str, &str)]) -> String {
+    let mut rules: Value = serde_json::from_str(&rules_with_final_states(&[])).unwrap();
+    rules["examples"] = examples
+        .iter()
+        .map(|(source, token_type)| (source.to_string(), Value::from(*token_type)))
+        .collect();
+    rules.to_string()
+}
+
+#[test]
+fn shipped_examples_pass() {
+    let dfa = Dfa::from_json(&rules_with_final_states(&[])).unwrap();
+
+    assert!(!dfa.examples.is_empty());
+    assert_eq!(dfa.self_test(), Ok(()));
+}
+
+#[test]
+fn examples_are_optional() {
+    let mut rules: Value = serde_json::from_str(&rules_with_final_states(&[])).unwrap();
+    rules.as_object_mut().unwrap().remove("examples");
+    let dfa = Dfa::from_json(&rules.to_string()).unwrap();
+
+    assert!(dfa.examples.is_empty());
+    assert_eq!(dfa.self_test(), Ok(()));
+}
+
+#[test]
+fn failing_examples_are_listed() {
+    let rules = rules_with_examples(&[
+        (":=", "ASSIGN_OPERATOR"),
+        ("<>", "ASSIGN_OPERATOR"),
+        ("x y", "IDENTIFIER"),
+        ("#", "SEMICOLON"),
+    ]);
+    let failures = Dfa::from_json(&rules).unwrap().self_test().expect_err("examples should fail");
+
+    assert_eq!(
+        failures,
+        [
+            "\"#\": expected SEMICOLON, found an error: Invalid token near \"#\"",
+            "\"<>\": expected ASSIGN_OPERATOR, found RELATIONAL_OPERATOR",
+            "\"x y\": expected IDENTIFIER, found 2 tokens",
+        ]
+    );
+}
+
+/// Rules for lowercase identifiers and nothing else
+const TINY_RULES: &str = r#"{
+    "start_state": "S",
+    "keywords": ["mulai"],
+    "word_logical_operators": [],
+    "word_arithmetic_operators": [],
+    "word_relational_operators": [],
+    "final_states": { "S_Word": "IDENTIFIER" },
+    "transitions": {
+        "S": { "a-z": "S_Word" },
+        "S_Word": { "a-z": "S_Word" }
+    }
+}"#;
+
+#[test]
+fn tiny_rules_parse_from_a_string() {
+    let dfa: Dfa = TINY_RULES.parse().expect("tiny rules should load");
+    let mut lexer = Lexer::new("mulai".to_string(), dfa);
+
+    let token = lexer.get_next_token().expect("one token");
+    assert_eq!(token.to_string(), "KEYWORD(mulai)");
+    assert!(lexer.get_next_token().is_none());
+}
+
+#[test]
+fn rules_load_from_a_reader() {
+    let dfa = Dfa::from_reader(TINY_RULES.as_bytes()).expect("tiny rules should load");
+    let mut lexer = Lexer::new("abc".to_string(), dfa);
+
+    let token = lexer.get_next_token().expect("one token");
+    assert_eq!(token.to_string(), "IDENTIFIER(abc)");
+    assert!(Dfa::from_reader(&b"{"[..]).is_err());
+}
+
+/// Keys out of `S` that overlap: `b` is matched by the exact key, the set and all three
+/// ranges, `c` by the set and the ranges, `d` by `a-d` and `b-e`, which are equally wide,
+/// `x` by the set and `a-y`, and `z` only by `any`
+const OVERLAPPING_RULES: &str = r#"{
+    "start_state": "S",
+    "keywords": [],
+    "word_logical_operators": [],
+    "word_arithmetic_operators": [],
+    "word_relational_operators": [],
+    "final_states": {
+        "S_Exact": "IDENTIFIER",
+        "S_Set": "NUMBER",
+        "S_Narrow": "COLON",
+        "S_Tie": "DOT",
+        "S_Wide": "COMMA",
+        "S_Any": "SEMICOLON"
+    },
+    "transitions": {
+        "S": {
+            "b": "S_Exact",
+            "bcx": "S_Set",
+            "a-d": "S_Narrow",
+            "b-e": "S_Tie",
+            "a-y": "S_Wide",
+            "any": "S_Any"
+        }
+    }
+}"#;
+
+/// Token type of the state reached from the start on each character
+fn targets(table: &DfaTable, chars: &str) -> Vec<String> {
+    chars
+        .chars()
+        .map(|ch| {
+            let state = table.next_state(table.start, ch).expect("every character has a transition");
+            table.final_type(state).unwrap_or("-").to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn overlapping_keys_follow_the_documented_priority() {
+    let dfa = Dfa::from_json(OVERLAPPING_RULES).expect("overlapping rules should load");
+
+    // Exact key, then the narrowest class, the key listed first among equally narrow
+    // ones, and `any` last
+    let expected = ["IDENTIFIER", "NUMBER", "COLON", "COLON", "NUMBER", "DOT", "SEMICOLON"];
+    // Every table is built from the rules' HashMaps afresh, so an order-dependent
+    // choice would not hold across all of them
+    for _ in 0..50 {
+        let dfa = Dfa::from_json(OVERLAPPING_RULES).unwrap();
+        assert_eq!(targets(&DfaTable::new(&dfa), "bcdaxez"), expected);
+    }
+    assert_eq!(targets(&DfaTable::new(&dfa), "bcdaxez"), expected);
+}
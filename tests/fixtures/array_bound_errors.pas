@@ -0,0 +1,9 @@
+program BatasSalah;
+variabel
+  suhu : larik[-5..5] dari real;
+  huruf : larik['a'..'z'] dari integer;
+  campur : larik[1..'z'] dari integer;
+  pecahan : larik[0.5..2.5] dari integer;
+mulai
+  writeln(huruf[1], suhu[6], suhu['a'])
+selesai.
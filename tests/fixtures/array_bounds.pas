@@ -0,0 +1,13 @@
+program Batas;
+tipe
+  Titik = rekaman
+    x, y : integer;
+  selesai;
+variabel
+  suhu : larik[-5..5] dari real;
+  huruf : larik['a'..'z'] dari integer;
+  jalur : larik[1..3] dari Titik;
+mulai
+  writeln(suhu[-5], huruf['c'], suhu[0]);
+  writeln(huruf['z'], suhu[5])
+selesai.
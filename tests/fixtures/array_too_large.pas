@@ -0,0 +1,14 @@
+{ flags: --int-width 64 }
+program LarikBesar;
+tipe
+  Titik = rekaman
+    x, y, z : integer;
+  selesai;
+variabel
+  a : larik[1..1000000000] dari Titik;
+  b : larik[-2147483648..2147483647] dari integer;
+  c : larik[1..10000000000] dari integer;
+  d : larik[1..4096, 1..4096] dari integer;
+  e : larik[1..4097, 1..4096] dari integer;
+mulai
+selesai.
@@ -0,0 +1,13 @@
+{ flags: --dump-ast json }
+program Json;
+variabel
+  n : integer;
+  x : real;
+  c : char;
+mulai
+  n := -3 + 4;
+  x := 2.5;
+  c := 'a';
+  jika n > 0 maka
+    writeln('positif', x)
+selesai.
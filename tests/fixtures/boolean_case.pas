@@ -0,0 +1,14 @@
+{ flags: --warn-non-exhaustive }
+program KasusLogika;
+variabel
+  b : boolean;
+mulai
+  b := benar;
+  kasus b dari
+    benar: writeln('ya')
+  selesai;
+  kasus b dari
+    benar: writeln('ya');
+    salah: writeln('tidak')
+  selesai
+selesai.
@@ -0,0 +1,18 @@
+program Bawaan;
+variabel
+  n : integer;
+  x : real;
+  c : char;
+  b : boolean;
+mulai
+  n := abs(-3) + sqr(n);
+  x := sqr(x) + sqrt(n);
+  c := chr(ord('a') + 1);
+  c := succ(c);
+  b := odd(n);
+  n := abs(2.5);
+  x := sqrt(1, 2);
+  c := chr();
+  b := odd(x);
+  writeln()
+selesai.
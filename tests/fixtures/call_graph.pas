@@ -0,0 +1,35 @@
+{ flags: --callgraph user }
+program Panggilan;
+variabel
+  hasil : integer;
+
+fungsi nol : integer;
+mulai
+  nol := 0
+selesai;
+
+fungsi fib(n : integer) : integer;
+mulai
+  jika n < 2 maka
+    fib := n
+  selain_itu
+    fib := fib(n - 1) + fib(n - 2)
+selesai;
+
+prosedur hitung_mundur(n : integer);
+
+  prosedur langkah(k : integer);
+  mulai
+    writeln(k);
+    hitung_mundur(k - 1)
+  selesai;
+
+mulai
+  jika n > nol maka
+    langkah(n)
+selesai;
+
+mulai
+  hasil := fib(10);
+  hitung_mundur(hasil)
+selesai.
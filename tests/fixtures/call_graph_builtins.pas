@@ -0,0 +1,15 @@
+{ flags: --callgraph all }
+program PanggilanBawaan;
+variabel
+  x : real;
+
+prosedur cetak(v : real);
+mulai
+  writeln(round(sqrt(abs(v))))
+selesai;
+
+mulai
+  x := 2.5;
+  cetak(x);
+  writeln(trunc(x))
+selesai.
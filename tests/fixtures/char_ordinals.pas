@@ -0,0 +1,14 @@
+{ flags: --char-ordinals }
+program Ordinal;
+konstanta
+  sebelum = 'A' < 66;
+variabel
+  c : char;
+  hasil : boolean;
+mulai
+  c := 'B';
+  hasil := c > 65;
+  hasil := 90 >= c;
+  jika c = 66 maka
+    writeln(sebelum)
+selesai.
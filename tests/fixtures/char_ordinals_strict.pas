@@ -0,0 +1,8 @@
+program OrdinalKetat;
+variabel
+  c : char;
+  hasil : boolean;
+mulai
+  c := 'B';
+  hasil := c > 65
+selesai.
@@ -0,0 +1,28 @@
+program Bersih;
+
+konstanta
+  N = 3;
+
+variabel
+  i, total : integer;
+  data : larik[1..N] dari integer;
+
+prosedur isi(nilai : integer);
+mulai
+  total := total + nilai
+selesai;
+
+mulai
+  total := 0;
+  untuk i := 1 ke N lakukan
+  mulai
+    isi(i * 2);
+    isi(data[i])
+  selesai;
+  jika total > 10 maka
+    writeln('besar')
+  selain_itu jika total > 5 maka
+    writeln('sedang')
+  selain_itu
+    writeln('kecil')
+selesai.
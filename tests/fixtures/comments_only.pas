@@ -0,0 +1,2 @@
+{ hanya komentar }
+(* tidak ada program di sini *)
@@ -0,0 +1,14 @@
+{ flags: --complete-boolean }
+program Pintas;
+konstanta
+  nol = 0;
+  aman = (nol > 0) dan (10 bagi nol > 1);
+  juga = (nol = 0) atau (10 mod nol = 1);
+  perlu = (nol = 0) dan (10 bagi nol > 1);
+variabel
+  i : integer;
+konstanta
+  bukan = salah dan (i > 0);
+mulai
+  i := 1
+selesai.
@@ -0,0 +1,20 @@
+program Banding;
+tipe
+  Vektor = larik[1 .. 3] dari integer;
+  Titik = rekaman x, y : integer; selesai;
+variabel
+  a, b : Vektor;
+  c : larik[1 .. 3] dari integer;
+  p, q : Titik;
+  r : rekaman x, y : integer; selesai;
+  s : larik[1 .. 5] dari char;
+  hasil : boolean;
+mulai
+  hasil := a = b;
+  hasil := a <> c;
+  hasil := p = q;
+  hasil := p <> r;
+  hasil := s = 'halo!';
+  hasil := s < 'zzzzz';
+  writeln(hasil)
+selesai.
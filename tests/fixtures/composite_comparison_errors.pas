@@ -0,0 +1,16 @@
+program BandingSalah;
+tipe
+  Titik = rekaman x, y : integer; selesai;
+variabel
+  a, b : larik[1 .. 3] dari integer;
+  d : larik[0 .. 2] dari integer;
+  p, q : Titik;
+  u : rekaman y, x : integer; selesai;
+  hasil : boolean;
+mulai
+  hasil := a = d;
+  hasil := a < b;
+  hasil := p >= q;
+  hasil := p = u;
+  hasil := a = 1
+selesai.
@@ -0,0 +1,21 @@
+program CampurTipe;
+tipe
+  Titik = rekaman
+    x, y : integer;
+  selesai;
+variabel
+  a : larik[1..3] dari integer;
+  nama : larik[1..4] dari char;
+  p : Titik;
+  i : integer;
+  r : real;
+  c : char;
+mulai
+  i := a;
+  a := i;
+  p := 1.5;
+  r := p;
+  c := nama;
+  nama := 'abc';
+  i := a[1] + p.x
+selesai.
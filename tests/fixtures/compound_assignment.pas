@@ -0,0 +1,20 @@
+program Gabung;
+tipe
+  Titik = rekaman
+    x, y : integer;
+  selesai;
+variabel
+  i : integer;
+  r : real;
+  a : larik[1..3] dari integer;
+  p : Titik;
+mulai
+  i := 1;
+  i += 2;
+  i -= 1 - 1;
+  i *= i + 1;
+  r := 10;
+  r /= 4;
+  a[i] += 1;
+  p.x -= a[1]
+selesai.
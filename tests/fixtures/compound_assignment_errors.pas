@@ -0,0 +1,10 @@
+program GabungSalah;
+variabel
+  i : integer;
+  b : boolean;
+  s : string;
+mulai
+  i /= 2;
+  b += 1;
+  s += 'x'
+selesai.
@@ -0,0 +1,7 @@
+program UlangGabung;
+variabel
+  i : integer;
+mulai
+  untuk i += 1 ke 10 lakukan
+    writeln(i)
+selesai.
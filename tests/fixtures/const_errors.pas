@@ -0,0 +1,27 @@
+{ flags: --int-width 64 }
+program ConstErrors;
+konstanta
+  nol = 0;
+  bagiNol = 10 bagi nol;
+  besar = 9223372036854775807 + 1;
+  raksasa = 100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000.0;
+  jauh = trunc(raksasa);
+  bukanAngka = round(raksasa * raksasa - raksasa * raksasa);
+variabel
+  k : integer;
+  a : larik[1 .. k] dari integer;
+  b : larik[1 .. bagiNol] dari integer;
+  c : larik[1 .. 10 / 0] dari integer;
+fungsi f : integer;
+mulai
+  f := 1
+selesai;
+konstanta
+  dariFungsi = f;
+mulai
+  kasus k dari
+    k: writeln(1);
+    f: writeln(2);
+    abs(nol - 3): writeln(3)
+  selesai
+selesai.
@@ -0,0 +1,20 @@
+program ConstExpressions;
+konstanta
+  n = 4;
+  dobel = n * 2 + 1;
+  huruf = chr(ord('a') + 2);
+  aktif = (n > 3) dan tidak salah;
+  setengah = dobel / 2;
+variabel
+  a : larik[1 .. dobel] dari integer;
+  b : larik['a' .. huruf] dari integer;
+  c : larik[pred(n) .. sqr(n) mod 7 + 3] dari char;
+  k : integer;
+mulai
+  kasus k dari
+    n - 1: writeln(huruf);
+    n bagi 2 * 3: writeln(aktif, setengah)
+  selesai;
+  b[huruf] := a[dobel];
+  writeln(c[5])
+selesai.
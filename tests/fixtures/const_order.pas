@@ -0,0 +1,12 @@
+program UrutanKonstanta;
+konstanta
+  a = a;
+  b = c + 1;
+  c = 2;
+  d = c * 2;
+  e : integer = -e;
+variabel
+  x : integer;
+mulai
+  x := d
+selesai.
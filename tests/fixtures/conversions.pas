@@ -0,0 +1,13 @@
+{ flags: --warn-widening }
+program Konversi;
+variabel
+  n : integer;
+  x : real;
+mulai
+  x := 7 / 2;
+  n := round(x);
+  n := trunc(x) + 1;
+  x := n;
+  n := x * 2;
+  n := round(x, 1)
+selesai.
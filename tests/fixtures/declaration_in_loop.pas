@@ -0,0 +1,6 @@
+program DeklarasiDiLoop;
+variabel
+  x : boolean;
+mulai
+  selama x lakukan variabel y: integer;
+selesai.
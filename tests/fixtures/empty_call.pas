@@ -0,0 +1,16 @@
+program KosongArgumen;
+variabel
+  n : integer;
+  b : boolean;
+
+fungsi tiga: integer;
+mulai
+  tiga := 3
+selesai;
+
+mulai
+  n := tiga();
+  b := eof();
+  writeln();
+  n := abs()
+selesai.
@@ -0,0 +1,18 @@
+program LoopKosong;
+konstanta
+  Batas = 10;
+variabel
+  i, n : integer;
+mulai
+  n := 5;
+  untuk i := 10 ke 1 lakukan
+    writeln(i);
+  untuk i := 1 turun_ke Batas lakukan
+    writeln(i);
+  untuk i := 1 ke 1 lakukan
+    writeln(i);
+  untuk i := Batas turun_ke 1 lakukan
+    writeln(i);
+  untuk i := n ke 1 lakukan
+    writeln(i)
+selesai.
@@ -0,0 +1,15 @@
+program Pemulihan;
+variabel
+  a, b : integer;
+  ok : boolean;
+  data : larik[1..5] dari integer;
+
+mulai
+  a := (b + 'x') * 2 + b bagi 3 - 1;
+  ok := tidak (a + benar > 0) dan (b = 1);
+  jika (b + salah) * 2 > a maka
+    a := 0;
+  selama tidak (a + 'c' < 10) lakukan
+    a := a + 1;
+  data[b + benar] := a + (ok * 2)
+selesai.
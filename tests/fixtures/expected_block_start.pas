@@ -0,0 +1,6 @@
+program Awal;
+var
+  x : integer;
+begin
+  x := 1
+end.
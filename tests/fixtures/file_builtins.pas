@@ -0,0 +1,10 @@
+program Berkas;
+variabel
+  n : integer;
+  b : boolean;
+mulai
+  b := eof(input, output);
+  b := eoln(n);
+  n := eof;
+  writeln(output, n)
+selesai.
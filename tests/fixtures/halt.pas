@@ -0,0 +1,17 @@
+program Berhenti;
+variabel
+  i : integer;
+
+mulai
+  readln(i);
+  jika i < 0 maka
+    halt(1);
+  jika i = 0 maka
+    mulai
+      writeln(0);
+      selesai_program
+    selesai;
+  writeln(i);
+  halt;
+  writeln(i + 1)
+selesai.
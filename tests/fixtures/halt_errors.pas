@@ -0,0 +1,10 @@
+program BerhentiSalah;
+variabel
+  r : real;
+
+mulai
+  halt(1, 2);
+  jika r > 0 maka
+    halt(r);
+  selesai_program('a')
+selesai.
@@ -0,0 +1,22 @@
+{ flags: --warn-ignored-result }
+program HasilDibuang;
+variabel
+  x : integer;
+
+fungsi dua : integer;
+mulai
+  dua := 2
+selesai;
+
+fungsi kuadrat(n : integer) : integer;
+mulai
+  kuadrat := n * n
+selesai;
+
+mulai
+  x := kuadrat(dua);
+  kuadrat(x);
+  dua;
+  abs(x);
+  writeln(x)
+selesai.
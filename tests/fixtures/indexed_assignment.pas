@@ -0,0 +1,18 @@
+program IndeksAssign;
+tipe
+  Titik = rekaman
+    x, y : integer;
+  selesai;
+variabel
+  a : larik[1..10] dari integer;
+  m : larik[1..3, 1..3] dari real;
+  t : larik[1..4] dari Titik;
+  i : integer;
+mulai
+  i := 1;
+  a[i] := a[i] + 1;
+  a[a[i]] := 2;
+  m[1, 2] := 0.5;
+  m[2][3] := m[1, 2] * 2;
+  t[i].x := a[2]
+selesai.
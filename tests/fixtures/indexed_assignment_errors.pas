@@ -0,0 +1,10 @@
+program IndeksSalah;
+variabel
+  a : larik[1..10] dari integer;
+  c : char;
+mulai
+  a[3] := 1.5;
+  a[11] := 0;
+  a[c] := 1;
+  c[1] := 'x'
+selesai.
@@ -0,0 +1,14 @@
+{ flags: --int-width 16 }
+program Lebar16;
+konstanta
+  batas = 30000;
+  ganda = batas + batas;
+  minimum = -32767 - 1;
+variabel
+  a : integer;
+
+mulai
+  a := 32767;
+  a := 40000;
+  a := minimum
+selesai.
@@ -0,0 +1,11 @@
+program Lebar32;
+konstanta
+  batas = 30000;
+  ganda = batas + batas;
+variabel
+  a : integer;
+
+mulai
+  a := 40000;
+  a := ganda
+selesai.
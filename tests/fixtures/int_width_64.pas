@@ -0,0 +1,12 @@
+{ flags: --int-width 64 }
+program Lebar64;
+konstanta
+  besar = 3000000000;
+  ganda = besar * 2;
+variabel
+  a : integer;
+
+mulai
+  a := 5000000000;
+  a := ganda
+selesai.
@@ -0,0 +1,26 @@
+program LompatSalah;
+label 10, 20, 30, 10, 12345;
+variabel
+  i : integer;
+
+prosedur lain;
+mulai
+  pergi_ke 30;
+20:
+  writeln(1)
+selesai;
+
+mulai
+  i := 0;
+10:
+  i := i + 1;
+10:
+  pergi_ke 40;
+  pergi_ke 20;
+  pergi_ke 20;
+  jika i > 0 maka
+    mulai
+30:
+      writeln(i)
+    selesai
+selesai.
@@ -0,0 +1,32 @@
+program Lompat;
+label 10, 20, 99;
+variabel
+  i : integer;
+
+prosedur cari(n : integer);
+label 1;
+mulai
+  jika n < 0 maka
+    pergi_ke 1;
+  writeln(n);
+1:
+selesai;
+
+mulai
+  i := 0;
+10:
+  i := i + 1;
+  jika i < 5 maka
+    pergi_ke 10;
+  selama benar lakukan
+    mulai
+      jika i > 8 maka
+        pergi_ke 20;
+      i := i + 1
+    selesai;
+20:
+  cari(i);
+  pergi_ke 99;
+  writeln(i);
+99:
+selesai.
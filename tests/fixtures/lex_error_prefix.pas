@@ -0,0 +1,5 @@
+program Lex;
+variabel x : integer;
+mulai
+  x := 1 ?# 2
+selesai.
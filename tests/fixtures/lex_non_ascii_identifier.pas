@@ -0,0 +1,8 @@
+program Unicode;
+konstanta
+  sapaan = 'Selamat pagi, Günther'; { komentar boleh berisi é }
+variabel
+  café : integer;
+mulai
+  café := 1
+selesai.
@@ -0,0 +1,6 @@
+program P;
+variabel
+  n : integer;
+mulai
+  n := &B1012
+selesai.
@@ -0,0 +1,5 @@
+program Lex;
+variabel s : char;
+mulai
+  s := 'a
+selesai.
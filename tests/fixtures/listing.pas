@@ -0,0 +1,10 @@
+{ flags: --list }
+program Daftar;
+variabel
+  i, tak_terpakai : integer;
+	c : char;
+mulai
+  i := 'a';
+	c := i + salah;
+  tulis(i)
+selesai.
@@ -0,0 +1,5 @@
+{ flags: --list }
+program DaftarLeksikal;
+mulai
+  writeln('tidak ditutup)
+selesai.
@@ -0,0 +1,8 @@
+{ flags: --list }
+program DaftarSintaks;
+variabel
+  i : integer;
+mulai
+  i := 1;;
+  i := (2 + 
+selesai.
@@ -0,0 +1,18 @@
+program KontrolLoop;
+variabel
+  i, n : integer;
+mulai
+  n := 0;
+  untuk i := 1 ke 10 lakukan
+  mulai
+    jika i mod 2 = 0 maka lanjut;
+    jika i > 7 maka keluar;
+    n := n + i
+  selesai;
+  selama benar lakukan
+    keluar;
+  ulangi
+    n := n - 1;
+    jika n = 3 maka keluar
+  sampai n < 0
+selesai.
@@ -0,0 +1,16 @@
+program KontrolSalah;
+variabel
+  n : integer;
+
+prosedur cetak;
+mulai
+  keluar
+selesai;
+
+mulai
+  n := 1;
+  jika n > 0 maka lanjut;
+  ulangi
+    n := n + 1
+  sampai n
+selesai.
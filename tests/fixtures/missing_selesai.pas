@@ -0,0 +1,8 @@
+program Hilang;
+variabel x : integer;
+mulai
+  jika x > 0 maka
+  mulai
+    x := 1;
+  x := 2
+selesai.
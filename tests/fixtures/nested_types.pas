@@ -0,0 +1,21 @@
+program Bersarang;
+tipe
+  Titik = rekaman
+    x, y : integer;
+  selesai;
+  Poligon = rekaman
+    jumlah : integer;
+    sudut : larik[1..8] dari Titik;
+    nama : larik[1..4] dari char;
+  selesai;
+variabel
+  titik : larik[1..10] dari Titik;
+  p : Poligon;
+  n : integer;
+  c : char;
+mulai
+  p.jumlah := 3;
+  n := titik[2].x + p.sudut[1].y;
+  c := p.nama[1];
+  writeln(n, c)
+selesai.
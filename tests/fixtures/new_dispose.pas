@@ -0,0 +1,28 @@
+program Heap;
+tipe
+  PNode = ^Node;
+  Node = rekaman
+    value : integer;
+    next : PNode;
+  selesai;
+variabel
+  head : PNode;
+  count : ^integer;
+
+prosedur tambah(p : PNode);
+mulai
+  new(p^.next);
+  p^.next^.value := p^.value + 1
+selesai;
+
+mulai
+  new(head);
+  head^.value := 1;
+  tambah(head);
+  new(count);
+  count^ := head^.next^.value;
+  writeln(count^);
+  dispose(head^.next);
+  dispose(head);
+  dispose(count)
+selesai.
@@ -0,0 +1,18 @@
+program HeapSalah;
+tipe
+  PInt = ^integer;
+variabel
+  p : PInt;
+  n : integer;
+
+fungsi buat : PInt;
+mulai
+  buat := p
+selesai;
+
+mulai
+  new(n);
+  new(buat);
+  dispose(p, p);
+  dispose(p^)
+selesai.
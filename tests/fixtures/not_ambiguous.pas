@@ -0,0 +1,7 @@
+program NegasiAmbigu;
+variabel
+  a, b : integer;
+  hasil : boolean;
+mulai
+  hasil := tidak a = b
+selesai.
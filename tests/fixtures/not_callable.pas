@@ -0,0 +1,11 @@
+program BukanPanggilan;
+konstanta
+  n = 3;
+variabel
+  x, y : integer;
+mulai
+  x(1);
+  y := x(2) + 1;
+  n;
+  y := n(4)
+selesai.
@@ -0,0 +1,14 @@
+program Negasi;
+variabel
+  a, b : integer;
+  flag, hasil : boolean;
+mulai
+  a := 1;
+  b := 2;
+  flag := benar;
+  hasil := tidak flag;
+  hasil := tidak (a = b);
+  hasil := tidak flag dan (a < b);
+  hasil := (tidak flag) = salah;
+  hasil := flag = tidak hasil
+selesai.
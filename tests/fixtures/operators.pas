@@ -0,0 +1,15 @@
+program Operator;
+variabel
+  a, b, q, r : integer;
+  x : real;
+  p : boolean;
+mulai
+  a := 17;
+  b := 5;
+  q := a bagi b;
+  r := a mod b;
+  x := a / b;
+  p := (a <> b) atau tidak (q >= r) dan (x <= 3.4);
+  q := x bagi 2;
+  q := a / b
+selesai.
@@ -0,0 +1,19 @@
+program ParameterScope;
+
+prosedur cetak(x : integer; c, c : char);
+variabel
+  x : integer;
+mulai
+  writeln(x, c)
+selesai;
+
+fungsi dua(a : integer): integer;
+variabel
+  b, a : real;
+mulai
+  dua := a * 2
+selesai;
+
+mulai
+  cetak(dua(1), 'a', 'b')
+selesai.
@@ -0,0 +1,17 @@
+program Pointers;
+tipe
+  PNode = ^Node;
+  Node = rekaman
+    value : integer;
+    next : ^Node;
+  selesai;
+variabel
+  head, tail : PNode;
+  count : ^integer;
+  n : integer;
+mulai
+  tail := head;
+  count^ := 3;
+  n := count^ + 1;
+  writeln(n)
+selesai.
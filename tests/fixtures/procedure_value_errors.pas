@@ -0,0 +1,21 @@
+program NilaiProsedur;
+variabel
+  x : integer;
+
+prosedur cetak(n : integer);
+mulai
+  writeln(n)
+selesai;
+
+prosedur halo;
+mulai
+  writeln('halo')
+selesai;
+
+mulai
+  x := cetak(1);
+  x := halo + 1;
+  jika halo maka
+    x := 0;
+  x := writeln(x)
+selesai.
@@ -0,0 +1,10 @@
+program Radix;
+konstanta
+  izin = &O755;
+  topeng = &B1111;
+variabel
+  n : integer;
+mulai
+  n := izin + &o10 - &B101;
+  writeln(n, topeng, &b0)
+selesai.
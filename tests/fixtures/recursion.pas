@@ -0,0 +1,29 @@
+program Rekursi;
+variabel
+  hasil : integer;
+
+fungsi fib(n : integer) : integer;
+mulai
+  jika n < 2 maka
+    fib := n
+  selain_itu
+    fib := fib(n - 1) + fib(n - 2)
+selesai;
+
+prosedur hitung_mundur(n : integer);
+
+  prosedur langkah(k : integer);
+  mulai
+    writeln(k);
+    hitung_mundur(k - 1)
+  selesai;
+
+mulai
+  jika n > 0 maka
+    langkah(n)
+selesai;
+
+mulai
+  hasil := fib(10);
+  hitung_mundur(hasil)
+selesai.
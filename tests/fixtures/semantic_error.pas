@@ -0,0 +1,11 @@
+program Semantik;
+variabel
+  x : integer;
+  b : boolean;
+mulai
+  x := 'a';
+  b := x + 1;
+  y := 2;
+  jika x maka
+    writeln(x)
+selesai.
@@ -0,0 +1,14 @@
+program HimpunanSalah;
+variabel
+  s : himpunan dari integer;
+  r : himpunan dari real;
+  x : real;
+  c : char;
+  ada : boolean;
+mulai
+  s := [1, 'a'];
+  s := [1.5];
+  ada := c dalam s;
+  ada := 1 dalam x;
+  s := ['a'..'z']
+selesai.
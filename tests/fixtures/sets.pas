@@ -0,0 +1,20 @@
+program Himpunan;
+tipe
+  Huruf = himpunan dari char;
+variabel
+  ganjil : himpunan dari 1..10;
+  vokal : Huruf;
+  flag : himpunan dari boolean;
+  n : integer;
+  c : char;
+  ada : boolean;
+mulai
+  ganjil := [1, 3, 5..9];
+  vokal := ['a', 'e', 'i', 'o', 'u'];
+  flag := [];
+  n := 3;
+  c := 'e';
+  ada := n dalam ganjil;
+  jika (c dalam vokal) dan (n dalam [2..n]) maka
+    writeln(ada)
+selesai.
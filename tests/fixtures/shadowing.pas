@@ -0,0 +1,25 @@
+{ flags: --warn-shadow }
+program Bayangan;
+variabel
+  x, hitung : integer;
+
+prosedur ubah(x : integer);
+variabel
+  hitung : integer;
+mulai
+  hitung := x
+selesai;
+
+fungsi kali(n : integer) : integer;
+variabel
+  read : integer;
+mulai
+  read := n;
+  kali := read
+selesai;
+
+mulai
+  x := kali(1);
+  hitung := x;
+  ubah(x)
+selesai.
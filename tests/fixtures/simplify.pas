@@ -0,0 +1,38 @@
+{ flags: --simplify }
+program Sederhana;
+konstanta
+  debug = salah;
+  n = 10;
+label
+  1;
+variabel
+  i : integer;
+  ok : boolean;
+mulai
+  i := n * 2 + 1;
+  ok := (n > 5) dan tidak debug;
+  ok := i > n;
+  jika benar maka
+    writeln('selalu')
+  selain_itu
+    writeln('tidak pernah');
+  jika debug maka
+    writeln('debug');
+  jika n < 5 maka
+    i := 1
+  selain_itu jika n = 10 maka
+    i := 2
+  selain_itu jika i > 0 maka
+    i := 3
+  selain_itu
+    i := 4;
+  jika debug maka
+    i := 5
+  selain_itu jika i > 0 maka
+    i := 6;
+  selama salah lakukan
+    i := i + 1;
+  selama debug lakukan
+    1: i := 0;
+  pergi_ke 1
+selesai.
@@ -0,0 +1,18 @@
+program Ukuran;
+tipe
+  titik = rekaman
+    x, y : integer;
+  selesai;
+  garis = larik[1..2] dari titik;
+konstanta
+  n = ukuran(garis);
+variabel
+  g : garis;
+  buffer : larik[1..ukuran(titik)] dari char;
+  a, b, c : integer;
+
+mulai
+  a := ukuran(integer);
+  b := ukuran(g) + ukuran(g[1]) + ukuran(g[1].x);
+  c := n
+selesai.
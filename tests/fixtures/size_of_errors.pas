@@ -0,0 +1,17 @@
+program UkuranSalah;
+konstanta
+  n = 3;
+variabel
+  a : integer;
+
+prosedur p;
+mulai
+selesai;
+
+mulai
+  a := ukuran(n);
+  a := ukuran(p);
+  a := ukuran(a + 1);
+  a := ukuran(a, a);
+  a := ukuran(zzz)
+selesai.
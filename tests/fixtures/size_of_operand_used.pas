@@ -0,0 +1,6 @@
+program P;
+variabel r, x : integer;
+mulai
+  x := ukuran(r);
+  writeln(x)
+selesai.
@@ -0,0 +1,21 @@
+{ flags: --sourcemap }
+program Peta;
+tipe
+  titik = rekaman
+    x, y : integer;
+  selesai;
+variabel
+  p : titik;
+  jumlah : integer;
+
+fungsi tambah(a, b : integer) : integer;
+mulai
+  tambah := a + b
+selesai;
+
+mulai
+  p.x := 1;
+  p.y := 2;
+  jumlah := tambah(p.x, p.y);
+  writeln(jumlah)
+selesai.
@@ -0,0 +1,8 @@
+program Titik;
+variabel
+  x : integer;;
+;
+mulai
+  x := 1;
+  writeln(x)
+selesai.
@@ -0,0 +1,20 @@
+{ flags: --strict-decl-order }
+program Urut;
+konstanta
+  batas = 3;
+tipe
+  Angka = integer;
+variabel
+  n : Angka;
+prosedur tulis;
+mulai
+  writeln(n)
+selesai;
+fungsi dua : integer;
+mulai
+  dua := 2
+selesai;
+mulai
+  n := batas * dua;
+  tulis
+selesai.
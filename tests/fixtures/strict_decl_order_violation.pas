@@ -0,0 +1,9 @@
+{ flags: --strict-decl-order }
+program Acak;
+variabel
+  n : integer;
+konstanta
+  batas = 3;
+mulai
+  n := batas
+selesai.
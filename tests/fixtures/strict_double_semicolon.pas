@@ -0,0 +1,10 @@
+{ flags: --strict-semicolons }
+program Ketat;
+variabel
+  n : integer;
+mulai
+  ulangi
+    n := n + 1;;
+    writeln(n)
+  sampai n > 3
+selesai.
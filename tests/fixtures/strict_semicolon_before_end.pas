@@ -0,0 +1,8 @@
+{ flags: --strict-semicolons }
+program Ketat;
+variabel
+  n : integer;
+mulai
+  n := 1;
+  writeln(n);
+selesai.
@@ -0,0 +1,15 @@
+{ flags: --strict-semicolons }
+program Ketat;
+variabel
+  i, n : integer;
+mulai
+  n := 0;
+  untuk i := 1 ke 3 lakukan
+    n := n + i;
+  ulangi
+    n := n - 1;
+    writeln(n)
+  sampai n = 0;
+  mulai
+  selesai
+selesai.
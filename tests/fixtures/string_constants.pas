@@ -0,0 +1,20 @@
+program Konstanta;
+konstanta
+  Salam = 'halo dunia';
+  Bintang = '*';
+  Kutip = '''';
+  Sapa = 'apa''kabar';
+variabel
+  c : char;
+  s : larik[1..10] dari char;
+mulai
+  c := Bintang;
+  jika c = Bintang maka
+    writeln(Salam, Bintang);
+  write(Kutip, Sapa);
+  s := Salam;
+  kasus c dari
+    Bintang: writeln('bintang');
+    Kutip: writeln('kutip')
+  selesai
+selesai.
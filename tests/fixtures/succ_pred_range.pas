@@ -0,0 +1,19 @@
+{ flags: --int-width 16 }
+program Batas;
+konstanta
+  akhir = 32767;
+variabel
+  b : boolean;
+  c : char;
+  i : integer;
+mulai
+  b := succ(salah);
+  b := succ(benar);
+  b := pred(salah);
+  c := pred(chr(0));
+  c := succ('a');
+  i := succ(akhir);
+  i := pred(-32767 - 1);
+  i := succ(i);
+  b := pred(b)
+selesai.
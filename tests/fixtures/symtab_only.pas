@@ -0,0 +1,20 @@
+{ flags: --symtab-only }
+program Tabel;
+konstanta
+  n = 3;
+variabel
+  data : larik[1..n] dari integer;
+  total : integer;
+
+prosedur tambah(x : integer);
+variabel
+  lama : integer;
+mulai
+  lama := total;
+  total := lama + x
+selesai;
+
+mulai
+  total := 0;
+  tambah(data[1])
+selesai.
@@ -0,0 +1,17 @@
+{ flags: --symtab-only }
+program TabelSalah;
+variabel
+  a : integer;
+  b : boolean;
+
+prosedur p(x : integer);
+mulai
+  x := b
+selesai;
+
+variabel
+  c : char;
+mulai
+  a := c;
+  p(a)
+selesai.
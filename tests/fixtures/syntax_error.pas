@@ -0,0 +1,6 @@
+program Sintaks;
+variabel x : integer;
+mulai
+  selama x < 10 maka
+    x := x + 1
+selesai.
@@ -0,0 +1 @@
+program Terpotong;
@@ -0,0 +1,24 @@
+program TipeSalah;
+tipe
+  angka = integr;
+  daftar = larik[1..3] dari reel;
+  simpul = rekaman
+    isi : angka;
+    berikut : simpul2;
+  selesai;
+  simpul2 = ^simpul;
+variabel
+  a : angka;
+  b : Integer;
+  c : boolen;
+  d : teks;
+
+fungsi f(x : chr) : realx;
+mulai
+  f := 1
+selesai;
+
+mulai
+  a := 1;
+  b := 2
+selesai.
@@ -0,0 +1,33 @@
+program TakTerjangkau;
+label 10;
+variabel
+  i : integer;
+
+mulai
+  i := 0;
+  selama i < 10 lakukan
+    mulai
+      i := i + 1;
+      jika i = 5 maka
+        keluar;
+      writeln(i)
+    selesai;
+  selama i > 0 lakukan
+    mulai
+      i := i - 1;
+      lanjut;
+      writeln(i);
+      writeln(i)
+    selesai;
+  ulangi
+    mulai
+      keluar
+    selesai;
+    i := 1
+  sampai i > 0;
+  pergi_ke 10;
+  i := 2;
+10:
+  writeln(i);
+  pergi_ke 10
+selesai.
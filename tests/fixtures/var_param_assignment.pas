@@ -0,0 +1,25 @@
+program ParameterVariabel;
+variabel
+  a, b : integer;
+
+prosedur tukar(variabel x, y : integer);
+variabel
+  t : integer;
+mulai
+  t := x;
+  x := y;
+  y := t
+selesai;
+
+prosedur gandakan(n : integer; variabel hasil : integer);
+mulai
+  n := n * 2;
+  hasil := n
+selesai;
+
+mulai
+  a := 1;
+  b := 2;
+  tukar(a, b);
+  gandakan(a, b)
+selesai.
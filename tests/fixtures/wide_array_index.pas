@@ -0,0 +1,12 @@
+{ flags: --int-width 64 }
+program IndeksLebar;
+konstanta
+  Jauh = 4294967297;
+variabel
+  a : larik[1..3] dari integer;
+mulai
+  a[2] := 1;
+  a[4294967298] := 2;
+  a[Jauh] := 3;
+  a[-4294967295] := 4
+selesai.
@@ -0,0 +1,10 @@
+program CetakSalah;
+variabel
+  a : integer;
+  t : larik[1 .. 2] dari integer;
+mulai
+  a := 1;
+  writeln(a + 'x', t, a dan 1, ' ok');
+  write;
+  writeln(t[1], a)
+selesai.
@@ -0,0 +1,15 @@
+program Cetak;
+variabel
+  a, b : integer;
+  x : real;
+  c : char;
+mulai
+  writeln;
+  a := 1;
+  b := 2;
+  x := a / b;
+  c := 'z';
+  write(a + b, ' done');
+  writeln(a * b, c, x / 2, a < b, 'selesai');
+  writeln
+selesai.
@@ -0,0 +1,152 @@
+//! Keyword sets other than the default, spelled by the `keyword_roles` of the DFA rules.
+
+use cgk_tubes_if2224::{
+    CompileOptions, Status, compile, dfa::Dfa, keyword::KeywordSet, symbol_table::SymbolTable,
+};
+use serde_json::{Value, json};
+
+const ENGLISH_KEYWORDS: &[(&str, &str)] = &[
+    ("program", "PROGRAM"),
+    ("label", "LABEL"),
+    ("const", "CONST"),
+    ("type", "TYPE"),
+    ("var", "VAR"),
+    ("procedure", "PROCEDURE"),
+    ("function", "FUNCTION"),
+    ("begin", "BEGIN"),
+    ("end", "END"),
+    ("if", "IF"),
+    ("then", "THEN"),
+    ("else", "ELSE"),
+    ("while", "WHILE"),
+    ("do", "DO"),
+    ("for", "FOR"),
+    ("to", "TO"),
+    ("downto", "DOWNTO"),
+    ("repeat", "REPEAT"),
+    ("until", "UNTIL"),
+    ("case", "CASE"),
+    ("of", "OF"),
+    ("break", "BREAK"),
+    ("continue", "CONTINUE"),
+    ("goto", "GOTO"),
+    ("array", "ARRAY"),
+    ("record", "RECORD"),
+    ("set", "SET"),
+    ("integer", "INTEGER"),
+    ("real", "REAL"),
+    ("boolean", "BOOLEAN"),
+    ("char", "CHAR"),
+    ("true", "TRUE"),
+    ("false", "FALSE"),
+];
+
+const ENGLISH_OPERATORS: &[(&str, &str, &str)] = &[
+    ("word_logical_operators", "and", "AND"),
+    ("word_logical_operators", "or", "OR"),
+    ("word_logical_operators", "not", "NOT"),
+    ("word_arithmetic_operators", "div", "DIV"),
+    ("word_arithmetic_operators", "mod", "MOD"),
+    ("word_relational_operators", "in", "IN"),
+];
+
+/// The shipped rules with English keywords and word operators
+fn english_rules() -> Value {
+    let rules = std::fs::read_to_string("dfa_rules.json").expect("dfa_rules.json should exist");
+    let mut rules: Value = serde_json::from_str(&rules).expect("dfa_rules.json should be JSON");
+
+    let mut roles = serde_json::Map::new();
+    rules["keywords"] = ENGLISH_KEYWORDS.iter().map(|(spelling, _)| Value::from(*spelling)).collect();
+    for (spelling, role) in ENGLISH_KEYWORDS {
+        roles.insert(spelling.to_string(), Value::from(*role));
+    }
+    for list in ["word_logical_operators", "word_arithmetic_operators", "word_relational_operators"] {
+        rules[list] = json!([]);
+    }
+    for (list, spelling, role) in ENGLISH_OPERATORS {
+        rules[*list].as_array_mut().unwrap().push(Value::from(*spelling));
+        roles.insert(spelling.to_string(), Value::from(*role));
+    }
+    roles.insert("packed".to_string(), Value::from("PACKED"));
+    rules["keyword_roles"] = Value::Object(roles);
+    rules
+}
+
+#[test]
+fn english_program_compiles() {
+    let dfa = Dfa::from_json(&english_rules().to_string()).expect("English rules should load");
+    let source = "program Sums;
+var total, i : integer; odd : boolean;
+begin
+  total := 0;
+  for i := 10 downto 1 do
+  begin
+    odd := not (i mod 2 = 0);
+    if odd and (i > 2) then total := total + i else total := total - i div 2
+  end;
+  repeat total := total - 1 until total <= 0
+end.
+";
+
+    let compilation = compile(source.to_string(), dfa, CompileOptions::default());
+
+    assert_eq!(compilation.status, Status::Success, "{}", compilation.report());
+}
+
+#[test]
+fn diagnostics_use_the_loaded_spellings() {
+    let dfa = Dfa::from_json(&english_rules().to_string()).unwrap();
+    let source = "program P; begin if true do writeln(1) end.";
+
+    let compilation = compile(source.to_string(), dfa, CompileOptions::default());
+
+    assert_eq!(compilation.status, Status::SyntaxError);
+    assert!(compilation.report().contains("Expected 'then' keyword."), "{}", compilation.report());
+}
+
+#[test]
+fn operator_errors_use_the_loaded_spellings() {
+    let dfa = Dfa::from_json(&english_rules().to_string()).unwrap();
+    let source = "program P; var i : integer; begin i := 7 div true; i := 7 mod 'a' end.";
+
+    let compilation = compile(source.to_string(), dfa, CompileOptions::default());
+    let report = compilation.report();
+
+    assert_eq!(compilation.status, Status::SemanticErrors(2));
+    assert!(report.contains("Invalid operation 'div' for types integer and boolean"), "{}", report);
+    assert!(report.contains("Invalid operation 'mod' for types integer and char"), "{}", report);
+}
+
+#[test]
+fn goto_and_case_diagnostics_use_the_loaded_spellings() {
+    let dfa = Dfa::from_json(&english_rules().to_string()).unwrap();
+    let source = "program P; label 1; var b : boolean;
+begin b := true; case b of true: writeln(1) end; goto 1 end.";
+    let options = CompileOptions { warn_non_exhaustive: true, ..CompileOptions::default() };
+
+    let compilation = compile(source.to_string(), dfa, options);
+    let report = compilation.report();
+
+    assert!(report.contains("Label 1 is the target of 'goto' but never defined"), "{}", report);
+    assert!(report.contains("no label for false and no 'else'"), "{}", report);
+}
+
+#[test]
+fn reserved_words_follow_the_rules() {
+    let dfa = Dfa::from_json(&english_rules().to_string()).unwrap();
+    let table = SymbolTable::with_keywords(&KeywordSet::from_dfa(&dfa));
+    let names: Vec<&str> = table.tab[..5].iter().map(|entry| entry.name.as_str()).collect();
+
+    assert_eq!(names, ["and", "array", "begin", "case", "const"]);
+    assert_eq!(table.tab[28].name, "packed");
+    assert!(table.lookup("mulai").is_none());
+}
+
+#[test]
+fn unknown_roles_are_rejected() {
+    let mut rules = english_rules();
+    rules["keyword_roles"]["loop"] = Value::from("LOOP");
+    let error = Dfa::from_json(&rules.to_string()).expect_err("unknown roles should be rejected");
+
+    assert_eq!(error.to_string(), "Unknown role in keyword_roles: loop -> LOOP");
+}
@@ -0,0 +1,173 @@
+//! Tokenizing of numbers next to dots: ranges, real literals and field access, pointer operators,
+//! the source positions recorded on tokens, the ASCII-only identifier policy and lexing a
+//! whole source at once.
+
+use cgk_tubes_if2224::{
+    dfa::Dfa,
+    lexer::{DEFAULT_MAX_TOKEN_LENGTH, Lexer},
+};
+
+/// Tokens of `source` in their compact `TYPE(value)` form
+fn tokens(source: &str) -> Vec<String> {
+    let dfa = Dfa::from_file("dfa_rules.json").expect("dfa_rules.json should load");
+    let mut lexer = Lexer::new(source.to_string(), dfa);
+    let tokens = std::iter::from_fn(|| lexer.get_next_token()).map(|token| token.to_string()).collect();
+    assert!(lexer.errors.is_empty(), "unexpected lex errors: {:?}", lexer.errors);
+    tokens
+}
+
+#[test]
+fn range_between_integers() {
+    assert_eq!(tokens("5..10"), ["NUMBER(5)", "RANGE_OPERATOR(..)", "NUMBER(10)"]);
+    assert_eq!(tokens("5 .. 10"), ["NUMBER(5)", "RANGE_OPERATOR(..)", "NUMBER(10)"]);
+}
+
+#[test]
+fn real_literals() {
+    assert_eq!(tokens("5.0"), ["NUMBER(5.0)"]);
+    assert_eq!(tokens("5. + .5"), ["NUMBER(5.)", "ARITHMETIC_OPERATOR(+)", "NUMBER(.5)"]);
+}
+
+#[test]
+fn field_access_keeps_its_dot() {
+    assert_eq!(tokens("a.b"), ["IDENTIFIER(a)", "DOT(.)", "IDENTIFIER(b)"]);
+    assert_eq!(
+        tokens("a[1].b"),
+        ["IDENTIFIER(a)", "LBRACKET([)", "NUMBER(1)", "RBRACKET(])", "DOT(.)", "IDENTIFIER(b)"]
+    );
+}
+
+#[test]
+fn pointer_operators() {
+    assert_eq!(tokens("p^"), ["IDENTIFIER(p)", "CARET(^)"]);
+    assert_eq!(tokens("@x"), ["AT(@)", "IDENTIFIER(x)"]);
+    assert_eq!(
+        tokens("p^.next^ := @x;"),
+        [
+            "IDENTIFIER(p)",
+            "CARET(^)",
+            "DOT(.)",
+            "IDENTIFIER(next)",
+            "CARET(^)",
+            "ASSIGN_OPERATOR(:=)",
+            "AT(@)",
+            "IDENTIFIER(x)",
+            "SEMICOLON(;)"
+        ]
+    );
+}
+
+#[test]
+fn offsets_count_bytes() {
+    let dfa = Dfa::from_file("dfa_rules.json").expect("dfa_rules.json should load");
+    let mut lexer = Lexer::new("s := 'é'; { ü }\n  t".to_string(), dfa);
+    let offsets: Vec<(String, usize, usize)> = std::iter::from_fn(|| lexer.get_next_token())
+        .map(|token| (token.value, token.column, token.offset))
+        .collect();
+
+    assert_eq!(
+        offsets,
+        [
+            ("s".to_string(), 1, 0),
+            (":=".to_string(), 3, 2),
+            ("'é'".to_string(), 6, 5),
+            (";".to_string(), 9, 9),
+            ("t".to_string(), 3, 20),
+        ]
+    );
+}
+
+#[test]
+fn lines_after_multi_line_comments() {
+    let dfa = Dfa::from_file("dfa_rules.json").expect("dfa_rules.json should load");
+    let source = "a { satu\n  dua\n  tiga } b\n(* empat\n lima *)\n   c";
+    let mut lexer = Lexer::new(source.to_string(), dfa);
+    let positions: Vec<(String, usize, usize)> = std::iter::from_fn(|| lexer.get_next_token())
+        .map(|token| (token.value, token.line, token.column))
+        .collect();
+
+    assert_eq!(
+        positions,
+        [("a".to_string(), 1, 1), ("b".to_string(), 3, 10), ("c".to_string(), 6, 4)]
+    );
+}
+
+/// Message of the first lex error in `source`
+fn lex_error(source: &str) -> String {
+    let dfa = Dfa::from_file("dfa_rules.json").expect("dfa_rules.json should load");
+    let mut lexer = Lexer::new(source.to_string(), dfa);
+    while lexer.get_next_token().is_some() {}
+    lexer.errors.first().expect("expected a lex error").message.clone()
+}
+
+#[test]
+fn accented_identifiers_are_rejected() {
+    assert_eq!(
+        lex_error("café := 1"),
+        "Non-ASCII letter 'é' in identifier \"café\" (identifiers are ASCII only)"
+    );
+    assert_eq!(
+        lex_error("x := élan"),
+        "Non-ASCII letter 'é' in identifier \"élan\" (identifiers are ASCII only)"
+    );
+}
+
+#[test]
+fn accents_allowed_in_strings_and_comments() {
+    assert_eq!(tokens("s := 'é' { ü }"), ["IDENTIFIER(s)", "ASSIGN_OPERATOR(:=)", "CHAR_LITERAL('é')"]);
+}
+
+#[test]
+fn overlong_tokens_are_skipped() {
+    let dfa = Dfa::from_file("dfa_rules.json").expect("dfa_rules.json should load");
+    let source = format!("x := {}; y", "a".repeat(DEFAULT_MAX_TOKEN_LENGTH + 1));
+    let mut lexer = Lexer::new(source, dfa);
+    let tokens: Vec<String> = std::iter::from_fn(|| lexer.get_next_token()).map(|token| token.to_string()).collect();
+
+    assert_eq!(tokens, ["IDENTIFIER(x)", "ASSIGN_OPERATOR(:=)", "SEMICOLON(;)", "IDENTIFIER(y)"]);
+    assert_eq!(lexer.errors.len(), 1);
+    assert_eq!(
+        lexer.errors[0].message,
+        "Token too long near \"aaaaaaaaaaaaaaaa...\" (4097 characters, at most 4096)"
+    );
+    assert_eq!((lexer.errors[0].line, lexer.errors[0].column), (1, 6));
+}
+
+#[test]
+fn token_length_limit_is_configurable() {
+    let dfa = Dfa::from_file("dfa_rules.json").expect("dfa_rules.json should load");
+    let mut lexer = Lexer::new("12345 'abcdef' abc { a long comment is no token }".to_string(), dfa);
+    lexer.max_token_length = 5;
+    let tokens: Vec<String> = std::iter::from_fn(|| lexer.get_next_token()).map(|token| token.to_string()).collect();
+
+    assert_eq!(tokens, ["NUMBER(12345)", "IDENTIFIER(abc)"]);
+    assert_eq!(lexer.errors.len(), 1);
+    assert_eq!(lexer.errors[0].prefix, "'abcdef' abc { a");
+}
+
+#[test]
+fn long_runs_of_over_long_tokens_are_skipped() {
+    let dfa = Dfa::from_file("dfa_rules.json").expect("dfa_rules.json should load");
+    let mut lexer = Lexer::new(format!("{}x", "abc ".repeat(100_000)), dfa);
+    lexer.max_token_length = 2;
+
+    let tokens: Vec<String> = lexer.tokenize_all().iter().map(|token| token.to_string()).collect();
+
+    assert_eq!(tokens, ["IDENTIFIER(x)"]);
+    assert_eq!(lexer.errors.len(), 100_000);
+}
+
+#[test]
+fn tokenize_all_matches_token_by_token() {
+    let dfa = Dfa::from_file("dfa_rules.json").expect("dfa_rules.json should load");
+    let source: String = (0..500)
+        .map(|i| format!("a{} := (b + {}.5e2) * 'c''{}' {{ {} }} (* x *) dalam [1..{}];\n", i, i, i, i, i))
+        .collect();
+
+    let all = Lexer::new(source.clone(), dfa.clone()).tokenize_all();
+    let mut lexer = Lexer::new(source, dfa);
+    let one_by_one: Vec<_> = std::iter::from_fn(|| lexer.get_next_token()).collect();
+
+    assert_eq!(all.len(), 500 * 17);
+    assert_eq!(format!("{:?}", all), format!("{:?}", one_by_one));
+}
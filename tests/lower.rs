@@ -0,0 +1,96 @@
+//! Lowering untuk loops into selama loops.
+
+mod common;
+
+use cgk_tubes_if2224::{
+    ast::AstNode, lower::lower_for_loops, operator::Operator, semantic_analyzer::SemanticAnalyzer,
+};
+use common::parse;
+
+/// Decorated AST of `source` with its untuk loops lowered
+fn lowered(source: &str) -> AstNode {
+    let mut analyzer = SemanticAnalyzer::new();
+    let mut ast = analyzer.analyze(&parse(source)).expect("source should analyze");
+    lower_for_loops(&mut ast, &analyzer.symbol_table);
+    ast
+}
+
+fn analyzed(source: &str) -> AstNode {
+    SemanticAnalyzer::new().analyze(&parse(source)).expect("source should analyze")
+}
+
+/// Statements of the program body
+fn body_statements(ast: &AstNode) -> &[AstNode] {
+    let AstNode::Program { body, .. } = ast else {
+        panic!("expected a program");
+    };
+    let AstNode::Block { statements, .. } = body.as_ref() else {
+        panic!("expected a block");
+    };
+    statements
+}
+
+#[test]
+fn counting_up_matches_the_hand_written_while_loop() {
+    let lowered = lowered(
+        "program P; variabel i, n : integer;
+         mulai n := 3; untuk i := 1 ke n lakukan writeln(i) selesai.",
+    );
+    let expected = analyzed(
+        "program P; variabel i, n : integer;
+         mulai n := 3; mulai i := 1; selama i <= n lakukan mulai writeln(i); i := i + 1 selesai selesai selesai.",
+    );
+
+    assert_eq!(lowered.to_string(), expected.to_string());
+}
+
+#[test]
+fn counting_down_tests_with_ge_and_decrements() {
+    let ast = lowered("program P; variabel i : integer; mulai untuk i := 10 turun_ke 1 lakukan writeln(i) selesai.");
+    let [AstNode::Block { statements, .. }] = body_statements(&ast) else {
+        panic!("expected the loop to become one block, found {:?}", body_statements(&ast));
+    };
+    let [AstNode::Assign { target, .. }, AstNode::While { condition, body }] = statements.as_slice() else {
+        panic!("expected an assignment and a while loop, found {:?}", statements);
+    };
+    let AstNode::Block { statements: loop_body, .. } = body.as_ref() else {
+        panic!("expected a block as the loop body");
+    };
+    let [_, AstNode::Assign { value: step, .. }] = loop_body.as_slice() else {
+        panic!("expected the body and the step, found {:?}", loop_body);
+    };
+
+    let AstNode::Var { tab_index, .. } = target.as_ref() else {
+        panic!("expected the loop variable as the target");
+    };
+    let loop_variable = *tab_index;
+    assert!(
+        matches!(condition.as_ref(), AstNode::BinOp { op: Operator::Ge, left, .. }
+            if matches!(left.as_ref(), AstNode::Var { tab_index, .. } if *tab_index == loop_variable))
+    );
+    assert!(
+        matches!(step.as_ref(), AstNode::BinOp { op: Operator::Sub, left, .. }
+            if matches!(left.as_ref(), AstNode::Var { tab_index, .. } if *tab_index == loop_variable))
+    );
+}
+
+#[test]
+fn nested_loops_are_all_lowered() {
+    let ast = lowered(
+        "program P; variabel i, j : integer;
+         mulai untuk i := 1 ke 3 lakukan untuk j := 1 ke i lakukan writeln(i, j) selesai.",
+    );
+
+    assert!(!ast.to_string().contains("For("), "{}", ast);
+    assert_eq!(ast.to_string().matches("While").count(), 2, "{}", ast);
+}
+
+#[test]
+fn loop_continued_with_lanjut_is_kept() {
+    let ast = lowered(
+        "program P; variabel i : integer;
+         mulai untuk i := 1 ke 3 lakukan mulai jika i = 2 maka lanjut; writeln(i) selesai selesai.",
+    );
+
+    assert!(matches!(body_statements(&ast), [AstNode::For { .. }]), "{}", ast);
+}
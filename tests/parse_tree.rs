@@ -0,0 +1,122 @@
+//! Structural equality, cloning and size of parse trees.
+
+mod common;
+
+use cgk_tubes_if2224::{
+    node::{NodeType, ParseNode},
+    token::{Token, TokenType},
+};
+use common::parse;
+
+fn number(value: &str) -> ParseNode {
+    ParseNode::new_terminal(Token {
+        token_type: TokenType::Number,
+        value: value.to_string(),
+        line: 1,
+        column: 1,
+        offset: 0,
+        keyword: None,
+    })
+}
+
+#[test]
+fn layout_does_not_affect_equality() {
+    let compact = parse("program P; variabel x : integer; mulai x := 1 + 2 selesai.");
+    let spread = parse("program P;\nvariabel\n  x : integer;\nmulai\n  x := 1 + 2\nselesai.\n");
+
+    assert_eq!(compact, spread);
+}
+
+#[test]
+fn different_tokens_are_not_equal() {
+    let plus = parse("program P; variabel x : integer; mulai x := 1 + 2 selesai.");
+    let minus = parse("program P; variabel x : integer; mulai x := 1 - 2 selesai.");
+    let renamed = parse("program P; variabel y : integer; mulai y := 1 + 2 selesai.");
+
+    assert_ne!(plus, minus);
+    assert_ne!(plus, renamed);
+}
+
+#[test]
+fn cloned_subtree_can_be_rewritten_independently() {
+    let tree = parse("program P; variabel x : integer; mulai x := 1 selesai.");
+    let mut copy = tree.clone();
+    assert_eq!(tree, copy);
+
+    // Drop the declaration part's children from the copy only
+    copy.children[1].children.clear();
+    assert_ne!(tree, copy);
+    assert!(!tree.children[1].children.is_empty());
+}
+
+#[test]
+fn lone_node_has_count_and_depth_one() {
+    let leaf = number("1");
+
+    assert_eq!(leaf.node_count(), 1);
+    assert_eq!(leaf.max_depth(), 1);
+}
+
+#[test]
+fn count_and_depth_of_a_small_tree() {
+    // <term> with a bare factor on the left and a nested factor on the right:
+    //   term
+    //     factor -> 1
+    //     factor -> factor -> 2
+    let mut left = ParseNode::new(NodeType::Factor);
+    left.children.push(number("1"));
+    let mut inner = ParseNode::new(NodeType::Factor);
+    inner.children.push(number("2"));
+    let mut right = ParseNode::new(NodeType::Factor);
+    right.children.push(inner);
+
+    let mut term = ParseNode::new(NodeType::Term);
+    term.children.push(left);
+    term.children.push(right);
+
+    assert_eq!(term.node_count(), 6);
+    assert_eq!(term.max_depth(), 4);
+}
+
+/// First node of `node_type` in a depth-first walk
+fn find<'a>(node: &'a ParseNode, node_type: &NodeType) -> Option<&'a ParseNode> {
+    if &node.node_type == node_type {
+        return Some(node);
+    }
+    node.children.iter().find_map(|child| find(child, node_type))
+}
+
+#[test]
+fn field_chain_nests_one_node_per_field() {
+    let tree = parse(
+        "program P; tipe T = rekaman a : rekaman b : integer; selesai; selesai; \
+         variabel r : T; x : integer; mulai x := r.a.b selesai.",
+    );
+    let factor = find(&tree.children[2], &NodeType::Factor).expect("assignment has a factor");
+
+    // r.a.b is ((r).a).b, the outer node selects the last field
+    let outer = &factor.children[0];
+    assert_eq!(outer.node_type, NodeType::FieldAccess);
+    assert_eq!(outer.children[2].first_token().unwrap().value, "b");
+    let inner = &outer.children[0];
+    assert_eq!(inner.node_type, NodeType::FieldAccess);
+    assert_eq!(inner.children[2].first_token().unwrap().value, "a");
+    assert_eq!(inner.children[0].first_token().unwrap().value, "r");
+}
+
+#[test]
+fn compound_assignment_parses_as_its_expansion() {
+    let program = |statement: &str| {
+        format!("program P; variabel x : integer; a : larik[1..3] dari integer; mulai {} selesai.", statement)
+    };
+
+    for (sugar, expanded) in [
+        ("x += 1", "x := x + (1)"),
+        ("x -= 1 - 2", "x := x - (1 - 2)"),
+        ("x *= x + 1", "x := x * (x + 1)"),
+        ("x /= 2", "x := x / (2)"),
+        ("a[x] += 1", "a[x] := a[x] + (1)"),
+    ] {
+        assert_eq!(parse(&program(sugar)), parse(&program(expanded)), "{}", sugar);
+    }
+}
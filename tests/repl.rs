@@ -0,0 +1,58 @@
+//! Line-by-line analysis in the REPL, with declarations carried between lines.
+
+use cgk_tubes_if2224::{dfa::Dfa, repl::Repl};
+
+fn repl() -> Repl {
+    Repl::new(Dfa::from_file("dfa_rules.json").expect("dfa_rules.json should load"))
+}
+
+#[test]
+fn expressions_report_their_type() {
+    let mut repl = repl();
+
+    assert_eq!(repl.eval("1 + 2"), "integer\n");
+    assert_eq!(repl.eval("1 / 2"), "real\n");
+    assert_eq!(repl.eval("benar dan (3 > 2)"), "boolean\n");
+    assert_eq!(repl.eval("chr(65)"), "char\n");
+}
+
+#[test]
+fn declarations_persist_to_later_lines() {
+    let mut repl = repl();
+
+    assert!(repl.eval("variabel n : integer; x : real;").contains("VarDecl('x')"));
+    assert!(repl.eval("n := 3").starts_with("Assign('n' := 3)"));
+    assert_eq!(repl.eval("x * n"), "real\n");
+    assert_eq!(
+        repl.eval("n := x"),
+        "Semantic error at IDENTIFIER(n): Cannot assign a real value to an integer, use round or trunc\n"
+    );
+}
+
+#[test]
+fn errors_do_not_end_the_session() {
+    let mut repl = repl();
+
+    assert!(repl.eval("y + 1").contains("Undeclared identifier 'y'"));
+    assert!(repl.eval("1 +").starts_with("Syntax error"));
+    assert_eq!(repl.eval(""), "");
+    assert_eq!(repl.eval("ord('a')"), "integer\n");
+}
+
+#[test]
+fn procedure_calls_are_statements() {
+    let mut repl = repl();
+    repl.eval("variabel x : integer;");
+    repl.eval("prosedur q; mulai writeln(1) selesai;");
+
+    assert_eq!(repl.eval("writeln(x)"), "writeln(...) → predefined, tab_index:29\n");
+    assert!(repl.eval("q").starts_with("q(...), tab_index:"), "{}", repl.eval("q"));
+}
+
+#[test]
+fn procedure_in_an_expression_has_no_value() {
+    let mut repl = repl();
+    repl.eval("prosedur q; mulai writeln(1) selesai;");
+
+    assert!(repl.eval("1 + q").contains("Procedure 'q' has no return value"), "{}", repl.eval("1 + q"));
+}
@@ -0,0 +1,182 @@
+//! Randomized inputs through every phase: compiling must report errors, never panic.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use cgk_tubes_if2224::{CompileOptions, Status, compile, dfa::Dfa};
+
+const CASES: usize = 400;
+
+/// Words and symbols the random programs are built from
+const VOCABULARY: &[&str] = &[
+    "program", "variabel", "konstanta", "tipe", "label", "prosedur", "fungsi", "mulai", "selesai",
+    "jika", "maka", "selain_itu", "selama", "lakukan", "untuk", "ke", "turun_ke", "ulangi",
+    "sampai", "kasus", "dari", "keluar", "lanjut", "pergi_ke", "larik", "rekaman", "himpunan",
+    "padat", "string", "integer", "real", "boolean", "char", "benar", "salah", "dan", "atau",
+    "tidak", "bagi", "mod", "dalam", "writeln", "write", "readln", "ord", "chr", "succ", "x", "y",
+    "a", "f", "p", "r", "0", "1", "2147483647", "99999999999", "2.5", "1e400", "'c'", "'teks'",
+    ":=", "+=", "=", "<>", "<", "<=", ">", ">=", "+", "-", "*", "/", "(", ")", "[", "]", ";",
+    ":", ",", ".", "..", "^", "@", "{ c }",
+];
+
+/// Xorshift generator, seeded so a failure reproduces
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+}
+
+fn dfa() -> Dfa {
+    Dfa::from_file("dfa_rules.json").expect("dfa_rules.json should load")
+}
+
+/// Compile `source` with and without the optional passes, naming the input on a panic
+fn assert_no_panic(dfa: &Dfa, source: &str) {
+    let simplified = CompileOptions { simplify: true, ..CompileOptions::default() };
+    for options in [CompileOptions::default(), simplified] {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            compile(source.to_string(), dfa.clone(), options);
+        }));
+        assert!(result.is_ok(), "compiling panicked on input:\n{:?}", source);
+    }
+}
+
+/// Sources of the snapshot fixtures, the starting points for mutation
+fn fixture_sources() -> Vec<String> {
+    let mut paths: Vec<_> = std::fs::read_dir("tests/fixtures")
+        .expect("tests/fixtures should exist")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "pas"))
+        .collect();
+    paths.sort();
+    paths.iter().map(|path| std::fs::read_to_string(path).unwrap()).collect()
+}
+
+#[test]
+fn random_bytes_do_not_panic() {
+    let dfa = dfa();
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+
+    for _ in 0..CASES {
+        let length = rng.below(80);
+        let bytes: Vec<u8> = (0..length).map(|_| rng.next() as u8).collect();
+        assert_no_panic(&dfa, &String::from_utf8_lossy(&bytes));
+    }
+}
+
+#[test]
+fn random_word_sequences_do_not_panic() {
+    let dfa = dfa();
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..CASES {
+        let mut words = vec!["program", "P", ";"];
+        for _ in 0..rng.below(40) {
+            words.push(VOCABULARY[rng.below(VOCABULARY.len())]);
+        }
+        words.push(".");
+        assert_no_panic(&dfa, &words.join(" "));
+    }
+}
+
+#[test]
+fn mutated_fixtures_do_not_panic() {
+    let dfa = dfa();
+    let mut rng = Rng(0xd1b5_4a32_d192_ed03);
+
+    for source in fixture_sources() {
+        let words: Vec<&str> = source.split_whitespace().collect();
+        if words.is_empty() {
+            continue;
+        }
+        for _ in 0..CASES / 20 {
+            let mut mutated = words.clone();
+            for _ in 0..1 + rng.below(3) {
+                let at = rng.below(mutated.len());
+                match rng.below(3) {
+                    0 => {
+                        mutated.remove(at);
+                    }
+                    1 => mutated.insert(at, VOCABULARY[rng.below(VOCABULARY.len())]),
+                    _ => mutated[at] = VOCABULARY[rng.below(VOCABULARY.len())],
+                }
+                if mutated.is_empty() {
+                    break;
+                }
+            }
+            assert_no_panic(&dfa, &mutated.join(" "));
+        }
+    }
+}
+
+#[test]
+fn empty_statement_before_missing_until_ends_the_loop() {
+    let source = "program P; mulai ulangi a keluar selesai.";
+
+    let compilation = compile(source.to_string(), dfa(), CompileOptions::default());
+
+    assert_eq!(compilation.status, Status::SyntaxError);
+    assert!(compilation.report().contains("Expected 'sampai' keyword."), "{}", compilation.report());
+}
+
+/// Programs nesting expressions, statements and types `depth` levels deep
+fn deeply_nested(depth: usize) -> Vec<String> {
+    vec![
+        format!(
+            "program P; variabel x : integer; mulai x := {}1{} selesai.",
+            "(".repeat(depth),
+            ")".repeat(depth)
+        ),
+        format!("program P; variabel b : boolean; mulai b := {}benar selesai.", "tidak ".repeat(depth)),
+        format!("program P; mulai {}writeln(1){} selesai.", "mulai ".repeat(depth), " selesai".repeat(depth)),
+        format!("program P; variabel b : boolean; mulai {}writeln(1) selesai.", "jika b maka ".repeat(depth)),
+        format!("program P; variabel a : {}integer; mulai selesai.", "larik[1..1] dari ".repeat(depth)),
+    ]
+}
+
+#[test]
+fn deep_nesting_does_not_overflow() {
+    let dfa = dfa();
+
+    for depth in [90, 1000, 10000] {
+        for source in deeply_nested(depth) {
+            assert_no_panic(&dfa, &source);
+        }
+    }
+}
+
+#[test]
+fn nesting_within_the_limit_compiles() {
+    for source in deeply_nested(90) {
+        let compilation = compile(source, dfa(), CompileOptions::default());
+
+        assert_eq!(compilation.status, Status::Success, "{}", compilation.report());
+    }
+}
+
+#[test]
+fn nesting_past_the_limit_is_a_syntax_error() {
+    for source in deeply_nested(1000) {
+        let compilation = compile(source, dfa(), CompileOptions::default());
+
+        assert_eq!(compilation.status, Status::SyntaxError);
+        assert!(compilation.report().contains("Nesting is too deep"), "{}", compilation.report());
+    }
+}
+
+#[test]
+fn many_comments_do_not_overflow() {
+    let source = format!("program P; {} mulai selesai.", "{c}".repeat(10000));
+
+    let compilation = compile(source, dfa(), CompileOptions::default());
+
+    assert_eq!(compilation.status, Status::Success, "{}", compilation.report());
+}
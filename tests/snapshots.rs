@@ -0,0 +1,101 @@
+//! Compiles every `tests/fixtures/*.pas` and compares the full report with the
+//! `.expected` snapshot next to it. Run with `UPDATE_SNAPSHOTS=1` to rewrite them.
+//! A fixture whose first line is `{ flags: --warn-shadow }` is compiled with those options.
+
+use cgk_tubes_if2224::{
+    AstFormat, CompileOptions, call_graph::CallGraphScope, compile, dfa::Dfa, types::IntWidth,
+};
+use std::fs;
+use std::path::Path;
+
+fn options_for(source: &str) -> CompileOptions {
+    let mut options = CompileOptions::default();
+    let flags = source
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix("{ flags:"))
+        .and_then(|line| line.strip_suffix('}'))
+        .unwrap_or_default();
+
+    let mut flags = flags.split_whitespace();
+    while let Some(flag) = flags.next() {
+        match flag {
+            "--dump-ast" => match flags.next() {
+                Some("json") => options.ast_format = AstFormat::Json,
+                value => panic!("unknown --dump-ast format {:?}", value),
+            },
+            "--callgraph" => match flags.next() {
+                Some("user") => options.call_graph = Some(CallGraphScope::User),
+                Some("all") => options.call_graph = Some(CallGraphScope::All),
+                value => panic!("unknown --callgraph scope {:?}", value),
+            },
+            "--warn-shadow" => options.warn_shadow = true,
+            "--warn-widening" => options.warn_widening = true,
+            "--warn-ignored-result" => options.warn_ignored_result = true,
+            "--warn-non-exhaustive" => options.warn_non_exhaustive = true,
+            "--strict-semicolons" => options.strict_semicolons = true,
+            "--strict-decl-order" => options.strict_decl_order = true,
+            "--char-ordinals" => options.char_ordinals = true,
+            "--sourcemap" => options.source_map = true,
+            "--symtab-only" => options.symtab_only = true,
+            "--list" => options.listing = true,
+            "--simplify" => options.simplify = true,
+            "--complete-boolean" => options.short_circuit = false,
+            "--int-width" => match flags.next().and_then(IntWidth::from_bits) {
+                Some(width) => options.int_width = width,
+                None => panic!("--int-width expects 16, 32 or 64"),
+            },
+            _ => panic!("unknown fixture flag {}", flag),
+        }
+    }
+    options
+}
+
+fn render(source: &Path) -> String {
+    let dfa = Dfa::from_file("dfa_rules.json").expect("dfa_rules.json should load");
+    let source = fs::read_to_string(source).expect("fixture should be readable");
+    let options = options_for(&source);
+    compile(source, dfa, options).report()
+}
+
+#[test]
+fn fixtures_match_snapshots() {
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+    let mut fixtures: Vec<_> = fs::read_dir("tests/fixtures")
+        .expect("tests/fixtures should exist")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "pas"))
+        .collect();
+    fixtures.sort();
+    assert!(!fixtures.is_empty(), "no fixtures found");
+
+    let mut failures = Vec::new();
+    for fixture in &fixtures {
+        let actual = render(fixture);
+        let snapshot = fixture.with_extension("expected");
+
+        if update {
+            fs::write(&snapshot, &actual).unwrap();
+            continue;
+        }
+
+        let expected = fs::read_to_string(&snapshot).unwrap_or_default();
+        if actual != expected {
+            let line = actual
+                .lines()
+                .zip(expected.lines())
+                .position(|(a, e)| a != e)
+                .unwrap_or_else(|| actual.lines().count().min(expected.lines().count()));
+            failures.push(format!(
+                "{}: differs from {} at line {}\n  actual:   {:?}\n  expected: {:?}",
+                fixture.display(),
+                snapshot.display(),
+                line + 1,
+                actual.lines().nth(line).unwrap_or("<end>"),
+                expected.lines().nth(line).unwrap_or("<end>"),
+            ));
+        }
+    }
+
+    assert!(failures.is_empty(), "snapshot mismatches:\n{}", failures.join("\n"));
+}
@@ -0,0 +1,65 @@
+//! --tokens-out, --symtab-out and --ast-out: redirected sections leave the main output,
+//! the rest of the report stays where it was.
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Fresh directory for the output files of one test
+fn out_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("cgk-split-output-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn compile(fixture: &str, output: &PathBuf, extra: &[String]) {
+    let status = Command::new(env!("CARGO_BIN_EXE_CGK-Tubes-IF2224"))
+        .arg(format!("tests/fixtures/{}.pas", fixture))
+        .arg(output)
+        .args(extra)
+        .output()
+        .expect("compiler binary should run")
+        .status;
+    assert!(status.success());
+}
+
+#[test]
+fn redirected_sections_leave_the_main_output() {
+    let dir = out_dir("all");
+    compile("clean", &dir.join("full.txt"), &[]);
+    let paths = ["tokens.txt", "symtab.txt", "ast.txt"].map(|file| dir.join(file));
+    let extra: Vec<String> = ["--tokens-out", "--symtab-out", "--ast-out"]
+        .iter()
+        .zip(&paths)
+        .flat_map(|(flag, path)| [flag.to_string(), path.display().to_string()])
+        .collect();
+    compile("clean", &dir.join("rest.txt"), &extra);
+
+    let read = |path: &PathBuf| fs::read_to_string(path).unwrap();
+    let [tokens, symtab, ast] = paths.each_ref().map(read);
+    let rest = read(&dir.join("rest.txt"));
+
+    assert!(tokens.starts_with("---TOKENS---"));
+    assert!(symtab.starts_with("\n---SEMANTIC ANALYSIS---"));
+    assert!(ast.starts_with("\n---DECORATED AST---"));
+    assert!(rest.starts_with("\n---PARSE TREE---"));
+    // Putting the parts back in report order gives the undivided output
+    assert_eq!(tokens + &rest + &symtab + &ast, read(&dir.join("full.txt")));
+}
+
+#[test]
+fn sections_without_a_flag_stay_in_the_main_output() {
+    let dir = out_dir("ast");
+    compile("clean", &dir.join("full.txt"), &[]);
+    let ast_path = dir.join("ast.txt");
+    compile("clean", &dir.join("rest.txt"), &["--ast-out".to_string(), ast_path.display().to_string()]);
+
+    let full = fs::read_to_string(dir.join("full.txt")).unwrap();
+    let ast = fs::read_to_string(ast_path).unwrap();
+    let rest = fs::read_to_string(dir.join("rest.txt")).unwrap();
+
+    assert!(rest.contains("---TOKENS---") && rest.contains("---SEMANTIC ANALYSIS---"));
+    assert!(!rest.contains("---DECORATED AST---"));
+    assert_eq!(rest + &ast, full);
+}
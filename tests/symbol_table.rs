@@ -0,0 +1,62 @@
+//! Identifiers visible from a scope, as used by editor tooling.
+
+use cgk_tubes_if2224::{
+    symbol_table::{FIRST_USER_INDEX, SymbolTable, TabEntry},
+    types::{DataType, ObjectKind},
+};
+
+fn variable(name: &str, data_type: DataType) -> TabEntry {
+    TabEntry {
+        name: name.to_string(),
+        link: None,
+        obj: ObjectKind::Variable,
+        data_type,
+        ref_index: None,
+        normal: true,
+        level: 0,
+        address: 0,
+    }
+}
+
+fn names(table: &SymbolTable) -> Vec<&str> {
+    table.visible_identifiers().iter().map(|entry| entry.name.as_str()).collect()
+}
+
+#[test]
+fn predefined_identifiers_are_visible_in_an_empty_table() {
+    let table = SymbolTable::new();
+    let visible = names(&table);
+
+    assert_eq!(visible.len(), FIRST_USER_INDEX);
+    assert!(visible.contains(&"writeln"));
+    assert!(visible.contains(&"output"));
+}
+
+#[test]
+fn inner_declarations_come_first_and_hide_outer_ones() {
+    let mut table = SymbolTable::new();
+    table.insert(variable("x", DataType::Integer));
+    table.insert(variable("y", DataType::Integer));
+    table.enter_block();
+    table.insert(variable("x", DataType::Real));
+    table.insert(variable("z", DataType::Char));
+
+    let visible = table.visible_identifiers();
+    let user: Vec<(&str, &DataType)> = visible
+        .iter()
+        .take(3)
+        .map(|entry| (entry.name.as_str(), &entry.data_type))
+        .collect();
+    assert_eq!(
+        user,
+        [("x", &DataType::Real), ("z", &DataType::Char), ("y", &DataType::Integer)]
+    );
+    assert_eq!(visible.len(), FIRST_USER_INDEX + 3);
+
+    // Leaving the block uncovers the outer x again
+    table.exit_block();
+    let visible = table.visible_identifiers();
+    assert_eq!(visible[0].name, "x");
+    assert_eq!(visible[0].data_type, DataType::Integer);
+    assert!(!names(&table).contains(&"z"));
+}
@@ -0,0 +1,105 @@
+//! Field width and precision of write/writeln arguments: `expr:width` and `expr:width:prec`.
+
+mod common;
+
+use cgk_tubes_if2224::{
+    ast::{AstNode, LiteralValue},
+    semantic_analyzer::SemanticAnalyzer,
+};
+use common::parse;
+
+/// Arguments of the first call in the body of `source`, which must analyze cleanly
+fn call_args(source: &str) -> Vec<AstNode> {
+    let ast = SemanticAnalyzer::new().analyze(&parse(source)).expect("source should analyze");
+    let AstNode::Program { body, .. } = ast else {
+        panic!("expected a program");
+    };
+    let AstNode::Block { statements, .. } = *body else {
+        panic!("expected a block");
+    };
+    match statements.into_iter().next() {
+        Some(AstNode::ProcCall { args, .. }) => args,
+        other => panic!("expected a call, found {:?}", other),
+    }
+}
+
+/// Messages of the semantic errors in `source`
+fn errors_of(source: &str) -> Vec<String> {
+    match SemanticAnalyzer::new().analyze(&parse(source)) {
+        Ok(_) => Vec::new(),
+        Err(errors) => errors.iter().map(|error| error.message.clone()).collect(),
+    }
+}
+
+fn literal(node: &AstNode) -> &LiteralValue {
+    match node {
+        AstNode::Literal { value, .. } => value,
+        other => panic!("expected a literal, found {:?}", other),
+    }
+}
+
+fn integer(node: &AstNode) -> i64 {
+    match literal(node) {
+        LiteralValue::Integer(value) => *value,
+        other => panic!("expected an integer, found {}", other),
+    }
+}
+
+#[test]
+fn width_and_precision_are_attached_to_the_argument() {
+    let args = call_args("program P; mulai write(3.14159:8:2) selesai.");
+    let [AstNode::WriteArg { expr, width: Some(width), prec: Some(prec) }] = args.as_slice() else {
+        panic!("expected one formatted argument, found {:?}", args);
+    };
+
+    let text = literal(expr).write_text(Some(integer(width)), Some(integer(prec)));
+
+    assert_eq!(text, "    3.14");
+}
+
+#[test]
+fn width_alone_right_justifies() {
+    let args = call_args("program P; mulai writeln(42:5, 'ab':4, 7) selesai.");
+    let texts: Vec<String> = args
+        .iter()
+        .map(|arg| match arg {
+            AstNode::WriteArg { expr, width, prec: None } => {
+                literal(expr).write_text(width.as_deref().map(integer), None)
+            }
+            plain => literal(plain).write_text(None, None),
+        })
+        .collect();
+
+    assert_eq!(texts, ["   42", "  ab", "7"]);
+}
+
+#[test]
+fn width_and_precision_may_be_variables() {
+    let source = "program P; variabel w, d : integer; x : real; mulai w := 8; d := 2; x := 1.5; writeln(x:w:d) selesai.";
+
+    assert_eq!(errors_of(source), Vec::<String>::new());
+}
+
+#[test]
+fn width_and_precision_must_be_integers() {
+    let errors = errors_of("program P; mulai writeln(1.5:2.5, 1.5:3:'a') selesai.");
+
+    assert_eq!(
+        errors,
+        ["Type mismatch: expected integer, found real", "Type mismatch: expected integer, found char"]
+    );
+}
+
+#[test]
+fn precision_is_only_for_reals() {
+    let errors = errors_of("program P; mulai writeln(5:8:2) selesai.");
+
+    assert_eq!(errors, ["Type mismatch: expected real, found integer"]);
+}
+
+#[test]
+fn format_outside_write_is_rejected() {
+    let errors = errors_of("program P; variabel x : integer; mulai x := abs(3:2) selesai.");
+
+    assert_eq!(errors, ["Field width is only allowed in write and writeln, not in a call to 'abs'"]);
+}